@@ -0,0 +1,880 @@
+use crate::{
+    traits::{ChromBounds, ParseError, ValueBounds},
+    BedGraph, Coordinates, Genome, GenomeError, Strand,
+};
+use bedrs_derive::Coordinates;
+use flate2::read::ZlibDecoder;
+use getset::CopyGetters;
+use num_traits::ToPrimitive;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io::{self, Read, Seek, SeekFrom},
+    str::FromStr,
+    vec,
+};
+
+/// Magic number of bigWig files
+pub(crate) const BIGWIG_MAGIC: u32 = 0x888F_FC26;
+
+/// Magic number of bigBed files
+pub(crate) const BIGBED_MAGIC: u32 = 0x8789_F2EB;
+
+/// Magic number of the chromosome B+ tree
+const CHROM_TREE_MAGIC: u32 = 0x78CA_8C91;
+
+/// Magic number of the R-tree indexes
+const RTREE_MAGIC: u32 = 0x2468_ACE0;
+
+/// Size of the common header of bigWig and bigBed files
+const HEADER_SIZE: usize = 64;
+
+/// Largest buffer reserved up front for a decompressed block, as the
+/// declared buffer size is not trusted
+const MAX_BLOCK_CAPACITY: usize = 1 << 20;
+
+pub(crate) fn invalid_data(msg: &str) -> ParseError {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string()).into()
+}
+
+/// Converts a file position into the coordinate type
+pub(crate) fn position<T: ValueBounds>(value: u32, field: &'static str) -> Result<T, ParseError> {
+    T::from_u32(value).ok_or_else(|| ParseError::InvalidField {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// A cursor over the bytes of a file section in the file's byte order
+pub(crate) struct Bytes<'a> {
+    data: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Bytes<'a> {
+    pub(crate) fn new(data: &'a [u8], big_endian: bool) -> Self {
+        Self {
+            data,
+            pos: 0,
+            big_endian,
+        }
+    }
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + n)
+            .ok_or_else(|| invalid_data("truncated bigWig/bigBed section"))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(self.take(N)?);
+        if self.big_endian {
+            buf.reverse();
+        }
+        Ok(buf)
+    }
+    pub(crate) fn u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
+    }
+    pub(crate) fn u16(&mut self) -> Result<u16, ParseError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+    pub(crate) fn u32(&mut self) -> Result<u32, ParseError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+    pub(crate) fn u64(&mut self) -> Result<u64, ParseError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+    pub(crate) fn f32(&mut self) -> Result<f32, ParseError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+    fn f64(&mut self) -> Result<f64, ParseError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+    /// Reads a NUL-terminated string
+    pub(crate) fn c_str(&mut self) -> Result<&'a str, ParseError> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| invalid_data("unterminated string"))?;
+        self.pos += len + 1;
+        std::str::from_utf8(&rest[..len]).map_err(|_| invalid_data("invalid UTF-8 string"))
+    }
+}
+
+/// The summary statistics of a whole bigWig or bigBed file
+#[derive(Debug, Clone, Copy, PartialEq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct BbiSummary {
+    /// The number of bases with data
+    bases_covered: u64,
+    min: f64,
+    max: f64,
+    sum: f64,
+    sum_squares: f64,
+}
+
+impl BbiSummary {
+    /// The mean value over the covered bases
+    #[must_use]
+    pub fn mean(&self) -> Option<f64> {
+        self.bases_covered
+            .to_f64()
+            .filter(|&n| n > 0.0)
+            .map(|n| self.sum / n)
+    }
+}
+
+/// A summary record of a zoom level of a bigWig or bigBed file.
+///
+/// Zoom levels store precomputed statistics over fixed-size windows so
+/// that large regions can be displayed without reading the full
+/// resolution data.
+#[derive(Debug, Default, Clone, Copy, Coordinates, CopyGetters)]
+pub struct ZoomRecord<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    chr: C,
+    start: T,
+    end: T,
+    /// The number of bases with data
+    #[getset(get_copy = "pub")]
+    valid_count: u32,
+    #[getset(get_copy = "pub")]
+    min: f32,
+    #[getset(get_copy = "pub")]
+    max: f32,
+    #[getset(get_copy = "pub")]
+    sum: f32,
+    #[getset(get_copy = "pub")]
+    sum_squares: f32,
+}
+
+impl<C, T> ZoomRecord<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// The mean value over the bases with data
+    #[must_use]
+    pub fn mean(&self) -> Option<f64> {
+        if self.valid_count == 0 {
+            None
+        } else {
+            Some(f64::from(self.sum) / f64::from(self.valid_count))
+        }
+    }
+}
+
+impl<C, T> From<ZoomRecord<C, T>> for BedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Converts a zoom record to a [`BedGraph`] scored by its mean
+    fn from(record: ZoomRecord<C, T>) -> Self {
+        let score = record.mean().unwrap_or(0.0);
+        Self::new(record.chr, record.start, record.end, score)
+    }
+}
+
+/// A compressed block of data listed by an R-tree index
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Block {
+    offset: u64,
+    size: u64,
+}
+
+/// A region of a file expressed with chromosome ids
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Region {
+    chrom_ix: u32,
+    start: u32,
+    end: u32,
+}
+
+impl Region {
+    /// Returns whether a span on a chromosome overlaps the region
+    pub(crate) fn overlaps(&self, chrom_ix: u32, start: u32, end: u32) -> bool {
+        chrom_ix == self.chrom_ix && start < self.end && end > self.start
+    }
+
+    /// Returns whether a span between two chromosome positions overlaps
+    /// the region
+    fn overlaps_span(&self, first: (u32, u32), last: (u32, u32)) -> bool {
+        first < (self.chrom_ix, self.end) && last > (self.chrom_ix, self.start)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ZoomHeader {
+    reduction: u32,
+    index_offset: u64,
+}
+
+/// The shared structure of bigWig and bigBed files
+pub(crate) struct BbiFile<R> {
+    reader: R,
+    big_endian: bool,
+    version: u16,
+    field_count: u16,
+    defined_field_count: u16,
+    uncompress_buf_size: u32,
+    index_offset: u64,
+    zooms: Vec<ZoomHeader>,
+    chroms: Vec<(String, u32)>,
+    ids: HashMap<String, u32>,
+    summary: Option<BbiSummary>,
+    auto_sql: Option<String>,
+}
+
+impl<R: Read + Seek> BbiFile<R> {
+    /// Parses the header, chromosome tree, and summary of a file
+    pub(crate) fn open(reader: R, magic: u32) -> Result<Self, ParseError> {
+        let mut file = Self {
+            reader,
+            big_endian: false,
+            version: 0,
+            field_count: 0,
+            defined_field_count: 0,
+            uncompress_buf_size: 0,
+            index_offset: 0,
+            zooms: Vec::new(),
+            chroms: Vec::new(),
+            ids: HashMap::new(),
+            summary: None,
+            auto_sql: None,
+        };
+        let header = file.read_at(0, HEADER_SIZE)?;
+        let found = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        if found == magic {
+            file.big_endian = false;
+        } else if found.swap_bytes() == magic {
+            file.big_endian = true;
+        } else {
+            return Err(invalid_data(if magic == BIGWIG_MAGIC {
+                "not a bigWig file"
+            } else {
+                "not a bigBed file"
+            }));
+        }
+        let mut bytes = Bytes::new(&header[4..], file.big_endian);
+        file.version = bytes.u16()?;
+        let zoom_levels = bytes.u16()?;
+        let chrom_tree_offset = bytes.u64()?;
+        let _full_data_offset = bytes.u64()?;
+        file.index_offset = bytes.u64()?;
+        file.field_count = bytes.u16()?;
+        file.defined_field_count = bytes.u16()?;
+        let auto_sql_offset = bytes.u64()?;
+        let summary_offset = bytes.u64()?;
+        file.uncompress_buf_size = bytes.u32()?;
+
+        let zoom_data = file.read_at(HEADER_SIZE as u64, 24 * zoom_levels as usize)?;
+        let mut bytes = Bytes::new(&zoom_data, file.big_endian);
+        for _ in 0..zoom_levels {
+            let reduction = bytes.u32()?;
+            let _reserved = bytes.u32()?;
+            let _data_offset = bytes.u64()?;
+            let index_offset = bytes.u64()?;
+            file.zooms.push(ZoomHeader {
+                reduction,
+                index_offset,
+            });
+        }
+
+        if summary_offset > 0 {
+            let data = file.read_at(summary_offset, 40)?;
+            let mut bytes = Bytes::new(&data, file.big_endian);
+            file.summary = Some(BbiSummary {
+                bases_covered: bytes.u64()?,
+                min: bytes.f64()?,
+                max: bytes.f64()?,
+                sum: bytes.f64()?,
+                sum_squares: bytes.f64()?,
+            });
+        }
+        if auto_sql_offset > 0 {
+            file.auto_sql = Some(file.read_c_str(auto_sql_offset)?);
+        }
+        file.read_chrom_tree(chrom_tree_offset)?;
+        Ok(file)
+    }
+
+    /// Reads `len` bytes at an absolute offset.
+    ///
+    /// Lengths are read from the file, so the buffer grows with the bytes
+    /// actually read rather than being allocated up front.
+    fn read_at(&mut self, offset: u64, len: usize) -> Result<Vec<u8>, ParseError> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut data = Vec::with_capacity(len.min(MAX_BLOCK_CAPACITY));
+        (&mut self.reader).take(len as u64).read_to_end(&mut data)?;
+        if data.len() < len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(data)
+    }
+
+    /// Reads a NUL-terminated string at an absolute offset
+    fn read_c_str(&mut self, offset: u64) -> Result<String, ParseError> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut data = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            self.reader.read_exact(&mut byte)?;
+            if byte[0] == 0 {
+                break;
+            }
+            data.push(byte[0]);
+        }
+        String::from_utf8(data).map_err(|_| invalid_data("invalid UTF-8 string"))
+    }
+
+    fn read_chrom_tree(&mut self, offset: u64) -> Result<(), ParseError> {
+        let header = self.read_at(offset, 32)?;
+        let mut bytes = Bytes::new(&header, self.big_endian);
+        if bytes.u32()? != CHROM_TREE_MAGIC {
+            return Err(invalid_data("invalid chromosome tree"));
+        }
+        let _block_size = bytes.u32()?;
+        let key_size = bytes.u32()? as usize;
+        let _val_size = bytes.u32()?;
+        let mut chroms = Vec::new();
+        let mut nodes = vec![offset + 32];
+        let mut visited = HashSet::new();
+        while let Some(node) = nodes.pop() {
+            if !visited.insert(node) {
+                return Err(invalid_data("cyclic chromosome tree"));
+            }
+            let head = self.read_at(node, 4)?;
+            let mut bytes = Bytes::new(&head, self.big_endian);
+            let is_leaf = bytes.u8()? != 0;
+            let _reserved = bytes.u8()?;
+            let count = bytes.u16()? as usize;
+            let items = self.read_at(node + 4, count * (key_size + 8))?;
+            let mut bytes = Bytes::new(&items, self.big_endian);
+            for _ in 0..count {
+                let key = bytes.take(key_size)?;
+                let len = key.iter().position(|&b| b == 0).unwrap_or(key.len());
+                let name = String::from_utf8(key[..len].to_vec())
+                    .map_err(|_| invalid_data("invalid chromosome name"))?;
+                if is_leaf {
+                    let id = bytes.u32()?;
+                    let size = bytes.u32()?;
+                    chroms.push((id, name, size));
+                } else {
+                    nodes.push(bytes.u64()?);
+                }
+            }
+        }
+        chroms.sort_unstable();
+        for (expected, (id, name, size)) in chroms.into_iter().enumerate() {
+            if id as usize != expected {
+                return Err(invalid_data("chromosome ids are not contiguous"));
+            }
+            self.ids.insert(name.clone(), id);
+            self.chroms.push((name, size));
+        }
+        Ok(())
+    }
+
+    /// Lists the data blocks of an R-tree overlapping a region, or all
+    /// blocks if no region is given.
+    fn blocks(
+        &mut self,
+        index_offset: u64,
+        region: Option<&Region>,
+    ) -> Result<Vec<Block>, ParseError> {
+        let header = self.read_at(index_offset, 48)?;
+        if Bytes::new(&header, self.big_endian).u32()? != RTREE_MAGIC {
+            return Err(invalid_data("invalid R-tree index"));
+        }
+        let mut blocks = Vec::new();
+        let mut nodes = vec![index_offset + 48];
+        let mut visited = HashSet::new();
+        while let Some(node) = nodes.pop() {
+            if !visited.insert(node) {
+                return Err(invalid_data("cyclic R-tree index"));
+            }
+            let head = self.read_at(node, 4)?;
+            let mut bytes = Bytes::new(&head, self.big_endian);
+            let is_leaf = bytes.u8()? != 0;
+            let _reserved = bytes.u8()?;
+            let count = bytes.u16()? as usize;
+            let item_size = if is_leaf { 32 } else { 24 };
+            let items = self.read_at(node + 4, count * item_size)?;
+            let mut bytes = Bytes::new(&items, self.big_endian);
+            let mut children = Vec::new();
+            for _ in 0..count {
+                let first = (bytes.u32()?, bytes.u32()?);
+                let last = (bytes.u32()?, bytes.u32()?);
+                let offset = bytes.u64()?;
+                let size = if is_leaf { bytes.u64()? } else { 0 };
+                if region.is_some_and(|r| !r.overlaps_span(first, last)) {
+                    continue;
+                }
+                if is_leaf {
+                    blocks.push(Block { offset, size });
+                } else {
+                    children.push(offset);
+                }
+            }
+            // visit children in file order
+            nodes.extend(children.into_iter().rev());
+        }
+        Ok(blocks)
+    }
+
+    /// Reads and decompresses a data block.
+    ///
+    /// Fails if the block inflates past the declared buffer size.
+    fn read_block(&mut self, block: Block) -> Result<Vec<u8>, ParseError> {
+        let size = usize::try_from(block.size).map_err(|_| invalid_data("block too large"))?;
+        let raw = self.read_at(block.offset, size)?;
+        if self.uncompress_buf_size == 0 {
+            return Ok(raw);
+        }
+        let limit = u64::from(self.uncompress_buf_size);
+        let capacity = (self.uncompress_buf_size as usize).min(MAX_BLOCK_CAPACITY);
+        let mut data = Vec::with_capacity(capacity);
+        ZlibDecoder::new(raw.as_slice())
+            .take(limit + 1)
+            .read_to_end(&mut data)?;
+        if data.len() as u64 > limit {
+            return Err(invalid_data("block larger than the declared buffer size"));
+        }
+        Ok(data)
+    }
+
+    /// Lists the blocks of the full resolution data
+    pub(crate) fn data_blocks(
+        &mut self,
+        region: Option<&Region>,
+    ) -> Result<Vec<Block>, ParseError> {
+        self.blocks(self.index_offset, region)
+    }
+
+    /// Lists the blocks of a zoom level
+    pub(crate) fn zoom_blocks(
+        &mut self,
+        level: usize,
+        region: Option<&Region>,
+    ) -> Result<Vec<Block>, ParseError> {
+        let zoom = self
+            .zooms
+            .get(level)
+            .copied()
+            .ok_or(ParseError::InvalidField {
+                field: "zoom level",
+                value: level.to_string(),
+            })?;
+        self.blocks(zoom.index_offset, region)
+    }
+}
+
+impl<R> BbiFile<R> {
+    /// Converts an interval into a region of the file.
+    ///
+    /// Returns `None` if the chromosome is not present in the file.
+    pub(crate) fn region<I, C, T>(&self, iv: &I) -> Option<Region>
+    where
+        I: Coordinates<C, T>,
+        C: ChromBounds + Display,
+        T: ValueBounds,
+    {
+        let chrom_ix = *self.ids.get(&iv.chr().to_string())?;
+        let clamp = |value: T| {
+            value
+                .to_u32()
+                .unwrap_or(if value > T::zero() { u32::MAX } else { 0 })
+        };
+        Some(Region {
+            chrom_ix,
+            start: clamp(iv.start()),
+            end: clamp(iv.end()),
+        })
+    }
+
+    /// Parses the chromosome names into the chromosome type
+    pub(crate) fn parse_chroms<C: ChromBounds + FromStr>(&self) -> Result<Vec<C>, ParseError> {
+        self.chroms
+            .iter()
+            .map(|(name, _)| {
+                name.parse().map_err(|_| ParseError::InvalidField {
+                    field: "chr",
+                    value: name.clone(),
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn chrom_name(&self, chrom_ix: u32) -> Result<&str, ParseError> {
+        self.chroms
+            .get(chrom_ix as usize)
+            .map(|(name, _)| name.as_str())
+            .ok_or_else(|| invalid_data("unknown chromosome id"))
+    }
+
+    pub(crate) fn big_endian(&self) -> bool {
+        self.big_endian
+    }
+    pub(crate) fn version(&self) -> u16 {
+        self.version
+    }
+    pub(crate) fn field_count(&self) -> u16 {
+        self.field_count
+    }
+    pub(crate) fn defined_field_count(&self) -> u16 {
+        self.defined_field_count
+    }
+    pub(crate) fn summary(&self) -> Option<&BbiSummary> {
+        self.summary.as_ref()
+    }
+    pub(crate) fn auto_sql(&self) -> Option<&str> {
+        self.auto_sql.as_deref()
+    }
+    pub(crate) fn zoom_levels(&self) -> Vec<u32> {
+        self.zooms.iter().map(|zoom| zoom.reduction).collect()
+    }
+    pub(crate) fn chrom_sizes(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.chroms
+            .iter()
+            .map(|(name, size)| (name.as_str(), *size))
+    }
+
+    /// Builds a [`Genome`] from the chromosome sizes of the file
+    pub(crate) fn genome<C, T>(&self, chroms: &[C]) -> Result<Genome<C, T>, GenomeError>
+    where
+        C: ChromBounds + Display,
+        T: ValueBounds + Display,
+    {
+        let sizes = chroms
+            .iter()
+            .zip(&self.chroms)
+            .map(|(chr, (_, size))| Ok((chr.clone(), position(*size, "size")?)))
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Genome::from_sizes(sizes)
+    }
+}
+
+/// Decodes the records of a block overlapping an optional region
+pub(crate) type Decode<R, C, Item> =
+    fn(&BbiFile<R>, &[C], &[u8], Option<&Region>, &mut Vec<Item>) -> Result<(), ParseError>;
+
+/// An iterator over the records of a bigWig or bigBed file.
+///
+/// Blocks are read and decompressed lazily, one at a time.
+pub struct BbiIter<'a, R, C, Item> {
+    file: &'a mut BbiFile<R>,
+    chroms: &'a [C],
+    blocks: vec::IntoIter<Block>,
+    region: Option<Region>,
+    decode: Decode<R, C, Item>,
+    buffer: vec::IntoIter<Item>,
+}
+
+impl<'a, R, C, Item> BbiIter<'a, R, C, Item>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        file: &'a mut BbiFile<R>,
+        chroms: &'a [C],
+        blocks: Vec<Block>,
+        region: Option<Region>,
+        decode: Decode<R, C, Item>,
+    ) -> Self {
+        Self {
+            file,
+            chroms,
+            blocks: blocks.into_iter(),
+            region,
+            decode,
+            buffer: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<R, C, Item> Iterator for BbiIter<'_, R, C, Item>
+where
+    R: Read + Seek,
+{
+    type Item = Result<Item, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buffer.next() {
+                return Some(Ok(item));
+            }
+            let block = self.blocks.next()?;
+            let mut items = Vec::new();
+            let decoded = self.file.read_block(block).and_then(|data| {
+                (self.decode)(
+                    self.file,
+                    self.chroms,
+                    &data,
+                    self.region.as_ref(),
+                    &mut items,
+                )
+            });
+            if let Err(e) = decoded {
+                self.blocks = Vec::new().into_iter();
+                return Some(Err(e));
+            }
+            self.buffer = items.into_iter();
+        }
+    }
+}
+
+/// Decodes the summary records of a zoom level block
+pub(crate) fn decode_zoom<R, C, T>(
+    file: &BbiFile<R>,
+    chroms: &[C],
+    data: &[u8],
+    region: Option<&Region>,
+    out: &mut Vec<ZoomRecord<C, T>>,
+) -> Result<(), ParseError>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    let mut bytes = Bytes::new(data, file.big_endian);
+    while !bytes.is_empty() {
+        let chrom_ix = bytes.u32()?;
+        let start = bytes.u32()?;
+        let end = bytes.u32()?;
+        let record = (
+            bytes.u32()?,
+            bytes.f32()?,
+            bytes.f32()?,
+            bytes.f32()?,
+            bytes.f32()?,
+        );
+        if region.is_some_and(|r| !r.overlaps(chrom_ix, start, end)) {
+            continue;
+        }
+        let chr = chroms
+            .get(chrom_ix as usize)
+            .ok_or_else(|| invalid_data("unknown chromosome id"))?;
+        out.push(ZoomRecord {
+            chr: chr.clone(),
+            start: position(start, "start")?,
+            end: position(end, "end")?,
+            valid_count: record.0,
+            min: record.1,
+            max: record.2,
+            sum: record.3,
+            sum_squares: record.4,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::Write;
+
+    /// A block of records for [`encode`], listed by its span
+    pub(crate) struct TestBlock {
+        pub(crate) chrom_ix: u32,
+        pub(crate) start: u32,
+        pub(crate) end: u32,
+        pub(crate) data: Vec<u8>,
+    }
+
+    /// Encodes a zoom record
+    pub(crate) fn zoom_record(
+        chrom_ix: u32,
+        start: u32,
+        end: u32,
+        count: u32,
+        sum: f32,
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [chrom_ix, start, end, count] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [0.0f32, sum, sum, sum * sum] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+
+    fn push_rtree(out: &mut Vec<u8>, blocks: &[(u32, u32, u32, u64, u64)]) {
+        out.extend_from_slice(&RTREE_MAGIC.to_le_bytes());
+        out.extend_from_slice(&u32::try_from(blocks.len()).unwrap().to_le_bytes());
+        out.extend_from_slice(&(blocks.len() as u64).to_le_bytes());
+        let first = blocks.first().map_or((0, 0), |b| (b.0, b.1));
+        let last = blocks.last().map_or((0, 0), |b| (b.0, b.2));
+        for value in [first.0, first.1, last.0, last.1] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&0u64.to_le_bytes());
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&[1, 0]);
+        out.extend_from_slice(&u16::try_from(blocks.len()).unwrap().to_le_bytes());
+        for &(chrom_ix, start, end, offset, size) in blocks {
+            for value in [chrom_ix, start, chrom_ix, end] {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            out.extend_from_slice(&offset.to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+        }
+    }
+
+    fn push_blocks(out: &mut Vec<u8>, blocks: &[TestBlock]) -> Vec<(u32, u32, u32, u64, u64)> {
+        out.extend_from_slice(&(blocks.len() as u64).to_le_bytes());
+        blocks
+            .iter()
+            .map(|block| {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&block.data).unwrap();
+                let compressed = encoder.finish().unwrap();
+                let offset = out.len() as u64;
+                out.extend_from_slice(&compressed);
+                let size = compressed.len() as u64;
+                (block.chrom_ix, block.start, block.end, offset, size)
+            })
+            .collect()
+    }
+
+    /// Encodes a minimal little-endian bigWig or bigBed file
+    pub(crate) fn encode(
+        magic: u32,
+        chroms: &[(&str, u32)],
+        blocks: &[TestBlock],
+        zooms: &[(u32, Vec<TestBlock>)],
+        field_count: u16,
+        auto_sql: Option<&str>,
+    ) -> Vec<u8> {
+        let mut out = vec![0u8; HEADER_SIZE + 24 * zooms.len()];
+
+        let auto_sql_offset = auto_sql.map_or(0, |sql| {
+            let offset = out.len() as u64;
+            out.extend_from_slice(sql.as_bytes());
+            out.push(0);
+            offset
+        });
+
+        let summary_offset = out.len() as u64;
+        out.extend_from_slice(&10u64.to_le_bytes());
+        for value in [0.0f64, 5.0, 20.0, 60.0] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let chrom_tree_offset = out.len() as u64;
+        let key_size = chroms.iter().map(|(name, _)| name.len()).max().unwrap_or(1);
+        for value in [
+            CHROM_TREE_MAGIC,
+            u32::try_from(chroms.len()).unwrap(),
+            u32::try_from(key_size).unwrap(),
+            8,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&(chroms.len() as u64).to_le_bytes());
+        out.extend_from_slice(&0u64.to_le_bytes());
+        out.extend_from_slice(&[1, 0]);
+        out.extend_from_slice(&u16::try_from(chroms.len()).unwrap().to_le_bytes());
+        for (id, (name, size)) in chroms.iter().enumerate() {
+            let mut key = name.as_bytes().to_vec();
+            key.resize(key_size, 0);
+            out.extend_from_slice(&key);
+            out.extend_from_slice(&u32::try_from(id).unwrap().to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+        }
+
+        let data_offset = out.len() as u64;
+        let listed = push_blocks(&mut out, blocks);
+        let index_offset = out.len() as u64;
+        push_rtree(&mut out, &listed);
+
+        let mut zoom_headers = Vec::new();
+        for (reduction, zoom_blocks) in zooms {
+            let zoom_data = out.len() as u64;
+            let listed = push_blocks(&mut out, zoom_blocks);
+            let zoom_index = out.len() as u64;
+            push_rtree(&mut out, &listed);
+            zoom_headers.push((*reduction, zoom_data, zoom_index));
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&magic.to_le_bytes());
+        header.extend_from_slice(&4u16.to_le_bytes());
+        header.extend_from_slice(&u16::try_from(zooms.len()).unwrap().to_le_bytes());
+        for value in [chrom_tree_offset, data_offset, index_offset] {
+            header.extend_from_slice(&value.to_le_bytes());
+        }
+        header.extend_from_slice(&field_count.to_le_bytes());
+        header.extend_from_slice(&field_count.min(12).to_le_bytes());
+        header.extend_from_slice(&auto_sql_offset.to_le_bytes());
+        header.extend_from_slice(&summary_offset.to_le_bytes());
+        header.extend_from_slice(&65536u32.to_le_bytes());
+        header.extend_from_slice(&0u64.to_le_bytes());
+        for (reduction, data, index) in zoom_headers {
+            header.extend_from_slice(&reduction.to_le_bytes());
+            header.extend_from_slice(&0u32.to_le_bytes());
+            header.extend_from_slice(&data.to_le_bytes());
+            header.extend_from_slice(&index.to_le_bytes());
+        }
+        out[..header.len()].copy_from_slice(&header);
+        out
+    }
+
+    #[test]
+    fn bytes_byte_order() {
+        let data = [0, 0, 0, 1];
+        assert_eq!(Bytes::new(&data, true).u32().unwrap(), 1);
+        assert_eq!(Bytes::new(&data, false).u32().unwrap(), 1 << 24);
+        let mut bytes = Bytes::new(b"ab\0cd\0", false);
+        assert_eq!(bytes.c_str().unwrap(), "ab");
+        assert_eq!(bytes.c_str().unwrap(), "cd");
+        assert!(bytes.is_empty());
+        assert!(bytes.u8().is_err());
+    }
+
+    #[test]
+    fn region_overlaps() {
+        let region = Region {
+            chrom_ix: 1,
+            start: 10,
+            end: 20,
+        };
+        assert!(region.overlaps(1, 15, 30));
+        assert!(!region.overlaps(0, 15, 30));
+        assert!(!region.overlaps(1, 20, 30));
+        assert!(region.overlaps_span((0, 100), (1, 11)));
+        assert!(region.overlaps_span((1, 19), (2, 0)));
+        assert!(!region.overlaps_span((0, 0), (1, 10)));
+        assert!(!region.overlaps_span((1, 20), (3, 0)));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn zoom_record_mean() {
+        let record: ZoomRecord<&str, u32> = ZoomRecord {
+            chr: "chr1",
+            start: 0,
+            end: 10,
+            valid_count: 4,
+            min: 1.0,
+            max: 3.0,
+            sum: 8.0,
+            sum_squares: 18.0,
+        };
+        assert_eq!(record.mean(), Some(2.0));
+        let bg: BedGraph<&str, u32> = record.into();
+        assert_eq!(bg.score(), 2.0);
+    }
+}
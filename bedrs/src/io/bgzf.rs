@@ -0,0 +1,521 @@
+use flate2::{write::DeflateEncoder, Compression, Crc, Decompress, FlushDecompress, Status};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+/// Gzip magic bytes shared by all BGZF blocks
+pub(crate) const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Maximum number of uncompressed bytes stored in a single block
+const MAX_BLOCK_SIZE: usize = 0xff00;
+
+/// Maximum number of uncompressed bytes a block may declare, as capped by
+/// the BGZF specification
+const MAX_DATA_SIZE: usize = 0x10000;
+
+/// Size of the fixed gzip header of a BGZF block including its `BC` field
+const HEADER_SIZE: usize = 18;
+
+/// Size of the CRC32 and ISIZE trailer of a block
+const FOOTER_SIZE: usize = 8;
+
+/// The empty block terminating every BGZF file
+const EOF_BLOCK: [u8; 28] = [
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Reads into `buf` until it is full or the reader is exhausted
+/// returning the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match reader.read(&mut buf[total..]) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
+/// Returns the total size of a BGZF block from its gzip header and extra
+/// subfields, or `None` if the block carries no `BC` subfield.
+pub(crate) fn block_size(header: &[u8], extra: &[u8]) -> Option<usize> {
+    if header[..2] != GZIP_MAGIC || header[2] != 8 || header[3] & 4 == 0 {
+        return None;
+    }
+    let mut pos = 0;
+    while pos + 4 <= extra.len() {
+        let len = u16::from_le_bytes([extra[pos + 2], extra[pos + 3]]) as usize;
+        if extra[pos] == b'B' && extra[pos + 1] == b'C' && len == 2 && pos + 6 <= extra.len() {
+            let bsize = u16::from_le_bytes([extra[pos + 4], extra[pos + 5]]);
+            return Some(bsize as usize + 1);
+        }
+        pos += 4 + len;
+    }
+    None
+}
+
+/// Splits a BGZF virtual offset into its compressed block offset and
+/// the offset within the uncompressed block.
+#[must_use]
+pub fn split_virtual_offset(offset: u64) -> (u64, u16) {
+    (offset >> 16, (offset & 0xffff) as u16)
+}
+
+/// Builds a BGZF virtual offset from a compressed block offset and an
+/// offset within the uncompressed block.
+#[must_use]
+pub fn virtual_offset(block_offset: u64, within: u16) -> u64 {
+    (block_offset << 16) | u64::from(within)
+}
+
+/// A reader of [BGZF](https://samtools.github.io/hts-specs/SAMv1.pdf)
+/// compressed streams.
+///
+/// BGZF is a series of independently compressed gzip members, each
+/// holding at most 64KiB, which allows random access through virtual
+/// offsets: the position of a block in the compressed stream shifted
+/// left by 16 bits combined with a position inside the uncompressed
+/// block.
+///
+/// The reader implements [`BufRead`] so it can be handed directly to the
+/// record readers of this module.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::io::{BgzfReader, BgzfWriter};
+/// use std::io::{BufRead, Write};
+///
+/// let mut writer = BgzfWriter::new(Vec::new());
+/// writer.write_all(b"chr1\t10\t20\n").unwrap();
+/// let bytes = writer.finish().unwrap();
+///
+/// let mut reader = BgzfReader::new(bytes.as_slice());
+/// let mut line = String::new();
+/// reader.read_line(&mut line).unwrap();
+/// assert_eq!(line, "chr1\t10\t20\n");
+/// ```
+pub struct BgzfReader<R: Read> {
+    inner: R,
+    block: Vec<u8>,
+    compressed: Vec<u8>,
+    pos: usize,
+    block_offset: u64,
+    next_block_offset: u64,
+    decompress: Decompress,
+}
+
+impl<R: Read> BgzfReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            block: Vec::with_capacity(MAX_BLOCK_SIZE),
+            compressed: Vec::new(),
+            pos: 0,
+            block_offset: 0,
+            next_block_offset: 0,
+            decompress: Decompress::new(false),
+        }
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes the reader returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the virtual offset of the next byte to be read
+    pub fn virtual_offset(&self) -> u64 {
+        (self.block_offset << 16) | self.pos as u64
+    }
+
+    /// The compressed size of the currently loaded block
+    fn block_len(&self) -> u64 {
+        self.next_block_offset - self.block_offset
+    }
+
+    /// Reads and decompresses the next block of the stream.
+    ///
+    /// Returns `false` once the stream is exhausted.
+    fn read_block(&mut self) -> io::Result<bool> {
+        let mut header = [0u8; 12];
+        match read_full(&mut self.inner, &mut header)? {
+            0 => return Ok(false),
+            12 => {}
+            _ => return Err(invalid_data("truncated BGZF block header")),
+        }
+        let xlen = u16::from_le_bytes([header[10], header[11]]) as usize;
+        let mut extra = vec![0u8; xlen];
+        self.inner.read_exact(&mut extra)?;
+        let size = block_size(&header, &extra)
+            .ok_or_else(|| invalid_data("stream is not BGZF compressed"))?;
+        let remaining = size
+            .checked_sub(12 + xlen + FOOTER_SIZE)
+            .ok_or_else(|| invalid_data("invalid BGZF block size"))?;
+
+        self.compressed.resize(remaining + FOOTER_SIZE, 0);
+        self.inner.read_exact(&mut self.compressed)?;
+        let (data, footer) = self.compressed.split_at(remaining);
+        let crc = u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]);
+        let isize = u32::from_le_bytes([footer[4], footer[5], footer[6], footer[7]]) as usize;
+        if isize > MAX_DATA_SIZE {
+            return Err(invalid_data("BGZF block larger than 64 KiB"));
+        }
+
+        self.block.clear();
+        self.block.reserve(isize);
+        self.decompress.reset(false);
+        match self
+            .decompress
+            .decompress_vec(data, &mut self.block, FlushDecompress::Finish)
+        {
+            Ok(Status::StreamEnd) => {}
+            Ok(_) => return Err(invalid_data("truncated BGZF block data")),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+        let mut check = Crc::new();
+        check.update(&self.block);
+        if self.block.len() != isize || check.sum() != crc {
+            return Err(invalid_data("BGZF block checksum mismatch"));
+        }
+
+        self.block_offset = self.next_block_offset;
+        self.next_block_offset += size as u64;
+        self.pos = 0;
+        Ok(true)
+    }
+}
+
+impl<R: Read + Seek> BgzfReader<R> {
+    /// Moves the reader to a virtual offset
+    pub fn seek_virtual(&mut self, offset: u64) -> io::Result<()> {
+        let (block_offset, within) = split_virtual_offset(offset);
+        if block_offset != self.block_offset || self.block_len() == 0 {
+            self.inner.seek(SeekFrom::Start(block_offset))?;
+            self.next_block_offset = block_offset;
+            self.block_offset = block_offset;
+            self.block.clear();
+            self.pos = 0;
+            if !self.read_block()? && within > 0 {
+                return Err(invalid_data("virtual offset past the end of the stream"));
+            }
+        }
+        if within as usize > self.block.len() {
+            return Err(invalid_data("virtual offset past the end of its block"));
+        }
+        self.pos = within as usize;
+        Ok(())
+    }
+}
+
+impl BgzfReader<BufReader<File>> {
+    /// Opens a BGZF compressed file for reading
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R: Read> Read for BgzfReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: Read> BufRead for BgzfReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // empty blocks (such as the EOF marker) are skipped over
+        while self.pos == self.block.len() {
+            if !self.read_block()? {
+                break;
+            }
+        }
+        Ok(&self.block[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.block.len());
+    }
+}
+
+/// A writer of [BGZF](https://samtools.github.io/hts-specs/SAMv1.pdf)
+/// compressed streams.
+///
+/// Data is buffered into blocks of at most 64KiB which are compressed
+/// independently.
+/// The terminating empty block is written by [`BgzfWriter::finish`], or
+/// on drop if the writer was not finished explicitly.
+pub struct BgzfWriter<W: Write> {
+    inner: Option<W>,
+    buffer: Vec<u8>,
+    compressed: Vec<u8>,
+    block_offset: u64,
+    level: Compression,
+}
+
+impl<W: Write> BgzfWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_level(inner, Compression::default())
+    }
+
+    /// Creates a writer using a specific compression level (0-9)
+    pub fn with_level(inner: W, level: impl Into<Compression>) -> Self {
+        Self {
+            inner: Some(inner),
+            buffer: Vec::with_capacity(MAX_BLOCK_SIZE),
+            compressed: Vec::new(),
+            block_offset: 0,
+            level: level.into(),
+        }
+    }
+
+    /// Returns a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        match &self.inner {
+            Some(inner) => inner,
+            None => unreachable!("the writer is only taken when finished"),
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut W {
+        match &mut self.inner {
+            Some(inner) => inner,
+            None => unreachable!("the writer is only taken when finished"),
+        }
+    }
+
+    /// Returns the virtual offset of the next byte to be written
+    pub fn virtual_offset(&self) -> u64 {
+        (self.block_offset << 16) | self.buffer.len() as u64
+    }
+
+    /// Compresses and writes out the buffered data as a single block
+    fn write_block(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let size = self.buffer.len().min(MAX_BLOCK_SIZE);
+        let mut encoder = DeflateEncoder::new(std::mem::take(&mut self.compressed), self.level);
+        encoder.write_all(&self.buffer[..size])?;
+        let data = encoder.finish()?;
+        let mut crc = Crc::new();
+        crc.update(&self.buffer[..size]);
+
+        let total = HEADER_SIZE + data.len() + FOOTER_SIZE;
+        let bsize =
+            u16::try_from(total - 1).map_err(|_| invalid_data("BGZF block exceeds 64KiB"))?;
+        let isize = u32::try_from(size).map_err(|_| invalid_data("BGZF block exceeds 64KiB"))?;
+        let inner = self.inner_mut();
+        inner.write_all(&EOF_BLOCK[..16])?;
+        inner.write_all(&bsize.to_le_bytes())?;
+        inner.write_all(&data)?;
+        inner.write_all(&crc.sum().to_le_bytes())?;
+        inner.write_all(&isize.to_le_bytes())?;
+
+        self.compressed = data;
+        self.compressed.clear();
+        self.buffer.drain(..size);
+        self.block_offset += total as u64;
+        Ok(())
+    }
+
+    /// Writes any buffered data followed by the terminating empty block
+    fn try_finish(&mut self) -> io::Result<()> {
+        while !self.buffer.is_empty() {
+            self.write_block()?;
+        }
+        if let Some(inner) = self.inner.as_mut() {
+            inner.write_all(&EOF_BLOCK)?;
+            inner.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the stream returning the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.try_finish()?;
+        match self.inner.take() {
+            Some(inner) => Ok(inner),
+            None => unreachable!("the writer is only taken when finished"),
+        }
+    }
+}
+
+impl BgzfWriter<File> {
+    /// Creates a BGZF compressed file for writing
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(file))
+    }
+}
+
+impl<W: Write> Write for BgzfWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(MAX_BLOCK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..n]);
+        if self.buffer.len() == MAX_BLOCK_SIZE {
+            self.write_block()?;
+        }
+        Ok(n)
+    }
+
+    /// Writes out the buffered data as a block, so that the next byte
+    /// starts a new block.
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_block()?;
+        self.inner_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for BgzfWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.try_finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use std::io::Cursor;
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut writer = BgzfWriter::new(Vec::new());
+        writer.write_all(data).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn empty_stream() {
+        let bytes = compress(b"");
+        assert_eq!(bytes, EOF_BLOCK);
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = b"chr1\t10\t20\nchr1\t30\t40\n";
+        let bytes = compress(data);
+        assert!(bytes.ends_with(&EOF_BLOCK));
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn round_trip_multiple_blocks() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let bytes = compress(&data);
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn flush_starts_new_block() {
+        let mut writer = BgzfWriter::new(Vec::new());
+        assert_eq!(writer.virtual_offset(), 0);
+        writer.write_all(b"abc").unwrap();
+        assert_eq!(writer.virtual_offset(), 3);
+        writer.flush().unwrap();
+        let (block, within) = split_virtual_offset(writer.virtual_offset());
+        assert!(block > 0);
+        assert_eq!(within, 0);
+        assert_eq!(block, writer.get_ref().len() as u64);
+    }
+
+    #[test]
+    fn seek_virtual_offsets() {
+        let mut writer = BgzfWriter::new(Vec::new());
+        writer.write_all(b"first\n").unwrap();
+        writer.flush().unwrap();
+        let second = writer.virtual_offset();
+        writer.write_all(b"second\nthird\n").unwrap();
+        let third = writer.virtual_offset() - 6;
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = BgzfReader::new(Cursor::new(bytes));
+        let mut line = String::new();
+        reader.seek_virtual(third).unwrap();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "third\n");
+
+        line.clear();
+        reader.seek_virtual(second).unwrap();
+        assert_eq!(reader.virtual_offset(), second);
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "second\n");
+
+        line.clear();
+        reader.seek_virtual(0).unwrap();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "first\n");
+        assert_eq!(reader.virtual_offset(), 6);
+    }
+
+    #[test]
+    fn virtual_offset_tracks_reads() {
+        let bytes = compress(b"ab\ncd\n");
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        assert_eq!(reader.virtual_offset(), 0);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(reader.virtual_offset(), 3);
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(reader.virtual_offset(), 6);
+    }
+
+    #[test]
+    fn plain_gzip_is_rejected() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"chr1\t10\t20\n").unwrap();
+        let bytes = encoder.finish().unwrap();
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        let mut out = Vec::new();
+        assert!(reader.read_to_end(&mut out).is_err());
+    }
+
+    #[test]
+    fn corrupt_block_is_rejected() {
+        let mut bytes = compress(b"chr1\t10\t20\n");
+        bytes[20] ^= 0xff;
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        let mut out = Vec::new();
+        assert!(reader.read_to_end(&mut out).is_err());
+    }
+
+    #[test]
+    fn oversized_block_is_rejected() {
+        let mut bytes = compress(b"chr1\t10\t20\n");
+        let isize = bytes.len() - EOF_BLOCK.len() - 4;
+        bytes[isize..isize + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "BGZF block larger than 64 KiB");
+    }
+}
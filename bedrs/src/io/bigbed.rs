@@ -0,0 +1,386 @@
+use super::bbi::{
+    decode_zoom, BbiFile, BbiIter, BbiSummary, Bytes, Region, ZoomRecord, BIGBED_MAGIC,
+};
+use crate::{
+    traits::{ChromBounds, FromFields, IntervalBounds, ParseError, ValueBounds},
+    Genome, GenomeError,
+};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufReader, Read, Seek},
+    marker::PhantomData,
+    path::Path,
+    str::FromStr,
+};
+
+/// A reader of [bigBed](https://genome.ucsc.edu/goldenPath/help/bigBed.html)
+/// annotation tracks.
+///
+/// Each entry is rebuilt into its tab-delimited fields and parsed into
+/// any record implementing [`FromFields`], such as
+/// [`Bed6`](crate::Bed6), [`Bed12`](crate::Bed12), or
+/// [`NarrowPeak`](crate::NarrowPeak).
+///
+/// The chromosome tree and header are read when the file is opened, and
+/// the R-tree index is used to decompress only the blocks needed for a
+/// region.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bedrs::{io::BigBedReader, Bed12, Bed3};
+///
+/// type Gene = Bed12<String, u32, String, u32, u32, String, Vec<u32>, Vec<u32>>;
+///
+/// let mut reader: BigBedReader<_, Gene, _, _> =
+///     BigBedReader::from_path("genes.bb").unwrap();
+/// println!("{:?}", reader.auto_sql());
+///
+/// let region = Bed3::new("chr1".to_string(), 10_000, 20_000);
+/// let genes = reader
+///     .query(&region)
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// ```
+pub struct BigBedReader<R, I, C, T>
+where
+    R: Read + Seek,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    file: BbiFile<R>,
+    chroms: Vec<C>,
+    phantom_i: PhantomData<I>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<R, I, C, T> BigBedReader<R, I, C, T>
+where
+    R: Read + Seek,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr + Display,
+    T: ValueBounds,
+{
+    /// Opens a bigBed stream reading its header and chromosomes
+    pub fn new(reader: R) -> Result<Self, ParseError> {
+        let file = BbiFile::open(reader, BIGBED_MAGIC)?;
+        let chroms = file.parse_chroms()?;
+        Ok(Self {
+            file,
+            chroms,
+            phantom_i: PhantomData,
+            phantom_t: PhantomData,
+        })
+    }
+
+    /// The format version of the file
+    pub fn version(&self) -> u16 {
+        self.file.version()
+    }
+
+    /// The number of fields of each entry
+    pub fn field_count(&self) -> u16 {
+        self.file.field_count()
+    }
+
+    /// The number of fields of each entry following the BED standard
+    pub fn defined_field_count(&self) -> u16 {
+        self.file.defined_field_count()
+    }
+
+    /// The autoSql definition of the fields if present
+    pub fn auto_sql(&self) -> Option<&str> {
+        self.file.auto_sql()
+    }
+
+    /// The chromosome names and sizes of the file
+    pub fn chrom_sizes(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.file.chrom_sizes()
+    }
+
+    /// Builds a [`Genome`] from the chromosome sizes of the file
+    pub fn genome(&self) -> Result<Genome<C, T>, GenomeError>
+    where
+        T: Display,
+    {
+        self.file.genome(&self.chroms)
+    }
+
+    /// The summary statistics of the whole file if present
+    pub fn summary(&self) -> Option<&BbiSummary> {
+        self.file.summary()
+    }
+
+    /// The window sizes of the zoom levels of the file
+    pub fn zoom_levels(&self) -> Vec<u32> {
+        self.file.zoom_levels()
+    }
+
+    /// Iterates over every record of the file
+    pub fn records(&mut self) -> Result<BbiIter<'_, R, C, I>, ParseError> {
+        let blocks = self.file.data_blocks(None)?;
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            None,
+            decode_entries,
+        ))
+    }
+
+    /// Iterates over the records overlapping a region
+    pub fn query<Iv>(&mut self, region: &Iv) -> Result<BbiIter<'_, R, C, I>, ParseError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        let region = self.file.region(region);
+        let blocks = match region {
+            Some(region) => self.file.data_blocks(Some(&region))?,
+            None => Vec::new(),
+        };
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            region,
+            decode_entries,
+        ))
+    }
+
+    /// Iterates over every summary record of a zoom level
+    pub fn zoom(
+        &mut self,
+        level: usize,
+    ) -> Result<BbiIter<'_, R, C, ZoomRecord<C, T>>, ParseError> {
+        let blocks = self.file.zoom_blocks(level, None)?;
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            None,
+            decode_zoom,
+        ))
+    }
+
+    /// Iterates over the summary records of a zoom level overlapping a
+    /// region
+    pub fn zoom_query<Iv>(
+        &mut self,
+        level: usize,
+        region: &Iv,
+    ) -> Result<BbiIter<'_, R, C, ZoomRecord<C, T>>, ParseError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        let region = self.file.region(region);
+        let blocks = match region {
+            Some(region) => self.file.zoom_blocks(level, Some(&region))?,
+            None => Vec::new(),
+        };
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            region,
+            decode_zoom,
+        ))
+    }
+}
+
+impl<I, C, T> BigBedReader<BufReader<File>, I, C, T>
+where
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr + Display,
+    T: ValueBounds,
+{
+    /// Opens a bigBed file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let file = File::open(path)?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+/// Decodes the entries of a bigBed block
+fn decode_entries<R, I, C, T>(
+    file: &BbiFile<R>,
+    chroms: &[C],
+    data: &[u8],
+    region: Option<&Region>,
+    out: &mut Vec<I>,
+) -> Result<(), ParseError>
+where
+    I: FromFields<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    let mut bytes = Bytes::new(data, file.big_endian());
+    while !bytes.is_empty() {
+        let chrom_ix = bytes.u32()?;
+        let start = bytes.u32()?;
+        let end = bytes.u32()?;
+        let rest = bytes.c_str()?;
+        if region.is_some_and(|r| !r.overlaps(chrom_ix, start, end)) {
+            continue;
+        }
+        let name = file.chrom_name(chrom_ix)?;
+        let (start, end) = (start.to_string(), end.to_string());
+        let mut fields = vec![name, start.as_str(), end.as_str()];
+        if !rest.is_empty() {
+            fields.extend(rest.split('\t'));
+        }
+        out.push(I::from_fields(chroms[chrom_ix as usize].clone(), &fields)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        io::bbi::{
+            testing::{encode, zoom_record, TestBlock},
+            BIGWIG_MAGIC,
+        },
+        Bed12, Bed3, Bed6, Coordinates, Strand,
+    };
+    use std::io::Cursor;
+
+    type Gene = Bed12<String, u32, String, u32, u32, String, Vec<u32>, Vec<u32>>;
+
+    fn entry(chrom_ix: u32, start: u32, end: u32, rest: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [chrom_ix, start, end] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(rest.as_bytes());
+        data.push(0);
+        data
+    }
+
+    fn example() -> Vec<u8> {
+        let mut first = entry(0, 100, 500, "a\t0\t+\t120\t480\t0\t2\t100,100,\t0,300,");
+        first.extend(entry(0, 1000, 2000, "b\t5\t-\t1000\t2000\t0\t1\t1000,\t0,"));
+        let second = entry(1, 50, 80, "c\t0\t.\t50\t80\t0\t1\t30,\t0,");
+        let blocks = vec![
+            TestBlock {
+                chrom_ix: 0,
+                start: 100,
+                end: 2000,
+                data: first,
+            },
+            TestBlock {
+                chrom_ix: 1,
+                start: 50,
+                end: 80,
+                data: second,
+            },
+        ];
+        let zooms = vec![(
+            1024,
+            vec![TestBlock {
+                chrom_ix: 0,
+                start: 0,
+                end: 2048,
+                data: zoom_record(0, 0, 2048, 1400, 1400.0),
+            }],
+        )];
+        let sql = "table bed12 \"genes\" ()";
+        encode(
+            BIGBED_MAGIC,
+            &[("chr1", 10_000), ("chr2", 500)],
+            &blocks,
+            &zooms,
+            12,
+            Some(sql),
+        )
+    }
+
+    #[test]
+    fn read_header() {
+        let reader: BigBedReader<_, Bed3<String, u32>, _, _> =
+            BigBedReader::new(Cursor::new(example())).unwrap();
+        assert_eq!(reader.field_count(), 12);
+        assert_eq!(reader.defined_field_count(), 12);
+        assert_eq!(reader.auto_sql(), Some("table bed12 \"genes\" ()"));
+        assert_eq!(reader.chrom_sizes().count(), 2);
+        assert_eq!(reader.zoom_levels(), vec![1024]);
+        assert_eq!(reader.genome().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn read_bed12() {
+        let mut reader: BigBedReader<_, Gene, _, _> =
+            BigBedReader::new(Cursor::new(example())).unwrap();
+        let records = reader
+            .records()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].name(), "a");
+        assert_eq!(records[0].strand(), Some(Strand::Forward));
+        assert_eq!(records[0].block_starts(), &vec![0, 300]);
+        assert_eq!(records[2].chr(), "chr2");
+    }
+
+    #[test]
+    fn read_bed6_query() {
+        let mut reader: BigBedReader<_, Bed6<String, u32, String>, _, _> =
+            BigBedReader::new(Cursor::new(example())).unwrap();
+        let region = Bed3::new("chr1".to_string(), 400, 1001);
+        let records = reader
+            .query(&region)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].strand(), Some(Strand::Reverse));
+
+        let region = Bed3::new("chr1".to_string(), 500, 1000);
+        assert_eq!(reader.query(&region).unwrap().count(), 0);
+        let region = Bed3::new("chr2".to_string(), 0, 60);
+        assert_eq!(reader.query(&region).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn read_zoom() {
+        let mut reader: BigBedReader<_, Bed3<String, u32>, _, _> =
+            BigBedReader::new(Cursor::new(example())).unwrap();
+        let records = reader
+            .zoom(0)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].mean(), Some(1.0));
+        let region = Bed3::new("chr2".to_string(), 0, 100);
+        assert_eq!(reader.zoom_query(0, &region).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn reject_bigwig() {
+        let bytes = encode(BIGWIG_MAGIC, &[("chr1", 10)], &[], &[], 0, None);
+        let reader: Result<BigBedReader<_, Bed3<String, u32>, _, _>, _> =
+            BigBedReader::new(Cursor::new(bytes));
+        assert!(reader.is_err());
+    }
+
+    #[test]
+    fn malformed_entry() {
+        let blocks = vec![TestBlock {
+            chrom_ix: 0,
+            start: 0,
+            end: 10,
+            data: entry(0, 0, 10, "a\t0\t?"),
+        }];
+        let bytes = encode(BIGBED_MAGIC, &[("chr1", 100)], &blocks, &[], 6, None);
+        let mut reader: BigBedReader<_, Bed6<String, u32, String>, _, _> =
+            BigBedReader::new(Cursor::new(bytes)).unwrap();
+        let mut records = reader.records().unwrap();
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,544 @@
+use super::bbi::{
+    decode_zoom, invalid_data, position, BbiFile, BbiIter, BbiSummary, Bytes, Region, ZoomRecord,
+    BIGWIG_MAGIC,
+};
+use crate::{
+    traits::{ChromBounds, IntervalBounds, ParseError, ValueBounds},
+    BedGraph, Genome, GenomeError,
+};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufReader, Read, Seek},
+    marker::PhantomData,
+    path::Path,
+    str::FromStr,
+};
+
+/// A reader of [bigWig](https://genome.ucsc.edu/goldenPath/help/bigWig.html)
+/// signal tracks yielding [`BedGraph`] records.
+///
+/// The chromosome tree and header are read when the file is opened, and
+/// the R-tree index is used to decompress only the blocks needed for a
+/// region.
+/// Every data section type (`bedGraph`, `variableStep`, and `fixedStep`)
+/// is converted to one [`BedGraph`] record per item.
+///
+/// Zoom levels are exposed as [`ZoomRecord`]s holding the precomputed
+/// summaries of each window.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bedrs::{io::BigWigReader, Bed3, Coordinates};
+///
+/// let mut reader: BigWigReader<_, String, u32> =
+///     BigWigReader::from_path("signal.bw").unwrap();
+///
+/// let region = Bed3::new("chr1".to_string(), 10_000, 20_000);
+/// for record in reader.query(&region).unwrap() {
+///     let record = record.unwrap();
+///     println!("{}\t{}", record.start(), record.score());
+/// }
+/// ```
+pub struct BigWigReader<R, C, T>
+where
+    R: Read + Seek,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    file: BbiFile<R>,
+    chroms: Vec<C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<R, C, T> BigWigReader<R, C, T>
+where
+    R: Read + Seek,
+    C: ChromBounds + FromStr + Display,
+    T: ValueBounds,
+{
+    /// Opens a bigWig stream reading its header and chromosomes
+    pub fn new(reader: R) -> Result<Self, ParseError> {
+        let file = BbiFile::open(reader, BIGWIG_MAGIC)?;
+        let chroms = file.parse_chroms()?;
+        Ok(Self {
+            file,
+            chroms,
+            phantom_t: PhantomData,
+        })
+    }
+
+    /// The format version of the file
+    pub fn version(&self) -> u16 {
+        self.file.version()
+    }
+
+    /// The chromosome names and sizes of the file
+    pub fn chrom_sizes(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.file.chrom_sizes()
+    }
+
+    /// Builds a [`Genome`] from the chromosome sizes of the file
+    pub fn genome(&self) -> Result<Genome<C, T>, GenomeError>
+    where
+        T: Display,
+    {
+        self.file.genome(&self.chroms)
+    }
+
+    /// The summary statistics of the whole file if present
+    pub fn summary(&self) -> Option<&BbiSummary> {
+        self.file.summary()
+    }
+
+    /// The window sizes of the zoom levels of the file
+    pub fn zoom_levels(&self) -> Vec<u32> {
+        self.file.zoom_levels()
+    }
+
+    /// Iterates over every record of the file
+    pub fn records(&mut self) -> Result<BbiIter<'_, R, C, BedGraph<C, T>>, ParseError> {
+        let blocks = self.file.data_blocks(None)?;
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            None,
+            decode_sections,
+        ))
+    }
+
+    /// Iterates over the records overlapping a region
+    pub fn query<I>(&mut self, region: &I) -> Result<BbiIter<'_, R, C, BedGraph<C, T>>, ParseError>
+    where
+        I: IntervalBounds<C, T>,
+    {
+        let region = self.file.region(region);
+        let blocks = match region {
+            Some(region) => self.file.data_blocks(Some(&region))?,
+            None => Vec::new(),
+        };
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            region,
+            decode_sections,
+        ))
+    }
+
+    /// Iterates over every summary record of a zoom level
+    pub fn zoom(
+        &mut self,
+        level: usize,
+    ) -> Result<BbiIter<'_, R, C, ZoomRecord<C, T>>, ParseError> {
+        let blocks = self.file.zoom_blocks(level, None)?;
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            None,
+            decode_zoom,
+        ))
+    }
+
+    /// Iterates over the summary records of a zoom level overlapping a
+    /// region
+    pub fn zoom_query<I>(
+        &mut self,
+        level: usize,
+        region: &I,
+    ) -> Result<BbiIter<'_, R, C, ZoomRecord<C, T>>, ParseError>
+    where
+        I: IntervalBounds<C, T>,
+    {
+        let region = self.file.region(region);
+        let blocks = match region {
+            Some(region) => self.file.zoom_blocks(level, Some(&region))?,
+            None => Vec::new(),
+        };
+        Ok(BbiIter::new(
+            &mut self.file,
+            &self.chroms,
+            blocks,
+            region,
+            decode_zoom,
+        ))
+    }
+}
+
+impl<C, T> BigWigReader<BufReader<File>, C, T>
+where
+    C: ChromBounds + FromStr + Display,
+    T: ValueBounds,
+{
+    /// Opens a bigWig file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let file = File::open(path)?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+/// Decodes the data sections of a bigWig block
+fn decode_sections<R, C, T>(
+    file: &BbiFile<R>,
+    chroms: &[C],
+    data: &[u8],
+    region: Option<&Region>,
+    out: &mut Vec<BedGraph<C, T>>,
+) -> Result<(), ParseError>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    let mut bytes = Bytes::new(data, file.big_endian());
+    while !bytes.is_empty() {
+        let chrom_ix = bytes.u32()?;
+        let section_start = bytes.u32()?;
+        let _section_end = bytes.u32()?;
+        let step = bytes.u32()?;
+        let span = bytes.u32()?;
+        let kind = bytes.u8()?;
+        let _reserved = bytes.u8()?;
+        let count = bytes.u16()?;
+        let chr = chroms
+            .get(chrom_ix as usize)
+            .ok_or_else(|| invalid_data("unknown chromosome id"))?;
+        for i in 0..u32::from(count) {
+            let (start, end, value) = match kind {
+                1 => (bytes.u32()?, bytes.u32()?, bytes.f32()?),
+                2 => {
+                    let start = bytes.u32()?;
+                    (start, start.saturating_add(span), bytes.f32()?)
+                }
+                3 => {
+                    let start = section_start.saturating_add(i.saturating_mul(step));
+                    (start, start.saturating_add(span), bytes.f32()?)
+                }
+                _ => return Err(invalid_data("unknown bigWig section type")),
+            };
+            if region.is_some_and(|r| !r.overlaps(chrom_ix, start, end)) {
+                continue;
+            }
+            out.push(BedGraph::new(
+                chr.clone(),
+                position(start, "start")?,
+                position(end, "end")?,
+                f64::from(value),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        io::bbi::testing::{encode, zoom_record, TestBlock},
+        Bed3, Coordinates,
+    };
+    use std::io::Cursor;
+
+    fn section(
+        kind: u8,
+        chrom_ix: u32,
+        start: u32,
+        step: u32,
+        span: u32,
+        items: &[(u32, u32, f32)],
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        let end = items.iter().map(|item| item.1).max().unwrap_or(start);
+        for value in [chrom_ix, start, end, step, span] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[kind, 0]);
+        data.extend_from_slice(&u16::try_from(items.len()).unwrap().to_le_bytes());
+        for &(s, e, v) in items {
+            match kind {
+                1 => {
+                    data.extend_from_slice(&s.to_le_bytes());
+                    data.extend_from_slice(&e.to_le_bytes());
+                }
+                2 => data.extend_from_slice(&s.to_le_bytes()),
+                _ => {}
+            }
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data
+    }
+
+    fn example() -> BigWigReader<Cursor<Vec<u8>>, String, u32> {
+        let bedgraph = section(1, 0, 0, 0, 0, &[(0, 10, 1.0), (10, 30, 2.0), (50, 60, 3.0)]);
+        let variable = section(2, 0, 100, 0, 5, &[(100, 105, 4.0), (200, 205, 5.0)]);
+        let fixed = section(
+            3,
+            1,
+            1000,
+            100,
+            50,
+            &[(1000, 1050, 6.0), (1100, 1150, 7.0), (1200, 1250, 8.0)],
+        );
+        let blocks = vec![
+            TestBlock {
+                chrom_ix: 0,
+                start: 0,
+                end: 60,
+                data: bedgraph,
+            },
+            TestBlock {
+                chrom_ix: 0,
+                start: 100,
+                end: 205,
+                data: variable,
+            },
+            TestBlock {
+                chrom_ix: 1,
+                start: 1000,
+                end: 1250,
+                data: fixed,
+            },
+        ];
+        let mut zoom = zoom_record(0, 0, 128, 40, 60.0);
+        zoom.extend(zoom_record(0, 128, 256, 10, 45.0));
+        zoom.extend(zoom_record(1, 1000, 1256, 150, 1050.0));
+        let zooms = vec![(
+            128,
+            vec![TestBlock {
+                chrom_ix: 0,
+                start: 0,
+                end: 1256,
+                data: zoom,
+            }],
+        )];
+        let bytes = encode(
+            BIGWIG_MAGIC,
+            &[("chr1", 5000), ("chr2", 8000)],
+            &blocks,
+            &zooms,
+            0,
+            None,
+        );
+        BigWigReader::new(Cursor::new(bytes)).unwrap()
+    }
+
+    #[test]
+    fn read_header() {
+        let reader = example();
+        assert_eq!(reader.version(), 4);
+        assert_eq!(
+            reader.chrom_sizes().collect::<Vec<_>>(),
+            vec![("chr1", 5000), ("chr2", 8000)]
+        );
+        assert_eq!(reader.zoom_levels(), vec![128]);
+        let summary = reader.summary().unwrap();
+        assert_eq!(summary.bases_covered(), 10);
+        assert_eq!(summary.mean(), Some(2.0));
+        let genome = reader.genome().unwrap();
+        assert_eq!(genome.chrom_size(&"chr2".to_string()), Some(8000));
+    }
+
+    #[test]
+    fn read_all_records() {
+        let mut reader = example();
+        let records = reader
+            .records()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let spans: Vec<_> = records
+            .iter()
+            .map(|r| (r.chr().as_str(), r.start(), r.end(), r.score()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("chr1", 0, 10, 1.0),
+                ("chr1", 10, 30, 2.0),
+                ("chr1", 50, 60, 3.0),
+                ("chr1", 100, 105, 4.0),
+                ("chr1", 200, 205, 5.0),
+                ("chr2", 1000, 1050, 6.0),
+                ("chr2", 1100, 1150, 7.0),
+                ("chr2", 1200, 1250, 8.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_region() {
+        let mut reader = example();
+        let region = Bed3::new("chr1".to_string(), 25, 150);
+        let records = reader
+            .query(&region)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let starts: Vec<_> = records.iter().map(Coordinates::start).collect();
+        assert_eq!(starts, vec![10, 50, 100]);
+
+        let region = Bed3::new("chr2".to_string(), 1149, 1201);
+        let records = reader
+            .query(&region)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let starts: Vec<_> = records.iter().map(Coordinates::start).collect();
+        assert_eq!(starts, vec![1100, 1200]);
+
+        let region = Bed3::new("chrX".to_string(), 0, 100);
+        assert_eq!(reader.query(&region).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn zoom_levels() {
+        let mut reader = example();
+        let records = reader
+            .zoom(0)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].mean(), Some(1.5));
+        let region = Bed3::new("chr1".to_string(), 200, 210);
+        let records = reader
+            .zoom_query(0, &region)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].start(), 128);
+        assert_eq!(records[0].valid_count(), 10);
+        assert!(reader.zoom(1).is_err());
+    }
+
+    #[test]
+    fn reject_other_formats() {
+        let bytes = encode(
+            crate::io::bbi::BIGBED_MAGIC,
+            &[("chr1", 10)],
+            &[],
+            &[],
+            3,
+            None,
+        );
+        assert!(BigWigReader::<_, String, u32>::new(Cursor::new(bytes)).is_err());
+        assert!(BigWigReader::<_, String, u32>::new(Cursor::new(vec![0u8; 10])).is_err());
+    }
+
+    #[test]
+    fn reject_oversized_block() {
+        let data = section(1, 0, 0, 0, 0, &[(0, 10, 1.0), (10, 30, 2.0)]);
+        let blocks = [TestBlock {
+            chrom_ix: 0,
+            start: 0,
+            end: 30,
+            data,
+        }];
+        let mut bytes = encode(BIGWIG_MAGIC, &[("chr1", 100)], &blocks, &[], 0, None);
+        // Declare a decompression buffer smaller than the block
+        bytes[52..56].copy_from_slice(&16u32.to_le_bytes());
+        let mut reader = BigWigReader::<_, String, u32>::new(Cursor::new(bytes)).unwrap();
+        let result = reader.records().unwrap().next().unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_cyclic_trees() {
+        let bytes = encode(BIGWIG_MAGIC, &[("chr1", 100)], &[], &[], 0, None);
+        let offset = |at: usize| {
+            usize::try_from(u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())).unwrap()
+        };
+        let (chrom_tree_offset, index_offset) = (offset(8), offset(24));
+
+        // Turn the root of the chromosome tree into a node pointing to itself
+        let mut chrom_tree = bytes.clone();
+        let root = chrom_tree_offset + 32;
+        chrom_tree[root] = 0;
+        let child = root + 4 + "chr1".len();
+        chrom_tree[child..child + 8].copy_from_slice(&(root as u64).to_le_bytes());
+        assert!(BigWigReader::<_, String, u32>::new(Cursor::new(chrom_tree)).is_err());
+
+        // Replace the R-tree root with a node pointing to itself
+        let mut index = bytes;
+        let root = index_offset + 48;
+        index[root..root + 4].copy_from_slice(&[0, 0, 1, 0]);
+        let mut item = Vec::new();
+        for value in [0u32, 0, 0, 100] {
+            item.extend_from_slice(&value.to_le_bytes());
+        }
+        item.extend_from_slice(&(root as u64).to_le_bytes());
+        index.extend_from_slice(&[0; 24]);
+        index[root + 4..root + 28].copy_from_slice(&item);
+        let mut reader = BigWigReader::<_, String, u32>::new(Cursor::new(index)).unwrap();
+        assert!(reader.records().is_err());
+    }
+
+    #[test]
+    fn reject_sizes_past_end_of_file() {
+        let bytes = encode(BIGWIG_MAGIC, &[("chr1", 100)], &[], &[], 0, None);
+        let chrom_tree_offset =
+            usize::try_from(u64::from_le_bytes(bytes[8..16].try_into().unwrap())).unwrap();
+        // Declare chromosome keys of 4 GiB
+        let mut bytes = bytes;
+        let key_size = chrom_tree_offset + 8;
+        bytes[key_size..key_size + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(BigWigReader::<_, String, u32>::new(Cursor::new(bytes)).is_err());
+    }
+
+    /// A file laid out as written by `wigToBigWig`: a version 4 header
+    /// with an extension header, R-tree leaves padded to their block size,
+    /// a zoom level, and the trailing magic number.
+    ///
+    /// Assembled by hand from the UCSC writer layout for
+    /// `fixedStep chrom=chr1 start=11 step=10 span=5` (1.5, 2.5) and a
+    /// `chr2` bedGraph section (0-20: 3, 50-60: -1).
+    const FIXED_STEP: &[u8] = include_bytes!("../../tests/data/fixed_step.bw");
+
+    #[test]
+    fn read_fixture() {
+        let mut reader = BigWigReader::<_, String, u32>::new(Cursor::new(FIXED_STEP)).unwrap();
+        assert_eq!(reader.version(), 4);
+        assert_eq!(
+            reader.chrom_sizes().collect::<Vec<_>>(),
+            vec![("chr1", 100), ("chr2", 200)]
+        );
+        assert_eq!(reader.zoom_levels(), vec![50]);
+        assert_eq!(reader.summary().unwrap().bases_covered(), 40);
+        let records = reader
+            .records()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let spans: Vec<_> = records
+            .iter()
+            .map(|r| (r.chr().as_str(), r.start(), r.end(), r.score()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("chr1", 10, 15, 1.5),
+                ("chr1", 20, 25, 2.5),
+                ("chr2", 0, 20, 3.0),
+                ("chr2", 50, 60, -1.0),
+            ]
+        );
+        let region = Bed3::new("chr2".to_string(), 30, 55);
+        let records = reader
+            .query(&region)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].start(), 50);
+        let zoom = reader
+            .zoom(0)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(zoom.len(), 2);
+        assert_eq!(zoom[1].valid_count(), 30);
+    }
+}
@@ -0,0 +1,622 @@
+use crate::{
+    traits::{
+        fields::parse_value, ChromBounds, ColumnarError, IntervalBounds, MetaBounds, ValueBounds,
+    },
+    Bed12, Bed3, Bed4, Bed6, BedGraph, BroadPeak, FromFields, IntervalContainer, MetaInterval,
+    NarrowPeak, ParseError, StrandedBed3, ToFields,
+};
+use arrow_array::{
+    builder::{Float64Builder, Int64Builder, StringBuilder, StringDictionaryBuilder},
+    cast::AsArray,
+    types::{Float64Type, Int32Type, Int64Type},
+    Array, ArrayRef, RecordBatch,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+#[cfg(feature = "parquet")]
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+    file::reader::ChunkReader,
+};
+#[cfg(feature = "parquet")]
+use std::io::Write;
+use std::{
+    fmt::{Display, Write as _},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// The columns holding the coordinates of every record
+const COORDINATES: [(&str, ColumnKind); 2] = [("start", ColumnKind::Int), ("end", ColumnKind::Int)];
+
+/// The kind of an Arrow column holding a record field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// A 64-bit integer
+    Int,
+    /// A 64-bit float
+    Float,
+    /// A nullable 64-bit float where a missing score (`.`) is null
+    Score,
+    /// A nullable string of `+` or `-` where an unknown strand is null
+    Strand,
+    /// A string
+    Text,
+}
+
+impl ColumnKind {
+    /// The Arrow data type of the column
+    #[must_use]
+    pub fn data_type(self) -> DataType {
+        match self {
+            Self::Int => DataType::Int64,
+            Self::Float | Self::Score => DataType::Float64,
+            Self::Strand | Self::Text => DataType::Utf8,
+        }
+    }
+
+    /// Whether the column may hold nulls
+    #[must_use]
+    pub fn nullable(self) -> bool {
+        matches!(self, Self::Score | Self::Strand)
+    }
+}
+
+/// A trait for records with a columnar Arrow representation.
+///
+/// Every record is stored with a dictionary encoded `chr` column and
+/// integer `start` and `end` columns followed by the [`COLUMNS`](Self::COLUMNS)
+/// of the record type in the order of its BED fields.
+///
+/// # Examples
+/// ```
+/// use bedrs::{io::ArrowRecord, Bed6};
+/// use arrow_schema::DataType;
+///
+/// let schema = <Bed6<String, u32, String> as ArrowRecord<_, _>>::schema();
+/// let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+/// assert_eq!(names, ["chr", "start", "end", "name", "score", "strand"]);
+/// assert_eq!(schema.field(5).data_type(), &DataType::Utf8);
+/// assert!(schema.field(5).is_nullable());
+/// ```
+pub trait ArrowRecord<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// The name and kind of every column following `chr`, `start`, and `end`
+    const COLUMNS: &'static [(&'static str, ColumnKind)];
+
+    /// The Arrow schema of a batch of records
+    #[must_use]
+    fn schema() -> Schema {
+        let chr = Field::new(
+            "chr",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        );
+        let fields = COORDINATES
+            .iter()
+            .chain(Self::COLUMNS)
+            .map(|(name, kind)| Field::new(*name, kind.data_type(), kind.nullable()));
+        Schema::new(std::iter::once(chr).chain(fields).collect::<Vec<_>>())
+    }
+}
+
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T> + ArrowRecord<C, T> + ToFields<C, T>,
+    C: ChromBounds + Display,
+    T: ValueBounds,
+{
+    /// Converts the records of the container into an Arrow [`RecordBatch`]
+    /// with the schema of [`ArrowRecord::schema`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bedrs::{Bed3, Coordinates, IntervalContainer};
+    ///
+    /// let set = IntervalContainer::new(vec![
+    ///     Bed3::new("chr1".to_string(), 10, 20),
+    ///     Bed3::new("chr2".to_string(), 30, 40),
+    /// ]);
+    /// let batch = set.to_record_batch().unwrap();
+    /// assert_eq!(batch.num_rows(), 2);
+    ///
+    /// let observed: IntervalContainer<Bed3<String, u32>, _, _> =
+    ///     IntervalContainer::from_record_batch(&batch).unwrap();
+    /// assert_eq!(observed.records()[1].start(), 30);
+    /// ```
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ColumnarError> {
+        let columns: Vec<_> = COORDINATES.iter().chain(I::COLUMNS).collect();
+        let mut chroms = StringDictionaryBuilder::<Int32Type>::new();
+        let mut builders: Vec<_> = columns
+            .iter()
+            .map(|(_, kind)| ColumnBuilder::new(*kind, self.len()))
+            .collect();
+
+        let mut chr = String::new();
+        let mut line = String::new();
+        for record in self.records() {
+            chr.clear();
+            line.clear();
+            write!(chr, "{}", record.chr()).map_err(external)?;
+            record.write_fields(&mut line).map_err(external)?;
+            chroms.append(&chr)?;
+
+            let mut fields = line.split('\t').skip(1);
+            for ((name, kind), builder) in columns.iter().zip(builders.iter_mut()) {
+                let value = fields
+                    .next()
+                    .ok_or(ParseError::MissingField { field: name })?;
+                builder.append(*kind, name, value)?;
+            }
+        }
+
+        let arrays = std::iter::once(Arc::new(chroms.finish()) as ArrayRef)
+            .chain(builders.iter_mut().map(ColumnBuilder::finish))
+            .collect();
+        Ok(RecordBatch::try_new(Arc::new(I::schema()), arrays)?)
+    }
+
+    /// Writes the records of the container as a Parquet file
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: Write + Send>(&self, writer: W) -> Result<(), ColumnarError> {
+        let batch = self.to_record_batch()?;
+        let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+}
+
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T> + ArrowRecord<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    /// Builds a container from an Arrow [`RecordBatch`].
+    ///
+    /// Columns are looked up by name and cast to the types of the record's
+    /// schema, so a plain string `chr` column or narrower integer columns
+    /// are accepted as well.
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Self, ColumnarError> {
+        let mut records = Vec::with_capacity(batch.num_rows());
+        extend_records(&mut records, batch)?;
+        Ok(Self::new(records))
+    }
+
+    /// Reads a container from a Parquet file
+    #[cfg(feature = "parquet")]
+    pub fn read_parquet<R: ChunkReader + 'static>(reader: R) -> Result<Self, ColumnarError> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(reader)?.build()?;
+        let mut records = Vec::new();
+        for batch in reader {
+            extend_records(&mut records, &batch?)?;
+        }
+        Ok(Self::new(records))
+    }
+}
+
+/// Parses every row of a batch into a record
+fn extend_records<I, C, T>(records: &mut Vec<I>, batch: &RecordBatch) -> Result<(), ColumnarError>
+where
+    I: ArrowRecord<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    let chroms = column(batch, "chr", ColumnKind::Text)?;
+    let chroms = chroms.as_string::<i32>();
+    let columns = COORDINATES
+        .iter()
+        .chain(I::COLUMNS)
+        .map(|(name, kind)| Ok((column(batch, name, *kind)?, *name, *kind)))
+        .collect::<Result<Vec<_>, ColumnarError>>()?;
+
+    let mut fields = Vec::with_capacity(columns.len() + 1);
+    for row in 0..batch.num_rows() {
+        if chroms.is_null(row) {
+            return Err(ColumnarError::NullValue { column: "chr" });
+        }
+        fields.clear();
+        fields.push(chroms.value(row).to_string());
+        for (array, name, kind) in &columns {
+            fields.push(field(array, row, name, *kind)?);
+        }
+        let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+        let chr = parse_value(fields[0], "chr")?;
+        records.push(I::from_fields(chr, &fields)?);
+    }
+    Ok(())
+}
+
+/// Finds a column by name casting it to the type of its kind
+fn column(
+    batch: &RecordBatch,
+    name: &'static str,
+    kind: ColumnKind,
+) -> Result<ArrayRef, ColumnarError> {
+    let array = batch
+        .column_by_name(name)
+        .ok_or(ColumnarError::MissingColumn { column: name })?;
+    Ok(arrow_cast::cast(array, &kind.data_type())?)
+}
+
+/// Formats the value of a row as the text of its BED field
+fn field(
+    array: &ArrayRef,
+    row: usize,
+    name: &'static str,
+    kind: ColumnKind,
+) -> Result<String, ColumnarError> {
+    if array.is_null(row) {
+        return match kind {
+            ColumnKind::Score | ColumnKind::Strand => Ok(".".to_string()),
+            _ => Err(ColumnarError::NullValue { column: name }),
+        };
+    }
+    Ok(match kind {
+        ColumnKind::Int => array.as_primitive::<Int64Type>().value(row).to_string(),
+        ColumnKind::Float | ColumnKind::Score => {
+            array.as_primitive::<Float64Type>().value(row).to_string()
+        }
+        ColumnKind::Strand | ColumnKind::Text => array.as_string::<i32>().value(row).to_string(),
+    })
+}
+
+fn external(err: std::fmt::Error) -> ArrowError {
+    ArrowError::ExternalError(Box::new(err))
+}
+
+/// A builder for a single column of a batch
+enum ColumnBuilder {
+    Int(Int64Builder),
+    Float(Float64Builder),
+    Text(StringBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(kind: ColumnKind, capacity: usize) -> Self {
+        match kind {
+            ColumnKind::Int => Self::Int(Int64Builder::with_capacity(capacity)),
+            ColumnKind::Float | ColumnKind::Score => {
+                Self::Float(Float64Builder::with_capacity(capacity))
+            }
+            ColumnKind::Strand | ColumnKind::Text => Self::Text(StringBuilder::new()),
+        }
+    }
+
+    /// Appends the text of a BED field
+    fn append(
+        &mut self,
+        kind: ColumnKind,
+        name: &'static str,
+        value: &str,
+    ) -> Result<(), ParseError> {
+        match self {
+            Self::Int(builder) => builder.append_value(parse_value(value, name)?),
+            Self::Float(builder) if kind == ColumnKind::Score && value == "." => {
+                builder.append_null();
+            }
+            Self::Float(builder) => builder.append_value(parse_value(value, name)?),
+            Self::Text(builder) if kind == ColumnKind::Strand && value == "." => {
+                builder.append_null();
+            }
+            Self::Text(builder) => builder.append_value(value),
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Self::Int(builder) => Arc::new(builder.finish()),
+            Self::Float(builder) => Arc::new(builder.finish()),
+            Self::Text(builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+impl<C, T> ArrowRecord<C, T> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[];
+}
+
+impl<C, T, N> ArrowRecord<C, T> for Bed4<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[("name", ColumnKind::Text)];
+}
+
+impl<C, T, N> ArrowRecord<C, T> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[
+        ("name", ColumnKind::Text),
+        ("score", ColumnKind::Score),
+        ("strand", ColumnKind::Strand),
+    ];
+}
+
+impl<C, T, N, Ts, Te, R, Si, St> ArrowRecord<C, T> for Bed12<C, T, N, Ts, Te, R, Si, St>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+    Ts: ValueBounds,
+    Te: ValueBounds,
+    R: MetaBounds,
+    Si: MetaBounds,
+    St: MetaBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[
+        ("name", ColumnKind::Text),
+        ("score", ColumnKind::Score),
+        ("strand", ColumnKind::Strand),
+        ("thick_start", ColumnKind::Int),
+        ("thick_end", ColumnKind::Int),
+        ("item_rgb", ColumnKind::Text),
+        ("block_count", ColumnKind::Int),
+        ("block_sizes", ColumnKind::Text),
+        ("block_starts", ColumnKind::Text),
+    ];
+}
+
+impl<C, T> ArrowRecord<C, T> for BedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[("score", ColumnKind::Float)];
+}
+
+impl<C, T> ArrowRecord<C, T> for StrandedBed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[("strand", ColumnKind::Strand)];
+}
+
+impl<C, T, M> ArrowRecord<C, T> for MetaInterval<C, T, M>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    M: MetaBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[("meta", ColumnKind::Text)];
+}
+
+impl<C, T, N> ArrowRecord<C, T> for NarrowPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[
+        ("name", ColumnKind::Text),
+        ("score", ColumnKind::Score),
+        ("strand", ColumnKind::Strand),
+        ("signal_value", ColumnKind::Float),
+        ("p_value", ColumnKind::Float),
+        ("q_value", ColumnKind::Float),
+        ("peak", ColumnKind::Int),
+    ];
+}
+
+impl<C, T, N> ArrowRecord<C, T> for BroadPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    const COLUMNS: &'static [(&'static str, ColumnKind)] = &[
+        ("name", ColumnKind::Text),
+        ("score", ColumnKind::Score),
+        ("strand", ColumnKind::Strand),
+        ("signal_value", ColumnKind::Float),
+        ("p_value", ColumnKind::Float),
+        ("q_value", ColumnKind::Float),
+    ];
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Coordinates, Score, Strand};
+    use arrow_array::{Int32Array, StringArray};
+
+    type Gene = Bed12<String, u32, String, u32, u32, String, Vec<u32>, Vec<u32>>;
+
+    fn peaks() -> IntervalContainer<Bed6<String, u32, String>, String, u32> {
+        IntervalContainer::new(vec![
+            Bed6::new(
+                "chr1".to_string(),
+                10,
+                20,
+                "a".to_string(),
+                Score(Some(5.0)),
+                Strand::Forward,
+            ),
+            Bed6::new(
+                "chr2".to_string(),
+                30,
+                40,
+                "b".to_string(),
+                Score(None),
+                Strand::Unknown,
+            ),
+            Bed6::new(
+                "chr1".to_string(),
+                50,
+                60,
+                "c".to_string(),
+                Score(Some(1.5)),
+                Strand::Reverse,
+            ),
+        ])
+    }
+
+    #[test]
+    fn bed6_columns() {
+        let batch = peaks().to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.num_columns(), 6);
+
+        let chr = batch.column(0).as_dictionary::<Int32Type>();
+        assert_eq!(chr.values().len(), 2);
+        assert_eq!(chr.keys().values(), &[0, 1, 0]);
+
+        let start = batch.column(1).as_primitive::<Int64Type>();
+        assert_eq!(start.values(), &[10, 30, 50]);
+        let score = batch.column(4).as_primitive::<Float64Type>();
+        assert!(score.is_valid(0));
+        assert!(score.is_null(1));
+        let strand = batch.column(5).as_string::<i32>();
+        assert_eq!(strand.value(0), "+");
+        assert!(strand.is_null(1));
+        assert_eq!(strand.value(2), "-");
+    }
+
+    #[test]
+    fn bed6_round_trip() {
+        let batch = peaks().to_record_batch().unwrap();
+        let set: IntervalContainer<Bed6<String, u32, String>, _, _> =
+            IntervalContainer::from_record_batch(&batch).unwrap();
+        let records = set.records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].chr(), "chr1");
+        assert_eq!(records[0].score(), Score(Some(5.0)));
+        assert_eq!(records[1].score(), Score(None));
+        assert_eq!(records[1].strand(), Some(Strand::Unknown));
+        assert_eq!(records[2].name(), "c");
+        assert_eq!(records[2].end(), 60);
+    }
+
+    #[test]
+    fn bed12_round_trip() {
+        let set = IntervalContainer::new(vec![Gene::new(
+            "chr1".to_string(),
+            100,
+            200,
+            "tx".to_string(),
+            Score(Some(0.0)),
+            Strand::Reverse,
+            110,
+            190,
+            "0,0,0".to_string(),
+            2,
+            vec![10, 20],
+            vec![0, 80],
+        )]);
+        let batch = set.to_record_batch().unwrap();
+        let sizes = batch
+            .column_by_name("block_sizes")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(sizes.value(0), "10,20,");
+
+        let observed: IntervalContainer<Gene, _, _> =
+            IntervalContainer::from_record_batch(&batch).unwrap();
+        let record = &observed.records()[0];
+        assert_eq!(record.thick_start(), 110);
+        assert_eq!(record.block_sizes(), &vec![10, 20]);
+        assert_eq!(record.block_starts(), &vec![0, 80]);
+    }
+
+    #[test]
+    fn cast_foreign_columns() {
+        let schema = Schema::new(vec![
+            Field::new("chr", DataType::Utf8, false),
+            Field::new("start", DataType::Int32, false),
+            Field::new("end", DataType::Int32, false),
+            Field::new("extra", DataType::Utf8, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec!["chr3", "chr4"])),
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(Int32Array::from(vec![5, 6])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+            ],
+        )
+        .unwrap();
+        let set: IntervalContainer<Bed3<String, u64>, _, _> =
+            IntervalContainer::from_record_batch(&batch).unwrap();
+        assert_eq!(set.records()[1].chr(), "chr4");
+        assert_eq!(set.records()[1].end(), 6);
+    }
+
+    #[test]
+    fn missing_and_null_columns() {
+        let batch = peaks().to_record_batch().unwrap();
+        let result: Result<IntervalContainer<NarrowPeak<String, u32, String>, _, _>, _> =
+            IntervalContainer::from_record_batch(&batch);
+        assert!(matches!(
+            result,
+            Err(ColumnarError::MissingColumn {
+                column: "signal_value"
+            })
+        ));
+
+        let schema = Schema::new(vec![
+            Field::new("chr", DataType::Utf8, true),
+            Field::new("start", DataType::Int64, true),
+            Field::new("end", DataType::Int64, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec!["chr1"])),
+                Arc::new(arrow_array::Int64Array::from(vec![None])),
+                Arc::new(arrow_array::Int64Array::from(vec![Some(5)])),
+            ],
+        )
+        .unwrap();
+        let result: Result<IntervalContainer<Bed3<String, u32>, _, _>, _> =
+            IntervalContainer::from_record_batch(&batch);
+        assert!(matches!(
+            result,
+            Err(ColumnarError::NullValue { column: "start" })
+        ));
+    }
+
+    #[test]
+    fn unrepresentable_coordinates() {
+        let set = IntervalContainer::new(vec![Bed3::new("chr1".to_string(), 0, u64::MAX)]);
+        assert!(matches!(
+            set.to_record_batch(),
+            Err(ColumnarError::Parse(ParseError::InvalidField {
+                field: "end",
+                ..
+            }))
+        ));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("bedrs-columnar-{}.parquet", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        peaks().write_parquet(file).unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let set: IntervalContainer<Bed6<String, u32, String>, _, _> =
+            IntervalContainer::read_parquet(file).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.records()[1].chr(), "chr2");
+        assert_eq!(set.records()[1].score(), Score(None));
+        assert_eq!(set.records()[2].strand(), Some(Strand::Reverse));
+    }
+}
@@ -0,0 +1,213 @@
+use super::bgzf::{block_size, BgzfReader, GZIP_MAGIC};
+use flate2::bufread::MultiGzDecoder;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// The compression of a stream as detected by an [`AutoDecoder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    /// Uncompressed text
+    Plain,
+    /// Gzip compressed, possibly as several concatenated members
+    Gzip,
+    /// Blocked gzip as written by `bgzip`
+    Bgzf,
+}
+
+enum Inner<R: BufRead> {
+    Plain(R),
+    Gzip(BufReader<MultiGzDecoder<R>>),
+    Bgzf(BgzfReader<R>),
+}
+
+/// A reader transparently decompressing gzip and BGZF streams.
+///
+/// The compression is detected from the magic bytes at the start of the
+/// stream, so plain text passes through unchanged.
+/// Gzip streams made of several concatenated members are read in full.
+///
+/// All `from_path` constructors of the readers in this module go through
+/// an [`AutoDecoder`], and other sources can be wrapped explicitly.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{
+///     io::{AutoDecoder, BedReader, BgzfWriter, CompressionFormat},
+///     Bed3,
+/// };
+/// use std::io::Write;
+///
+/// let mut writer = BgzfWriter::new(Vec::new());
+/// writer.write_all(b"chr1\t10\t20\nchr1\t30\t40\n").unwrap();
+/// let bytes = writer.finish().unwrap();
+///
+/// let decoder = AutoDecoder::new(bytes.as_slice()).unwrap();
+/// assert_eq!(decoder.format(), CompressionFormat::Bgzf);
+///
+/// let reader = BedReader::new(decoder);
+/// let records: Vec<Bed3<String, u32>> = reader.collect::<Result<_, _>>().unwrap();
+/// assert_eq!(records.len(), 2);
+/// ```
+pub struct AutoDecoder<R: BufRead> {
+    inner: Inner<R>,
+}
+
+impl<R: BufRead> AutoDecoder<R> {
+    /// Wraps a reader detecting its compression
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let inner = match detect(reader.fill_buf()?) {
+            CompressionFormat::Plain => Inner::Plain(reader),
+            CompressionFormat::Gzip => Inner::Gzip(BufReader::new(MultiGzDecoder::new(reader))),
+            CompressionFormat::Bgzf => Inner::Bgzf(BgzfReader::new(reader)),
+        };
+        Ok(Self { inner })
+    }
+
+    /// The detected compression of the stream
+    pub fn format(&self) -> CompressionFormat {
+        match self.inner {
+            Inner::Plain(_) => CompressionFormat::Plain,
+            Inner::Gzip(_) => CompressionFormat::Gzip,
+            Inner::Bgzf(_) => CompressionFormat::Bgzf,
+        }
+    }
+
+    /// Consumes the decoder returning the underlying reader
+    pub fn into_inner(self) -> R {
+        match self.inner {
+            Inner::Plain(reader) => reader,
+            Inner::Gzip(reader) => reader.into_inner().into_inner(),
+            Inner::Bgzf(reader) => reader.into_inner(),
+        }
+    }
+}
+
+impl AutoDecoder<BufReader<File>> {
+    /// Opens a file which may be compressed
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+/// Detects the compression of a stream from its leading bytes.
+///
+/// Streams whose header does not fit in the peeked bytes are treated as
+/// gzip, which is also able to read BGZF.
+fn detect(peek: &[u8]) -> CompressionFormat {
+    if peek.len() < 2 || peek[..2] != GZIP_MAGIC {
+        return CompressionFormat::Plain;
+    }
+    if peek.len() >= 12 {
+        let xlen = u16::from_le_bytes([peek[10], peek[11]]) as usize;
+        if let Some(extra) = peek.get(12..12 + xlen) {
+            if block_size(&peek[..12], extra).is_some() {
+                return CompressionFormat::Bgzf;
+            }
+        }
+    }
+    CompressionFormat::Gzip
+}
+
+impl<R: BufRead> Read for AutoDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.read(buf),
+            Inner::Gzip(reader) => reader.read(buf),
+            Inner::Bgzf(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for AutoDecoder<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.fill_buf(),
+            Inner::Gzip(reader) => reader.fill_buf(),
+            Inner::Bgzf(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.consume(amt),
+            Inner::Gzip(reader) => reader.consume(amt),
+            Inner::Bgzf(reader) => reader.consume(amt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::io::BgzfWriter;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn bgzip(text: &str) -> Vec<u8> {
+        let mut writer = BgzfWriter::new(Vec::new());
+        writer.write_all(text.as_bytes()).unwrap();
+        writer.finish().unwrap()
+    }
+
+    fn decode(bytes: &[u8]) -> (CompressionFormat, String) {
+        let mut decoder = AutoDecoder::new(bytes).unwrap();
+        let mut text = String::new();
+        decoder.read_to_string(&mut text).unwrap();
+        (decoder.format(), text)
+    }
+
+    #[test]
+    fn plain_text() {
+        let (format, text) = decode(b"chr1\t10\t20\n");
+        assert_eq!(format, CompressionFormat::Plain);
+        assert_eq!(text, "chr1\t10\t20\n");
+    }
+
+    #[test]
+    fn empty_input() {
+        let (format, text) = decode(b"");
+        assert_eq!(format, CompressionFormat::Plain);
+        assert!(text.is_empty());
+    }
+
+    #[test]
+    fn gzip_text() {
+        let (format, text) = decode(&gzip("chr1\t10\t20\n"));
+        assert_eq!(format, CompressionFormat::Gzip);
+        assert_eq!(text, "chr1\t10\t20\n");
+    }
+
+    #[test]
+    fn gzip_multi_member() {
+        let mut bytes = gzip("chr1\t10\t20\n");
+        bytes.extend(gzip("chr2\t30\t40\n"));
+        let (format, text) = decode(&bytes);
+        assert_eq!(format, CompressionFormat::Gzip);
+        assert_eq!(text, "chr1\t10\t20\nchr2\t30\t40\n");
+    }
+
+    #[test]
+    fn bgzf_text() {
+        let (format, text) = decode(&bgzip("chr1\t10\t20\n"));
+        assert_eq!(format, CompressionFormat::Bgzf);
+        assert_eq!(text, "chr1\t10\t20\n");
+    }
+
+    #[test]
+    fn into_inner_returns_source() {
+        let bytes = b"chr1\t10\t20\n";
+        let decoder = AutoDecoder::new(bytes.as_slice()).unwrap();
+        assert_eq!(decoder.into_inner(), bytes);
+    }
+}
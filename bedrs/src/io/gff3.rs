@@ -0,0 +1,555 @@
+use super::{
+    decoder::AutoDecoder,
+    lines::{parse_record, LineReader},
+};
+use crate::{
+    traits::{fields::parse_value, ChromBounds, MetaBounds, ParseError, ValueBounds},
+    types::ErrorPolicy,
+    Bed3, Gff3,
+};
+use num_traits::One;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+/// A single parsed line of a GFF3 stream
+enum Gff3Line<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    Record(Gff3<C, T, N>),
+    Flush,
+}
+
+/// A streaming reader of [`Gff3`] records.
+///
+/// GFF3 coordinates are converted from the 1-based inclusive convention
+/// to the half-open convention used throughout the library.
+///
+/// The following directives are handled:
+/// - `##sequence-region` directives are collected and available through
+///   [`Gff3Reader::sequence_regions`].
+/// - `###` markers signal that all forward references have been resolved
+///   and delimit the feature graphs returned by [`Gff3Reader::next_graph`].
+/// - `##FASTA` (or a line starting with `>`) ends the feature section.
+///
+/// All other comment and directive lines are skipped.
+///
+/// Malformed lines, including `##sequence-region` directives, are handled
+/// according to the [`ErrorPolicy`] of the reader.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::Gff3Reader, Coordinates};
+///
+/// let text = "##gff-version 3\n##sequence-region chr1 1 5000\n\
+///     chr1\t.\tgene\t1000\t4000\t.\t+\t.\tID=gene1\n\
+///     chr1\t.\tmRNA\t1000\t4000\t.\t+\t.\tID=tx1;Parent=gene1\n\
+///     chr1\t.\texon\t1000\t2000\t.\t+\t.\tID=exon1;Parent=tx1\n###\n";
+///
+/// let mut reader: Gff3Reader<_, String, u64, String> = Gff3Reader::new(text.as_bytes());
+/// let graph = reader.next_graph().unwrap().unwrap();
+/// assert_eq!(graph.len(), 3);
+///
+/// let gene = graph.roots()[0];
+/// let tx = graph.children(gene)[0];
+/// let exon = graph.children(tx)[0];
+/// assert_eq!(graph.record(exon).id(), Some("exon1"));
+/// assert_eq!(graph.record(exon).start(), 999);
+///
+/// let region = &reader.sequence_regions()[0];
+/// assert_eq!(region.end(), 5000);
+/// ```
+pub struct Gff3Reader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    lines: LineReader<R>,
+    regions: Vec<Bed3<C, T>>,
+    finished: bool,
+    phantom_n: std::marker::PhantomData<N>,
+}
+
+impl<R, C, T, N> Gff3Reader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    pub fn new(reader: R) -> Self {
+        Self::from_lines(LineReader::new(reader))
+    }
+
+    fn from_lines(lines: LineReader<R>) -> Self {
+        Self {
+            lines,
+            regions: Vec::new(),
+            finished: false,
+            phantom_n: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets how malformed lines are handled
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.lines.set_policy(policy);
+        self
+    }
+
+    /// Returns the number of malformed lines skipped so far
+    pub fn skipped(&self) -> usize {
+        self.lines.skipped()
+    }
+
+    /// Returns the number of malformed lines repaired so far
+    pub fn repaired(&self) -> usize {
+        self.lines.repaired()
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        self.lines.get_ref()
+    }
+
+    /// Consumes the reader returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.lines.into_inner()
+    }
+
+    /// Returns the `##sequence-region` directives read so far.
+    ///
+    /// Regions are stored in half-open coordinates.
+    pub fn sequence_regions(&self) -> &[Bed3<C, T>] {
+        &self.regions
+    }
+
+    /// Reads records until the next `###` marker (or the end of the
+    /// feature section) and resolves their `ID` / `Parent` relationships
+    /// into a [`FeatureGraph`].
+    ///
+    /// Returns `None` once the reader is exhausted.
+    pub fn next_graph(&mut self) -> Option<Result<FeatureGraph<C, T, N>, ParseError>> {
+        let mut records = Vec::new();
+        loop {
+            match self.next_line() {
+                Ok(Some(Gff3Line::Record(record))) => records.push(record),
+                Ok(Some(Gff3Line::Flush)) => {
+                    if !records.is_empty() {
+                        break;
+                    }
+                }
+                Ok(None) => {
+                    if records.is_empty() {
+                        return None;
+                    }
+                    break;
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(FeatureGraph::new(records))
+    }
+
+    /// Reads the next record or flush marker
+    fn next_line(&mut self) -> Result<Option<Gff3Line<C, T, N>>, ParseError> {
+        while !self.finished {
+            if !self.lines.read_line()? {
+                self.finished = true;
+                break;
+            }
+            let line = self.lines.line();
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.starts_with("###") {
+                return Ok(Some(Gff3Line::Flush));
+            }
+            if line.starts_with("##FASTA") || line.starts_with('>') {
+                self.finished = true;
+                break;
+            }
+            if let Some(region) = line.strip_prefix("##sequence-region") {
+                let result = parse_sequence_region(region).map(|region| (region, false));
+                if let Some(region) = self.lines.resolve(result) {
+                    self.regions.push(region?);
+                }
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+            let repair = self.lines.policy() == ErrorPolicy::Repair;
+            let mut fields: Vec<&str> = line.split('\t').collect();
+            let result = parse_value(fields[0], "chr")
+                .and_then(|chr| parse_record(chr, &mut fields, repair));
+            if let Some(record) = self.lines.resolve(result) {
+                return record.map(|r| Some(Gff3Line::Record(r)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<C, T, N> Gff3Reader<AutoDecoder<BufReader<File>>, C, T, N>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    /// Opens a file for reading records, decompressing gzip and BGZF
+    /// files on the fly
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let reader = AutoDecoder::from_path(&path)?;
+        let lines = LineReader::new(reader).with_path(path.as_ref().to_path_buf());
+        Ok(Self::from_lines(lines))
+    }
+}
+
+impl<R, C, T, N> Iterator for Gff3Reader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    type Item = Result<Gff3<C, T, N>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_line() {
+                Ok(Some(Gff3Line::Record(record))) => return Some(Ok(record)),
+                Ok(Some(Gff3Line::Flush)) => {}
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Parses the body of a `##sequence-region seqid start end` directive
+fn parse_sequence_region<C, T>(text: &str) -> Result<Bed3<C, T>, ParseError>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    let mut tokens = text.split_whitespace();
+    let chr = tokens
+        .next()
+        .ok_or(ParseError::MissingField { field: "seqid" })?;
+    let start = tokens
+        .next()
+        .ok_or(ParseError::MissingField { field: "start" })?;
+    let end = tokens
+        .next()
+        .ok_or(ParseError::MissingField { field: "end" })?;
+    let start_value: T = parse_value(start, "start")?;
+    if start_value < T::one() {
+        return Err(ParseError::InvalidField {
+            field: "start",
+            value: start.to_string(),
+        });
+    }
+    Ok(Bed3::new(
+        parse_value(chr, "seqid")?,
+        start_value - T::one(),
+        parse_value(end, "end")?,
+    ))
+}
+
+/// A set of [`Gff3`] records with their `ID` / `Parent` relationships
+/// resolved.
+///
+/// Records are addressed by their index in the graph.
+/// A record may have multiple parents (e.g. an exon shared by several
+/// transcripts), so the hierarchy is a directed acyclic graph whose
+/// roots are the records without a `Parent` attribute (typically genes).
+///
+/// Records sharing the same `ID` (e.g. the parts of a discontinuous CDS)
+/// are all kept, and children are attached to the first of them.
+#[derive(Debug, Clone)]
+pub struct FeatureGraph<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    records: Vec<Gff3<C, T, N>>,
+    parents: Vec<Vec<usize>>,
+    children: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    ids: HashMap<String, usize>,
+}
+
+impl<C, T, N> FeatureGraph<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Resolves the `Parent` references of the provided records.
+    ///
+    /// Returns an error if a record references an `ID` that is not
+    /// present in the records.
+    pub fn new(records: Vec<Gff3<C, T, N>>) -> Result<Self, ParseError> {
+        let mut ids = HashMap::new();
+        for (idx, record) in records.iter().enumerate() {
+            if let Some(id) = record.id() {
+                ids.entry(id.to_string()).or_insert(idx);
+            }
+        }
+        let mut parents = vec![Vec::new(); records.len()];
+        let mut children = vec![Vec::new(); records.len()];
+        let mut roots = Vec::new();
+        for (idx, record) in records.iter().enumerate() {
+            for parent in record.parents() {
+                let Some(&pidx) = ids.get(parent) else {
+                    return Err(ParseError::InvalidField {
+                        field: "Parent",
+                        value: parent.to_string(),
+                    });
+                };
+                parents[idx].push(pidx);
+                children[pidx].push(idx);
+            }
+            if parents[idx].is_empty() {
+                roots.push(idx);
+            }
+        }
+        Ok(Self {
+            records,
+            parents,
+            children,
+            roots,
+            ids,
+        })
+    }
+
+    /// Returns the number of records in the graph
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if the graph holds no records
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns all records of the graph in input order
+    #[must_use]
+    pub fn records(&self) -> &[Gff3<C, T, N>] {
+        &self.records
+    }
+
+    /// Returns the record at the provided index
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds
+    #[must_use]
+    pub fn record(&self, idx: usize) -> &Gff3<C, T, N> {
+        &self.records[idx]
+    }
+
+    /// Returns the indices of the records without a parent
+    #[must_use]
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Returns the indices of the direct children of a record
+    #[must_use]
+    pub fn children(&self, idx: usize) -> &[usize] {
+        &self.children[idx]
+    }
+
+    /// Returns the indices of the direct parents of a record
+    #[must_use]
+    pub fn parents(&self, idx: usize) -> &[usize] {
+        &self.parents[idx]
+    }
+
+    /// Returns the index of the first record with the provided `ID`
+    #[must_use]
+    pub fn find(&self, id: &str) -> Option<usize> {
+        self.ids.get(id).copied()
+    }
+
+    /// Returns the indices of all descendants of a record in depth-first
+    /// order.
+    ///
+    /// Records reachable through several paths are reported once.
+    #[must_use]
+    pub fn descendants(&self, idx: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.records.len()];
+        let mut stack: Vec<usize> = self.children[idx].iter().rev().copied().collect();
+        let mut descendants = Vec::new();
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            descendants.push(node);
+            stack.extend(self.children[node].iter().rev());
+        }
+        descendants
+    }
+
+    /// Consumes the graph returning the underlying records
+    #[must_use]
+    pub fn into_records(self) -> Vec<Gff3<C, T, N>> {
+        self.records
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Coordinates, Strand};
+
+    const GFF3: &str = "##gff-version 3\n\
+        ##sequence-region chr1 1 100000\n\
+        ##sequence-region chr2 1 5000\n\
+        chr1\tsrc\tgene\t1000\t9000\t.\t+\t.\tID=gene1;Name=EDEN\n\
+        chr1\tsrc\tmRNA\t1050\t9000\t.\t+\t.\tID=mRNA1;Parent=gene1\n\
+        chr1\tsrc\tmRNA\t1300\t9000\t.\t+\t.\tID=mRNA2;Parent=gene1\n\
+        chr1\tsrc\texon\t1300\t1500\t.\t+\t.\tID=exon1;Parent=mRNA1,mRNA2\n\
+        chr1\tsrc\tCDS\t1301\t1400\t.\t+\t0\tID=cds1;Parent=mRNA1\n\
+        chr1\tsrc\tCDS\t3000\t3902\t.\t+\t0\tID=cds1;Parent=mRNA1\n\
+        ###\n\
+        # a comment\n\
+        chr2\tsrc\tgene\t10\t20\t.\t-\t.\tID=gene2;Note=a%3Bb\n\
+        ##FASTA\n\
+        >chr1\n\
+        ACGT\n";
+
+    type Reader<'a> = Gff3Reader<&'a [u8], String, u64, String>;
+
+    #[test]
+    fn read_records() {
+        let reader: Reader = Gff3Reader::new(GFF3.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 7);
+        assert_eq!(records[0].start(), 999);
+        assert_eq!(records[0].end(), 9000);
+        assert_eq!(records[0].name(), Some("EDEN"));
+        assert_eq!(records[6].attributes().get("Note"), Some("a;b"));
+    }
+
+    #[test]
+    fn read_sequence_regions() {
+        let mut reader: Reader = Gff3Reader::new(GFF3.as_bytes());
+        while reader.next().is_some() {}
+        let regions = reader.sequence_regions();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].chr(), "chr1");
+        assert_eq!(regions[0].start(), 0);
+        assert_eq!(regions[0].end(), 100_000);
+        assert_eq!(regions[1].chr(), "chr2");
+    }
+
+    #[test]
+    fn read_invalid_sequence_region() {
+        let text = "##sequence-region chr1 1\n";
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes());
+        assert!(reader.next().unwrap().is_err());
+        let text = "##sequence-region chr1 0 10\n";
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes());
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn read_invalid_line_number() {
+        let text = "##gff-version 3\n\nchr1\t.\tgene\tx\t20\t.\t+\t.\tID=g1\n";
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(3));
+        assert!(matches!(
+            err.inner(),
+            ParseError::InvalidField { field: "start", .. }
+        ));
+    }
+
+    #[test]
+    fn read_graphs() {
+        let mut reader: Reader = Gff3Reader::new(GFF3.as_bytes());
+
+        let graph = reader.next_graph().unwrap().unwrap();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.roots(), &[0]);
+        assert_eq!(graph.children(0), &[1, 2]);
+        assert_eq!(graph.children(1), &[3, 4, 5]);
+        assert_eq!(graph.children(2), &[3]);
+        assert_eq!(graph.parents(3), &[1, 2]);
+        assert_eq!(graph.find("cds1"), Some(4));
+        assert_eq!(graph.descendants(0), vec![1, 3, 4, 5, 2]);
+
+        let graph = reader.next_graph().unwrap().unwrap();
+        assert_eq!(graph.len(), 1);
+        assert_eq!(graph.record(0).id(), Some("gene2"));
+
+        assert!(reader.next_graph().is_none());
+    }
+
+    #[test]
+    fn read_graph_forward_reference() {
+        let text = "chr1\t.\texon\t10\t20\t.\t+\t.\tParent=tx1\n\
+            chr1\t.\tmRNA\t10\t50\t.\t+\t.\tID=tx1\n";
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes());
+        let graph = reader.next_graph().unwrap().unwrap();
+        assert_eq!(graph.roots(), &[1]);
+        assert_eq!(graph.children(1), &[0]);
+    }
+
+    #[test]
+    fn read_graph_unknown_parent() {
+        let text = "chr1\t.\texon\t10\t20\t.\t+\t.\tParent=tx1\n###\n";
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes());
+        match reader.next_graph().unwrap() {
+            Err(ParseError::InvalidField { field, value }) => {
+                assert_eq!(field, "Parent");
+                assert_eq!(value, "tx1");
+            }
+            _ => panic!("Expected unknown parent"),
+        }
+    }
+
+    #[test]
+    fn read_error_policy() {
+        let text = "##sequence-region chr1 0 10\n\
+            ##sequence-region chr2 1 10\n\
+            chr1\t.\tgene\tx\t20\t.\t+\t.\tID=g1\n\
+            chr1\t.\tgene\t50\t40\t.\t?\t.\tID=g2\n\
+            chr1\t.\tgene\t60\t70\t.\t-\t.\tID=g3\n";
+
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes()).error_policy(ErrorPolicy::Skip);
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id(), Some("g3"));
+        assert_eq!(reader.sequence_regions().len(), 1);
+        assert_eq!(reader.skipped(), 3);
+        assert_eq!(reader.repaired(), 0);
+
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes()).error_policy(ErrorPolicy::Repair);
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].start(), records[0].end()), (39, 50));
+        assert_eq!(records[0].strand(), Some(Strand::Unknown));
+        assert_eq!(reader.skipped(), 2);
+        assert_eq!(reader.repaired(), 1);
+
+        let mut reader: Reader = Gff3Reader::new(text.as_bytes());
+        assert_eq!(reader.next().unwrap().unwrap_err().line(), Some(1));
+    }
+}
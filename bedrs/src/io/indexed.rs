@@ -0,0 +1,434 @@
+use super::{
+    bgzf::BgzfReader,
+    reader::is_skipped,
+    tabix::{Chunk, TabixIndex},
+};
+use crate::{
+    traits::{
+        fields::parse_value, ChromBounds, FromFields, IntervalBounds, ParseError, ValueBounds,
+    },
+    types::Query,
+};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader, Read, Seek},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    str::FromStr,
+    vec,
+};
+
+/// A reader fetching records by region from a BGZF-compressed file
+/// through its [`TabixIndex`].
+///
+/// Only the chunks of the file listed by the index for a region are
+/// decompressed, and the records found there are filtered with a
+/// [`Query`] so that the same overlap and strand methods used by the
+/// [`IntervalContainer`](crate::IntervalContainer) apply.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{
+///     io::{BgzfWriter, IndexConfig, IndexedReader, TabixIndex},
+///     types::{Query, QueryMethod},
+///     Bed3, Coordinates,
+/// };
+/// use std::io::{Cursor, Write};
+///
+/// let mut writer = BgzfWriter::new(Vec::new());
+/// writer
+///     .write_all(b"chr1\t10\t20\nchr1\t30\t40\nchr1\t50\t60\nchr2\t5\t15\n")
+///     .unwrap();
+/// let bytes = writer.finish().unwrap();
+/// let index = TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).unwrap();
+///
+/// let mut reader: IndexedReader<_, Bed3<String, u32>, _, _> =
+///     IndexedReader::new(Cursor::new(bytes), index);
+///
+/// let region: Bed3<String, u32> = "chr1:18-35".parse().unwrap();
+/// let records = reader
+///     .fetch(&region)
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[1].start(), 30);
+///
+/// // require at least 5bp of overlap
+/// let query = Query::new_predicate(QueryMethod::CompareBy(5));
+/// let records = reader
+///     .query(&region, query)
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].start(), 30);
+/// ```
+pub struct IndexedReader<R, I, C, T>
+where
+    R: Read + Seek,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    reader: BgzfReader<R>,
+    index: TabixIndex,
+    phantom_i: PhantomData<I>,
+    phantom_c: PhantomData<C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<R, I, C, T> IndexedReader<R, I, C, T>
+where
+    R: Read + Seek,
+    I: FromFields<C, T> + IntervalBounds<C, T>,
+    C: ChromBounds + FromStr + Display,
+    T: ValueBounds,
+{
+    /// Creates a reader over a BGZF-compressed stream and its index
+    pub fn new(reader: R, index: TabixIndex) -> Self {
+        Self {
+            reader: BgzfReader::new(reader),
+            index,
+            phantom_i: PhantomData,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+        }
+    }
+
+    /// Returns the index of the reader
+    pub fn index(&self) -> &TabixIndex {
+        &self.index
+    }
+
+    /// Consumes the reader returning the underlying reader and its index
+    pub fn into_inner(self) -> (R, TabixIndex) {
+        (self.reader.into_inner(), self.index)
+    }
+
+    /// Fetches all records overlapping a region
+    pub fn fetch<Iv>(
+        &mut self,
+        region: &Iv,
+    ) -> Result<IndexedQueryIter<'_, R, I, Iv, C, T>, ParseError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        self.query(region, Query::default())
+    }
+
+    /// Fetches the records of a region satisfying a [`Query`].
+    ///
+    /// Records are the targets of the query and the region its query
+    /// interval, as in [`IntervalContainer::query_iter`](crate::IntervalContainer::query_iter).
+    pub fn query<Iv>(
+        &mut self,
+        region: &Iv,
+        query: Query<T>,
+    ) -> Result<IndexedQueryIter<'_, R, I, Iv, C, T>, ParseError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        let start = region.start().to_u64().unwrap_or(0);
+        let end = region.end().to_u64().unwrap_or(u64::MAX);
+        let chunks = self.index.chunks(&region.chr().to_string(), start, end);
+        Ok(IndexedQueryIter {
+            reader: &mut self.reader,
+            config_meta: self.index.config().meta(),
+            chunks: chunks.into_iter(),
+            current: None,
+            region: region.clone(),
+            query,
+            buffer: String::new(),
+            phantom_i: PhantomData,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+        })
+    }
+}
+
+impl<I, C, T> IndexedReader<BufReader<File>, I, C, T>
+where
+    I: FromFields<C, T> + IntervalBounds<C, T>,
+    C: ChromBounds + FromStr + Display,
+    T: ValueBounds,
+{
+    /// Opens a BGZF-compressed file along with its index, looking for
+    /// `<path>.tbi` and then `<path>.csi`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        let with_suffix = |suffix: &str| {
+            let mut name = path.as_os_str().to_owned();
+            name.push(suffix);
+            PathBuf::from(name)
+        };
+        let tbi = with_suffix(".tbi");
+        let index_path = if tbi.exists() {
+            tbi
+        } else {
+            with_suffix(".csi")
+        };
+        Self::from_paths(path, index_path)
+    }
+
+    /// Opens a BGZF-compressed file along with an explicit index file
+    pub fn from_paths<P: AsRef<Path>, Q: AsRef<Path>>(
+        path: P,
+        index_path: Q,
+    ) -> Result<Self, ParseError> {
+        let index = TabixIndex::from_path(index_path)?;
+        let file = File::open(path)?;
+        Ok(Self::new(BufReader::new(file), index))
+    }
+}
+
+/// An iterator over the records of an [`IndexedReader`] overlapping a
+/// region.
+pub struct IndexedQueryIter<'a, R, I, Iv, C, T>
+where
+    R: Read + Seek,
+    T: ValueBounds,
+{
+    reader: &'a mut BgzfReader<R>,
+    config_meta: u8,
+    chunks: vec::IntoIter<Chunk>,
+    current: Option<Chunk>,
+    region: Iv,
+    query: Query<T>,
+    buffer: String,
+    phantom_i: PhantomData<I>,
+    phantom_c: PhantomData<C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<R, I, Iv, C, T> IndexedQueryIter<'_, R, I, Iv, C, T>
+where
+    R: Read + Seek,
+    I: FromFields<C, T> + IntervalBounds<C, T>,
+    Iv: IntervalBounds<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    /// Reads the next record line within the current chunks.
+    ///
+    /// Returns `false` once all chunks are exhausted.
+    fn next_line(&mut self) -> Result<bool, ParseError> {
+        loop {
+            let chunk = match self.current {
+                Some(chunk) => chunk,
+                None => match self.chunks.next() {
+                    Some(chunk) => {
+                        self.reader.seek_virtual(chunk.start())?;
+                        self.current = Some(chunk);
+                        chunk
+                    }
+                    None => return Ok(false),
+                },
+            };
+            // loading the next block first keeps offsets at block
+            // boundaries comparable with those of the index
+            self.buffer.clear();
+            if self.reader.fill_buf()?.is_empty()
+                || self.reader.virtual_offset() >= chunk.end()
+                || self.reader.read_line(&mut self.buffer)? == 0
+            {
+                self.current = None;
+                continue;
+            }
+            let line = self.buffer.trim_end_matches(['\n', '\r']);
+            if !is_skipped(line) && line.as_bytes().first() != Some(&self.config_meta) {
+                let len = line.len();
+                self.buffer.truncate(len);
+                return Ok(true);
+            }
+        }
+    }
+
+    fn parse_line(&self) -> Result<I, ParseError> {
+        let fields: Vec<&str> = self.buffer.split('\t').collect();
+        let chr = parse_value(fields[0], "chr")?;
+        I::from_fields(chr, &fields)
+    }
+}
+
+impl<R, I, Iv, C, T> Iterator for IndexedQueryIter<'_, R, I, Iv, C, T>
+where
+    R: Read + Seek,
+    I: FromFields<C, T> + IntervalBounds<C, T>,
+    Iv: IntervalBounds<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    type Item = Result<I, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_line() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+            let record = match self.parse_line() {
+                Ok(record) => record,
+                Err(e) => return Some(Err(e)),
+            };
+            if record.chr() != self.region.chr() {
+                continue;
+            }
+            // records are sorted so nothing past the region can overlap it
+            if record.start() >= self.region.end() {
+                self.current = None;
+                self.chunks = Vec::new().into_iter();
+                return None;
+            }
+            if self.query.predicate(&record, &self.region) {
+                return Some(Ok(record));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        io::{BgzfWriter, IndexConfig},
+        types::{QueryMethod, StrandMethod},
+        Bed3, Bed6, Coordinates, Gtf, Strand, StrandedBed3,
+    };
+    use std::io::{Cursor, Write};
+
+    fn indexed<I>(text: &str, config: IndexConfig) -> IndexedReader<Cursor<Vec<u8>>, I, String, u32>
+    where
+        I: FromFields<String, u32> + IntervalBounds<String, u32>,
+    {
+        let mut writer = BgzfWriter::new(Vec::new());
+        writer.write_all(text.as_bytes()).unwrap();
+        let bytes = writer.finish().unwrap();
+        let index = TabixIndex::build(bytes.as_slice(), config).unwrap();
+        IndexedReader::new(Cursor::new(bytes), index)
+    }
+
+    fn large_bed() -> String {
+        let records: String = ["chr1", "chr2"]
+            .iter()
+            .flat_map(|chr| {
+                (0..5000u32).map(move |i| format!("{chr}\t{}\t{}\n", i * 100, i * 100 + 150))
+            })
+            .collect();
+        format!("#chrom\tstart\tend\n{records}")
+    }
+
+    #[test]
+    fn fetch_matches_linear_scan() {
+        let text = large_bed();
+        let mut reader = indexed::<Bed3<String, u32>>(&text, IndexConfig::bed());
+        for (start, end) in [
+            (0, 10),
+            (12_345, 67_890),
+            (250_000, 260_000),
+            (499_990, 600_000),
+        ] {
+            let region = Bed3::new("chr2".to_string(), start, end);
+            let fetched = reader
+                .fetch(&region)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let expected: Vec<_> = text
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let f: Vec<&str> = line.split('\t').collect();
+                    Bed3::new(
+                        f[0].to_string(),
+                        f[1].parse().unwrap(),
+                        f[2].parse().unwrap(),
+                    )
+                })
+                .filter(|iv| iv.chr() == "chr2" && iv.start() < end && iv.end() > start)
+                .collect();
+            let span = |iv: &Bed3<String, u32>| (iv.start(), iv.end());
+            assert_eq!(
+                fetched.iter().map(span).collect::<Vec<_>>(),
+                expected.iter().map(span).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn fetch_unknown_chromosome() {
+        let mut reader = indexed::<Bed3<String, u32>>("chr1\t10\t20\n", IndexConfig::bed());
+        let region = Bed3::new("chr9".to_string(), 0, 100);
+        assert_eq!(reader.fetch(&region).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn fetch_whole_chromosome() {
+        let text = "chr1\t10\t20\nchr1\t30\t40\nchr2\t5\t15\n";
+        let mut reader = indexed::<Bed3<String, u32>>(text, IndexConfig::bed());
+        let region: Bed3<String, u32> = "chr1".parse().unwrap();
+        assert_eq!(reader.fetch(&region).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn query_strand_method() {
+        let text = "chr1\t10\t20\ta\t0\t+\nchr1\t15\t25\tb\t0\t-\n";
+        let mut reader = indexed::<Bed6<String, u32, String>>(text, IndexConfig::bed());
+        let region = StrandedBed3::new("chr1".to_string(), 0, 100, Strand::Forward);
+        let query = Query::new(QueryMethod::Compare, StrandMethod::MatchStrand);
+        let records = reader
+            .query(&region, query)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name(), "a");
+    }
+
+    #[test]
+    fn fetch_gtf_records() {
+        let text = "chr1\tsrc\tgene\t11\t20\t.\t+\t.\tgene_id \"a\";\n\
+            chr1\tsrc\tgene\t101\t200\t.\t+\t.\tgene_id \"b\";\n";
+        let mut reader = indexed::<Gtf<String, u32, String>>(text, IndexConfig::gff());
+        let region = Bed3::new("chr1".to_string(), 19, 20);
+        let records = reader
+            .fetch(&region)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].start(), 10);
+        let region = Bed3::new("chr1".to_string(), 20, 100);
+        assert_eq!(reader.fetch(&region).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn index_files_round_trip() {
+        let dir = std::env::temp_dir().join(format!("bedrs-indexed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("records.bed.gz");
+        let mut writer = BgzfWriter::from_path(&path).unwrap();
+        writer.write_all(large_bed().as_bytes()).unwrap();
+        writer.finish().unwrap();
+
+        let index = TabixIndex::build(File::open(&path).unwrap(), IndexConfig::bed()).unwrap();
+        index
+            .write_tbi(File::create(dir.join("records.bed.gz.tbi")).unwrap())
+            .unwrap();
+        let mut reader: IndexedReader<_, Bed3<String, u32>, _, _> =
+            IndexedReader::from_path(&path).unwrap();
+        let region = Bed3::new("chr1".to_string(), 1000, 1100);
+        assert_eq!(reader.fetch(&region).unwrap().count(), 2);
+
+        std::fs::remove_file(dir.join("records.bed.gz.tbi")).unwrap();
+        index
+            .write_csi(File::create(dir.join("records.bed.gz.csi")).unwrap())
+            .unwrap();
+        let mut reader: IndexedReader<_, Bed3<String, u32>, _, _> =
+            IndexedReader::from_path(&path).unwrap();
+        assert_eq!(reader.fetch(&region).unwrap().count(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
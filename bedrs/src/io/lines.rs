@@ -0,0 +1,255 @@
+use crate::{
+    traits::{ChromBounds, FromFields, ParseError, ValueBounds},
+    types::ErrorPolicy,
+    Strand,
+};
+use std::{io::BufRead, path::PathBuf, str::FromStr};
+
+/// A line-by-line reader shared by the text readers.
+///
+/// Keeps track of the line number (and the path, if the reader was opened
+/// from a file) so that errors can be located, and applies an
+/// [`ErrorPolicy`] to the records parsed from each line.
+pub(crate) struct LineReader<R: BufRead> {
+    reader: R,
+    buffer: String,
+    path: Option<PathBuf>,
+    line: usize,
+    policy: ErrorPolicy,
+    skipped: usize,
+    repaired: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            path: None,
+            line: 0,
+            policy: ErrorPolicy::default(),
+            skipped: 0,
+            repaired: 0,
+        }
+    }
+
+    /// Sets the path reported by located errors
+    pub(crate) fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub(crate) fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Returns the current line without its line ending
+    pub(crate) fn line(&self) -> &str {
+        &self.buffer
+    }
+
+    pub(crate) fn policy(&self) -> ErrorPolicy {
+        self.policy
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: ErrorPolicy) {
+        self.policy = policy;
+    }
+
+    pub(crate) fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    pub(crate) fn repaired(&self) -> usize {
+        self.repaired
+    }
+
+    /// Reads the next line into the buffer, removing its line ending.
+    ///
+    /// Returns `false` once the reader is exhausted.
+    pub(crate) fn read_line(&mut self) -> Result<bool, ParseError> {
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => Ok(false),
+            Ok(_) => {
+                self.line += 1;
+                let len = self.buffer.trim_end_matches(['\n', '\r']).len();
+                self.buffer.truncate(len);
+                Ok(true)
+            }
+            Err(e) => {
+                self.line += 1;
+                Err(self.locate(e.into()))
+            }
+        }
+    }
+
+    /// Reads lines until one is not matched by `skip`.
+    ///
+    /// Returns `false` once the reader is exhausted.
+    pub(crate) fn next_line(&mut self, skip: impl Fn(&str) -> bool) -> Result<bool, ParseError> {
+        while self.read_line()? {
+            if !skip(&self.buffer) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Attaches the current line (and path) to an error
+    pub(crate) fn locate(&self, source: ParseError) -> ParseError {
+        ParseError::Line {
+            path: self.path.clone(),
+            line: self.line,
+            source: Box::new(source),
+        }
+    }
+
+    /// Applies the error policy to the result of parsing the current line.
+    ///
+    /// Returns `None` if the line was skipped.
+    pub(crate) fn resolve<I>(
+        &mut self,
+        result: Result<(I, bool), ParseError>,
+    ) -> Option<Result<I, ParseError>> {
+        match result {
+            Ok((record, repaired)) => {
+                self.repaired += usize::from(repaired);
+                Some(Ok(record))
+            }
+            Err(e) if self.policy == ErrorPolicy::Fail => Some(Err(self.locate(e))),
+            Err(_) => {
+                self.skipped += 1;
+                None
+            }
+        }
+    }
+}
+
+/// Builds a record from the fields of a line, validating its intervals.
+///
+/// A start past its end (see [`FromFields::INTERVAL_FIELDS`]) is an
+/// error, unless `repair` is set in which case the two are swapped and
+/// an unrecognized strand (see [`FromFields::STRAND_FIELD`]) is read as
+/// unknown.
+/// Returns the record and whether it was repaired.
+pub(crate) fn parse_record<I, C, T>(
+    chr: C,
+    fields: &mut [&str],
+    repair: bool,
+) -> Result<(I, bool), ParseError>
+where
+    I: FromFields<C, T>,
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+{
+    let mut repaired = false;
+    for &((start_idx, start_field), (end_idx, end_field)) in I::INTERVAL_FIELDS {
+        let (Some(start), Some(end)) = (fields.get(start_idx), fields.get(end_idx)) else {
+            continue;
+        };
+        let (Ok(start_value), Ok(end_value)) = (start.parse::<T>(), end.parse::<T>()) else {
+            continue;
+        };
+        if start_value > end_value {
+            if !repair {
+                return Err(ParseError::InvalidInterval {
+                    start_field,
+                    start: (*start).to_string(),
+                    end_field,
+                    end: (*end).to_string(),
+                });
+            }
+            fields.swap(start_idx, end_idx);
+            repaired = true;
+        }
+    }
+    if let Some(idx) = I::STRAND_FIELD.filter(|_| repair) {
+        if let Some(field) = fields.get_mut(idx) {
+            if field.parse::<Strand>().is_err() {
+                *field = ".";
+                repaired = true;
+            }
+        }
+    }
+    I::from_fields(chr, fields).map(|record| (record, repaired))
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Bed3, Bed6, BedPe, Coordinates};
+
+    #[test]
+    fn test_line_numbers() {
+        let text = "a\r\n\nb\n";
+        let mut lines = LineReader::new(text.as_bytes());
+        assert!(lines.next_line(str::is_empty).unwrap());
+        assert_eq!(lines.line(), "a");
+        assert!(lines.next_line(str::is_empty).unwrap());
+        assert_eq!(lines.line(), "b");
+        let err = lines.locate(ParseError::MissingField { field: "end" });
+        assert_eq!(err.line(), Some(3));
+        assert!(!lines.next_line(str::is_empty).unwrap());
+    }
+
+    #[test]
+    fn test_parse_record_inverted() {
+        let mut fields = ["chr1", "20", "10"];
+        let result: Result<(Bed3<String, u64>, bool), _> =
+            parse_record("chr1".to_string(), &mut fields, false);
+        assert!(matches!(result, Err(ParseError::InvalidInterval { .. })));
+
+        let (record, repaired): (Bed3<String, u64>, _) =
+            parse_record("chr1".to_string(), &mut fields, true).unwrap();
+        assert!(repaired);
+        assert_eq!((record.start(), record.end()), (10, 20));
+
+        let mut fields = ["chr1", "10", "20", "chr2", "50", "40"];
+        let result: Result<(BedPe<String, u64, String>, bool), _> =
+            parse_record("chr1".to_string(), &mut fields, false);
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidInterval { start_field: "start2", start, end_field: "end2", end })
+                if start == "50" && end == "40"
+        ));
+    }
+
+    #[test]
+    fn test_parse_record_strand() {
+        let mut fields = ["chr1", "10", "20", "a", "0", "?"];
+        let result: Result<(Bed6<String, u64, String>, bool), _> =
+            parse_record("chr1".to_string(), &mut fields, false);
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidField {
+                field: "strand",
+                ..
+            })
+        ));
+
+        let (record, repaired): (Bed6<String, u64, String>, _) =
+            parse_record("chr1".to_string(), &mut fields, true).unwrap();
+        assert!(repaired);
+        assert_eq!(record.strand(), Some(Strand::Unknown));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut lines = LineReader::new("".as_bytes());
+        let err = || Err::<(u32, bool), _>(ParseError::MissingField { field: "end" });
+        assert!(matches!(
+            lines.resolve(err()),
+            Some(Err(ParseError::Line { .. }))
+        ));
+        lines.set_policy(ErrorPolicy::Skip);
+        assert!(lines.resolve(err()).is_none());
+        assert!(matches!(lines.resolve(Ok((1, true))), Some(Ok(1))));
+        assert_eq!(lines.skipped(), 1);
+        assert_eq!(lines.repaired(), 1);
+    }
+}
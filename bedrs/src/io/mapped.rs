@@ -0,0 +1,957 @@
+use crate::{
+    traits::{ChromBounds, IntervalBounds, MappedError, SetError, ValueBounds},
+    types::{Query, StrandMethod},
+    Coordinates, Distance, FromFields, IntervalContainer, Overlap, Strand, ToFields,
+};
+use memmap2::Mmap;
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    marker::PhantomData,
+    path::Path,
+    str::FromStr,
+};
+
+/// The leading bytes of every interval file
+const MAGIC: [u8; 8] = *b"BEDRSIVC";
+
+/// The version of the interval file format written by this library
+const VERSION: u32 = 1;
+
+/// The size of the fixed header in bytes
+const HEADER_SIZE: usize = 48;
+
+/// The size of a single entry of the record table in bytes
+const ENTRY_SIZE: usize = 32;
+
+/// Header flag set when the records are stored in sorted order
+const FLAG_SORTED: u32 = 1;
+
+/// Header flag set when the maximum record length is known
+const FLAG_MAX_LEN: u32 = 1 << 1;
+
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T> + ToFields<C, T>,
+    C: ChromBounds + Display,
+    T: ValueBounds,
+{
+    /// Writes the container in the binary format read by a [`MappedContainer`].
+    ///
+    /// Records are always written in sorted order (an unsorted container is
+    /// sorted on the fly without being modified) so that the file can be
+    /// searched as soon as it is mapped.
+    pub fn write_mapped<W: Write>(&self, mut writer: W) -> Result<(), MappedError> {
+        let mut records: Vec<&I> = self.records().iter().collect();
+        if !self.is_sorted() {
+            records.sort_unstable_by(|a, b| (*a).coord_cmp(*b));
+        }
+
+        let mut chroms: Vec<&C> = Vec::new();
+        let mut entries = Vec::with_capacity(records.len() * ENTRY_SIZE);
+        let mut payload = String::new();
+        for record in &records {
+            if chroms.last() != Some(&record.chr()) {
+                chroms.push(record.chr());
+            }
+            let chrom =
+                u32::try_from(chroms.len() - 1).map_err(|_| unrepresentable(chroms.len()))?;
+            entries.extend_from_slice(&chrom.to_le_bytes());
+            entries.extend_from_slice(&[encode_strand(record.strand()), 0, 0, 0]);
+            entries.extend_from_slice(&to_i64(record.start())?.to_le_bytes());
+            entries.extend_from_slice(&to_i64(record.end())?.to_le_bytes());
+            entries.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+            record
+                .write_fields(&mut payload)
+                .map_err(|_| io::Error::other("unable to format record"))?;
+        }
+
+        let mut flags = FLAG_SORTED;
+        let max_len = match self.max_len() {
+            Some(max_len) => {
+                flags |= FLAG_MAX_LEN;
+                to_i64(max_len)?
+            }
+            None => 0,
+        };
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&flags.to_le_bytes())?;
+        writer.write_all(&(records.len() as u64).to_le_bytes())?;
+        writer.write_all(&max_len.to_le_bytes())?;
+        writer.write_all(&(chroms.len() as u64).to_le_bytes())?;
+        writer.write_all(&(payload.len() as u64).to_le_bytes())?;
+        for chr in chroms {
+            let name = chr.to_string();
+            let size = u32::try_from(name.len()).map_err(|_| unrepresentable(name.len()))?;
+            writer.write_all(&size.to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+        }
+        writer.write_all(&entries)?;
+        writer.write_all(payload.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the container to a file readable by [`MappedContainer::open`]
+    pub fn save_mapped<P: AsRef<Path>>(&self, path: P) -> Result<(), MappedError> {
+        let file = File::create(path)?;
+        self.write_mapped(BufWriter::new(file))
+    }
+}
+
+/// A sorted set of intervals searched directly from its binary representation.
+///
+/// Files are written with [`IntervalContainer::write_mapped`] or
+/// [`IntervalContainer::save_mapped`] and hold the records in sorted order
+/// alongside the `is_sorted` flag and `max_len` of the container, so that
+/// loading one needs neither sorting nor parsing every record.
+/// [`MappedContainer::open`] memory-maps a file, and any other byte buffer
+/// can be used through [`MappedContainer::from_bytes`].
+///
+/// Searches (`lower_bound`, `query_iter`, and `closest`) run over a table of
+/// fixed-size coordinate entries, and only the records they return are
+/// decoded from their fields.
+///
+/// ## Format
+///
+/// All integers are little-endian.
+///
+/// | Section | Contents |
+/// |---------|----------|
+/// | Header | magic `BEDRSIVC`, version (`u32`), flags (`u32`), record count (`u64`), max length (`i64`), chromosome count (`u64`), field bytes (`u64`) |
+/// | Chromosomes | the sorted chromosome names, each as a length (`u32`) and UTF-8 bytes |
+/// | Entries | per record: chromosome index (`u32`), strand (`u8`), 3 padding bytes, start (`i64`), end (`i64`), offset of its fields (`u64`) |
+/// | Fields | the tab-delimited fields of every record following the chromosome |
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::MappedContainer, types::Query, Bed3, Coordinates, IntervalContainer};
+///
+/// let set = IntervalContainer::new(vec![
+///     Bed3::new("chr1".to_string(), 30, 40),
+///     Bed3::new("chr1".to_string(), 10, 20),
+///     Bed3::new("chr2".to_string(), 10, 20),
+/// ]);
+/// let mut bytes = Vec::new();
+/// set.write_mapped(&mut bytes).unwrap();
+///
+/// let mapped: MappedContainer<Bed3<String, u32>, _, _, _> =
+///     MappedContainer::from_bytes(bytes).unwrap();
+/// assert!(mapped.is_sorted());
+/// assert_eq!(mapped.max_len(), Some(10));
+///
+/// let query = Bed3::new("chr1".to_string(), 15, 35);
+/// let overlaps = mapped
+///     .query_iter(&query, Query::default())
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(overlaps.len(), 2);
+/// assert_eq!(overlaps[0].start(), 10);
+/// ```
+pub struct MappedContainer<I, C, T, B = Mmap>
+where
+    I: IntervalBounds<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+    B: AsRef<[u8]>,
+{
+    bytes: B,
+    chroms: Vec<C>,
+    names: Vec<String>,
+    len: usize,
+    entries: usize,
+    fields: usize,
+    fields_len: usize,
+    is_sorted: bool,
+    max_len: Option<T>,
+    phantom_i: PhantomData<I>,
+}
+
+impl<I, C, T> MappedContainer<I, C, T, Mmap>
+where
+    I: IntervalBounds<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    /// Memory-maps an interval file.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, MappedError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is only read and every access is checked against
+        // the layout validated in `from_bytes`. Concurrent modification of
+        // the file is documented as unsupported.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::from_bytes(map)
+    }
+}
+
+impl<I, C, T, B> MappedContainer<I, C, T, B>
+where
+    I: IntervalBounds<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+    B: AsRef<[u8]>,
+{
+    /// Reads an interval file from its bytes.
+    ///
+    /// The header, chromosomes, and coordinate entries are validated, but
+    /// the fields of the records are only parsed once they are requested.
+    pub fn from_bytes(bytes: B) -> Result<Self, MappedError> {
+        let data = bytes.as_ref();
+        if data.get(..MAGIC.len()) != Some(&MAGIC[..]) {
+            return Err(MappedError::InvalidMagic);
+        }
+        let header = section(data, 0, HEADER_SIZE, "truncated header")?;
+        let version = u32::from_le_bytes(array(header, 8));
+        if version != VERSION {
+            return Err(MappedError::UnsupportedVersion { version });
+        }
+        let flags = u32::from_le_bytes(array(header, 12));
+        let len = length(u64::from_le_bytes(array(header, 16)))?;
+        let max_len = i64::from_le_bytes(array(header, 24));
+        let num_chroms = length(u64::from_le_bytes(array(header, 32)))?;
+        let fields_len = length(u64::from_le_bytes(array(header, 40)))?;
+
+        let mut pos = HEADER_SIZE;
+        let mut names = Vec::with_capacity(num_chroms.min(data.len()));
+        for _ in 0..num_chroms {
+            let size =
+                u32::from_le_bytes(array(section(data, pos, 4, "truncated chromosomes")?, 0));
+            let size = length(u64::from(size))?;
+            let name = section(data, pos + 4, size, "truncated chromosomes")?;
+            let name =
+                std::str::from_utf8(name).map_err(|_| malformed("invalid chromosome name"))?;
+            names.push(name.to_string());
+            pos += 4 + size;
+        }
+        let chroms = names
+            .iter()
+            .map(|name| {
+                name.parse::<C>()
+                    .map_err(|_| malformed("invalid chromosome name"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if chroms.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(malformed("chromosomes are out of order"));
+        }
+
+        let entries = pos;
+        let fields = len
+            .checked_mul(ENTRY_SIZE)
+            .and_then(|size| size.checked_add(entries))
+            .ok_or_else(|| malformed("too many records"))?;
+        if fields.checked_add(fields_len) != Some(data.len()) {
+            return Err(malformed("unexpected file length"));
+        }
+
+        let max_len = if flags & FLAG_MAX_LEN == 0 {
+            None
+        } else {
+            Some(from_i64(max_len)?)
+        };
+        let container = Self {
+            bytes,
+            chroms,
+            names,
+            len,
+            entries,
+            fields,
+            fields_len,
+            is_sorted: flags & FLAG_SORTED != 0,
+            max_len,
+            phantom_i: PhantomData,
+        };
+        container.validate_entries()?;
+        Ok(container)
+    }
+
+    /// Checks that every entry can be converted without failing later on
+    fn validate_entries(&self) -> Result<(), MappedError> {
+        let mut offset = 0;
+        for idx in 0..self.len {
+            let raw = self.raw_entry(idx);
+            if raw.chrom >= self.chroms.len() {
+                return Err(malformed("chromosome index out of range"));
+            }
+            decode_strand(raw.strand)?;
+            from_i64::<T>(raw.start)?;
+            from_i64::<T>(raw.end)?;
+            if raw.offset < offset || raw.offset > self.fields_len {
+                return Err(malformed("field offsets out of order"));
+            }
+            offset = raw.offset;
+        }
+        Ok(())
+    }
+
+    /// The number of records in the file
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the file holds no records
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the records are stored in sorted order
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted
+    }
+
+    /// The length of the longest record, if known
+    pub fn max_len(&self) -> Option<T> {
+        self.max_len
+    }
+
+    /// The chromosomes of the records in sorted order
+    pub fn chroms(&self) -> &[C] {
+        &self.chroms
+    }
+
+    /// Decodes the record at the provided index
+    pub fn get(&self, idx: usize) -> Option<Result<I, MappedError>> {
+        (idx < self.len).then(|| self.decode(idx))
+    }
+
+    /// Decodes every record in order
+    pub fn records(&self) -> impl Iterator<Item = Result<I, MappedError>> + '_ {
+        (0..self.len).map(|idx| self.decode(idx))
+    }
+
+    /// Decodes every record into an [`IntervalContainer`] keeping the
+    /// stored sort state and maximum length
+    pub fn to_container(&self) -> Result<IntervalContainer<I, C, T>, MappedError> {
+        let records = self.records().collect::<Result<Vec<_>, _>>()?;
+        let mut set = IntervalContainer::new(records);
+        *set.sorted_mut() = self.is_sorted;
+        *set.max_len_mut() = self.max_len;
+        Ok(set)
+    }
+
+    /// Identifies the lower bound of a query in the sorted records.
+    ///
+    /// This follows [`IntervalContainer::lower_bound`] and returns the
+    /// index of the first record which could overlap the query.
+    pub fn lower_bound<Iv>(&self, query: &Iv) -> Result<usize, SetError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        if self.is_sorted() {
+            if self.is_empty() {
+                return Err(SetError::EmptySet);
+            } else if self.max_len().is_none() {
+                return Err(SetError::MissingMaxLen);
+            }
+            Ok(self.lower_bound_unchecked(query))
+        } else {
+            Err(SetError::UnsortedSet)
+        }
+    }
+
+    fn lower_bound_unchecked<Iv>(&self, query: &Iv) -> usize
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        let bias = self.max_len().unwrap_or_else(T::zero);
+        self.partition_point(|entry| entry.biased_lt(query, bias))
+    }
+
+    /// Find all records that overlap a query interval and return an
+    /// iterator decoding them.
+    ///
+    /// Will return an error if the records are not sorted.
+    pub fn query_iter<'a, Iv>(
+        &'a self,
+        query: &'a Iv,
+        method: Query<T>,
+    ) -> Result<MappedFindIter<'a, I, C, T, B, Iv>, SetError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        if self.is_sorted() {
+            method.validate()?;
+            Ok(MappedFindIter {
+                container: self,
+                query,
+                offset: self.lower_bound_unchecked(query),
+                method,
+            })
+        } else {
+            Err(SetError::UnsortedSet)
+        }
+    }
+
+    /// Returns the closest record to the query interval.
+    ///
+    /// Records on either side of the query are considered, and the strand
+    /// method restricts which records are eligible.
+    pub fn closest<Iv>(&self, query: &Iv, method: StrandMethod) -> Result<Option<I>, MappedError>
+    where
+        Iv: IntervalBounds<C, T>,
+    {
+        if !self.is_sorted() {
+            return Err(SetError::UnsortedSet.into());
+        } else if self.is_empty() {
+            return Err(SetError::EmptySet.into());
+        }
+        let eligible = |entry: &Entry<C, T>| match method {
+            StrandMethod::Ignore => true,
+            StrandMethod::MatchStrand => entry.bounded_strand(query),
+            StrandMethod::OppositeStrand => !entry.bounded_strand(query),
+        };
+
+        // the last eligible record before the query on its chromosome, or
+        // failing that the first eligible record after it
+        let low = self.partition_point(|entry| entry.lt(query));
+        let bound = match self.first_eligible((0..low).rev(), query, eligible) {
+            Some(bound) => bound,
+            None => match self.first_eligible(low..self.len, query, eligible) {
+                Some(bound) => bound,
+                None => return Ok(None),
+            },
+        };
+
+        let mut current_dist = T::max_value();
+        let mut closest = bound;
+        for idx in bound..self.len {
+            let entry = self.entry(idx);
+            if !eligible(&entry) {
+                continue;
+            }
+            match query.distance(&entry) {
+                Some(distance) if distance < current_dist => {
+                    current_dist = distance;
+                    closest = idx;
+                }
+                _ => break,
+            }
+        }
+
+        // a longer record starting further upstream may still end closer to
+        // the query, and none can once its start is beyond the reach of the
+        // longest record
+        let max_len = self.max_len();
+        for idx in (0..bound).rev() {
+            let entry = self.entry(idx);
+            if !entry.bounded_chr(query) {
+                break;
+            }
+            if let Some(max_len) = max_len {
+                let reach = max_len + current_dist;
+                if query.start() >= reach && entry.start() < query.start() - reach {
+                    break;
+                }
+            }
+            if !eligible(&entry) {
+                continue;
+            }
+            if let Some(distance) = query.distance(&entry).filter(|&d| d < current_dist) {
+                current_dist = distance;
+                closest = idx;
+            }
+        }
+        self.decode(closest).map(Some)
+    }
+
+    /// Returns the first eligible index sharing the chromosome of the query,
+    /// stopping at the first record on another chromosome
+    fn first_eligible<Iv, F>(
+        &self,
+        indices: impl Iterator<Item = usize>,
+        query: &Iv,
+        eligible: F,
+    ) -> Option<usize>
+    where
+        Iv: IntervalBounds<C, T>,
+        F: Fn(&Entry<C, T>) -> bool,
+    {
+        for idx in indices {
+            let entry = self.entry(idx);
+            if !entry.bounded_chr(query) {
+                return None;
+            } else if eligible(&entry) {
+                return Some(idx);
+            }
+        }
+        None
+    }
+
+    /// Returns the first index for which the predicate fails
+    fn partition_point<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&Entry<C, T>) -> bool,
+    {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(&self.entry(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    fn raw_entry(&self, idx: usize) -> RawEntry {
+        let start = self.entries + idx * ENTRY_SIZE;
+        RawEntry::parse(&self.bytes.as_ref()[start..start + ENTRY_SIZE])
+    }
+
+    /// The coordinates of the record at the provided index
+    fn entry(&self, idx: usize) -> Entry<C, T> {
+        let raw = self.raw_entry(idx);
+        // entries were validated when the file was opened
+        Entry {
+            chr: self.chroms[raw.chrom].clone(),
+            start: T::from_i64(raw.start).unwrap_or_else(T::zero),
+            end: T::from_i64(raw.end).unwrap_or_else(T::zero),
+            strand: decode_strand(raw.strand).unwrap_or_default(),
+        }
+    }
+
+    /// Parses the record at the provided index from its fields
+    fn decode(&self, idx: usize) -> Result<I, MappedError> {
+        let raw = self.raw_entry(idx);
+        let end = if idx + 1 < self.len {
+            self.raw_entry(idx + 1).offset
+        } else {
+            self.fields_len
+        };
+        let data = &self.bytes.as_ref()[self.fields + raw.offset..self.fields + end];
+        let text = std::str::from_utf8(data).map_err(|_| malformed("invalid record fields"))?;
+        let fields: Vec<&str> = std::iter::once(self.names[raw.chrom].as_str())
+            .chain(text.split('\t').skip(1))
+            .collect();
+        Ok(I::from_fields(self.chroms[raw.chrom].clone(), &fields)?)
+    }
+}
+
+/// An iterator over the records of a [`MappedContainer`] overlapping a query
+pub struct MappedFindIter<'a, I, C, T, B, Iv>
+where
+    I: IntervalBounds<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+    B: AsRef<[u8]>,
+    Iv: IntervalBounds<C, T>,
+{
+    container: &'a MappedContainer<I, C, T, B>,
+    query: &'a Iv,
+    offset: usize,
+    method: Query<T>,
+}
+
+impl<I, C, T, B, Iv> Iterator for MappedFindIter<'_, I, C, T, B, Iv>
+where
+    I: IntervalBounds<C, T> + FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+    B: AsRef<[u8]>,
+    Iv: IntervalBounds<C, T>,
+{
+    type Item = Result<I, MappedError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.container.len() {
+            let idx = self.offset;
+            self.offset += 1;
+            let entry = self.container.entry(idx);
+            if self.method.predicate(&entry, self.query) {
+                return Some(self.container.decode(idx));
+            } else if entry.chr() > self.query.chr() || entry.start() >= self.query.end() {
+                break;
+            }
+        }
+        None
+    }
+}
+
+/// The raw values of an entry in the record table
+struct RawEntry {
+    chrom: usize,
+    strand: u8,
+    start: i64,
+    end: i64,
+    offset: usize,
+}
+
+impl RawEntry {
+    fn parse(bytes: &[u8]) -> Self {
+        Self {
+            chrom: u32::from_le_bytes(array(bytes, 0)) as usize,
+            strand: bytes[4],
+            start: i64::from_le_bytes(array(bytes, 8)),
+            end: i64::from_le_bytes(array(bytes, 16)),
+            offset: usize::try_from(u64::from_le_bytes(array(bytes, 24))).unwrap_or(usize::MAX),
+        }
+    }
+}
+
+/// The coordinates of a stored record used while searching
+#[derive(Debug, Clone, Default)]
+struct Entry<C, T> {
+    chr: C,
+    start: T,
+    end: T,
+    strand: Option<Strand>,
+}
+
+impl<C, T> Coordinates<C, T> for Entry<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    fn empty() -> Self {
+        Self::default()
+    }
+    fn start(&self) -> T {
+        self.start
+    }
+    fn end(&self) -> T {
+        self.end
+    }
+    fn chr(&self) -> &C {
+        &self.chr
+    }
+    fn strand(&self) -> Option<Strand> {
+        self.strand
+    }
+    fn update_start(&mut self, val: &T) {
+        self.start = *val;
+    }
+    fn update_end(&mut self, val: &T) {
+        self.end = *val;
+    }
+    fn update_chr(&mut self, val: &C) {
+        self.chr = val.clone();
+    }
+    fn update_strand(&mut self, strand: Option<Strand>) {
+        self.strand = strand;
+    }
+    fn from<Iv: Coordinates<C, T>>(other: &Iv) -> Self {
+        Self {
+            chr: other.chr().clone(),
+            start: other.start(),
+            end: other.end(),
+            strand: other.strand(),
+        }
+    }
+}
+
+fn encode_strand(strand: Option<Strand>) -> u8 {
+    match strand {
+        None => 0,
+        Some(Strand::Forward) => 1,
+        Some(Strand::Reverse) => 2,
+        Some(Strand::Unknown) => 3,
+    }
+}
+
+fn decode_strand(byte: u8) -> Result<Option<Strand>, MappedError> {
+    match byte {
+        0 => Ok(None),
+        1 => Ok(Some(Strand::Forward)),
+        2 => Ok(Some(Strand::Reverse)),
+        3 => Ok(Some(Strand::Unknown)),
+        _ => Err(malformed("invalid strand")),
+    }
+}
+
+fn malformed(reason: &'static str) -> MappedError {
+    MappedError::Malformed { reason }
+}
+
+fn unrepresentable<V: std::fmt::Debug>(value: V) -> MappedError {
+    MappedError::UnrepresentableValue {
+        value: format!("{value:?}"),
+    }
+}
+
+fn to_i64<T: ValueBounds>(value: T) -> Result<i64, MappedError> {
+    value.to_i64().ok_or_else(|| unrepresentable(value))
+}
+
+fn from_i64<T: ValueBounds>(value: i64) -> Result<T, MappedError> {
+    T::from_i64(value).ok_or_else(|| unrepresentable(value))
+}
+
+fn length(value: u64) -> Result<usize, MappedError> {
+    usize::try_from(value).map_err(|_| malformed("length exceeds the address space"))
+}
+
+/// Returns a bounds checked section of the file
+fn section<'a>(
+    data: &'a [u8],
+    start: usize,
+    len: usize,
+    reason: &'static str,
+) -> Result<&'a [u8], MappedError> {
+    start
+        .checked_add(len)
+        .and_then(|end| data.get(start..end))
+        .ok_or_else(|| malformed(reason))
+}
+
+/// Copies a fixed number of bytes from an already bounds checked slice
+fn array<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    let mut buf = [0u8; N];
+    buf.copy_from_slice(&bytes[offset..offset + N]);
+    buf
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        types::{Query, QueryMethod},
+        Bed3, Bed6, Sam, Score, StrandedBed3,
+    };
+
+    type Peak = Bed6<String, u32, String>;
+
+    fn peak(chr: &str, start: u32, end: u32, strand: Strand) -> Peak {
+        Bed6::new(
+            chr.to_string(),
+            start,
+            end,
+            format!("{chr}:{start}"),
+            Score(Some(1.0)),
+            strand,
+        )
+    }
+
+    fn example() -> IntervalContainer<Peak, String, u32> {
+        IntervalContainer::new(vec![
+            peak("chr2", 10, 20, Strand::Forward),
+            peak("chr1", 50, 60, Strand::Reverse),
+            peak("chr1", 10, 20, Strand::Forward),
+            peak("chr1", 30, 35, Strand::Reverse),
+            peak("chr1", 70, 100, Strand::Forward),
+            peak("chr3", 500, 506, Strand::Unknown),
+        ])
+    }
+
+    fn mapped(
+        set: &IntervalContainer<Peak, String, u32>,
+    ) -> MappedContainer<Peak, String, u32, Vec<u8>> {
+        let mut bytes = Vec::new();
+        set.write_mapped(&mut bytes).unwrap();
+        MappedContainer::from_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let set = example();
+        let mapped = mapped(&set);
+        assert_eq!(mapped.len(), 6);
+        assert!(mapped.is_sorted());
+        assert_eq!(mapped.max_len(), Some(30));
+        assert_eq!(mapped.chroms(), ["chr1", "chr2", "chr3"]);
+
+        let mut expected = set.clone();
+        expected.sort();
+        let observed = mapped.to_container().unwrap();
+        assert!(observed.is_sorted());
+        assert_eq!(observed.max_len(), Some(30));
+        for (obs, exp) in observed.records().iter().zip(expected.records()) {
+            assert_eq!(obs.chr(), exp.chr());
+            assert_eq!(obs.start(), exp.start());
+            assert_eq!(obs.end(), exp.end());
+            assert_eq!(obs.name(), exp.name());
+            assert_eq!(obs.strand(), exp.strand());
+        }
+        assert!(mapped.get(6).is_none());
+        assert_eq!(mapped.get(4).unwrap().unwrap().name(), "chr2:10");
+    }
+
+    #[test]
+    fn round_trip_alignments() {
+        let set: IntervalContainer<Sam<String, u32, String>, String, u32> =
+            IntervalContainer::from_unsorted(vec![
+                "read2\t1040\tchr1\t51\t60\t10M50N10M".parse().unwrap(),
+                "read1\t0\tchr1\t11\t30\t5S20M".parse().unwrap(),
+            ]);
+        let mut bytes = Vec::new();
+        set.write_mapped(&mut bytes).unwrap();
+        let mapped =
+            MappedContainer::<Sam<String, u32, String>, String, u32, _>::from_bytes(bytes).unwrap();
+        let observed = mapped.to_container().unwrap();
+        for (obs, exp) in observed.records().iter().zip(set.records()) {
+            assert_eq!(obs.chr(), exp.chr());
+            assert_eq!((obs.start(), obs.end()), (exp.start(), exp.end()));
+            assert_eq!(obs.name(), exp.name());
+            assert_eq!(obs.flag(), exp.flag());
+            assert_eq!(obs.mapq(), exp.mapq());
+            assert_eq!(obs.strand(), exp.strand());
+        }
+        assert_eq!(observed.len(), 2);
+    }
+
+    #[test]
+    fn lower_bound_matches_container() {
+        let mut set = example();
+        set.sort();
+        let mapped = mapped(&set);
+        for (chr, start, end) in [
+            ("chr1", 0, 5),
+            ("chr1", 55, 58),
+            ("chr1", 200, 210),
+            ("chr2", 15, 16),
+            ("chr0", 1, 2),
+            ("chr9", 1, 2),
+        ] {
+            let query = Bed3::new(chr.to_string(), start, end);
+            assert_eq!(mapped.lower_bound(&query), set.lower_bound(&query));
+        }
+    }
+
+    #[test]
+    fn query_matches_container() {
+        let mut set = example();
+        set.sort();
+        let mapped = mapped(&set);
+        let methods = [
+            Query::default(),
+            Query::new(QueryMethod::Compare, StrandMethod::MatchStrand),
+            Query::new(
+                QueryMethod::CompareByQueryFraction(0.5),
+                StrandMethod::Ignore,
+            ),
+        ];
+        for method in methods {
+            for (start, end) in [(0, 100), (15, 55), (33, 34), (60, 70)] {
+                let query = StrandedBed3::new("chr1".to_string(), start, end, Strand::Reverse);
+                let expected = set
+                    .query_iter(&query, method)
+                    .unwrap()
+                    .map(Coordinates::start)
+                    .collect::<Vec<_>>();
+                let observed = mapped
+                    .query_iter(&query, method)
+                    .unwrap()
+                    .map(|record| record.unwrap().start())
+                    .collect::<Vec<_>>();
+                assert_eq!(observed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn closest_record() {
+        let mapped = mapped(&example());
+        let query = Bed3::new("chr1".to_string(), 40, 45);
+        let closest = mapped
+            .closest(&query, StrandMethod::Ignore)
+            .unwrap()
+            .unwrap();
+        assert_eq!(closest.start(), 30);
+
+        let query = StrandedBed3::new("chr1".to_string(), 40, 45, Strand::Forward);
+        let closest = mapped
+            .closest(&query, StrandMethod::MatchStrand)
+            .unwrap()
+            .unwrap();
+        assert_eq!(closest.start(), 10);
+        let closest = mapped
+            .closest(&query, StrandMethod::OppositeStrand)
+            .unwrap()
+            .unwrap();
+        assert_eq!(closest.start(), 30);
+
+        let query = Bed3::new("chr1".to_string(), 0, 2);
+        let closest = mapped
+            .closest(&query, StrandMethod::Ignore)
+            .unwrap()
+            .unwrap();
+        assert_eq!(closest.start(), 10);
+
+        let query = Bed3::new("chr4".to_string(), 0, 2);
+        assert!(mapped
+            .closest(&query, StrandMethod::Ignore)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn closest_long_upstream_record() {
+        let set = IntervalContainer::new(vec![
+            peak("chr1", 0, 48, Strand::Forward),
+            peak("chr1", 5, 10, Strand::Forward),
+            peak("chr1", 30, 35, Strand::Reverse),
+            peak("chr1", 80, 90, Strand::Reverse),
+        ]);
+        let mapped = mapped(&set);
+        let query = StrandedBed3::new("chr1".to_string(), 50, 55, Strand::Reverse);
+        let closest = |method| mapped.closest(&query, method).unwrap().unwrap();
+        assert_eq!(closest(StrandMethod::Ignore).start(), 0);
+        assert_eq!(closest(StrandMethod::MatchStrand).start(), 30);
+        assert_eq!(closest(StrandMethod::OppositeStrand).start(), 0);
+    }
+
+    #[test]
+    fn empty_container() {
+        let set: IntervalContainer<Peak, String, u32> = IntervalContainer::empty();
+        let mapped = mapped(&set);
+        assert!(mapped.is_empty());
+        let query = Bed3::new("chr1".to_string(), 0, 2);
+        assert_eq!(mapped.lower_bound(&query), Err(SetError::EmptySet));
+        assert_eq!(
+            mapped.query_iter(&query, Query::default()).unwrap().count(),
+            0
+        );
+        assert!(matches!(
+            mapped.closest(&query, StrandMethod::Ignore),
+            Err(MappedError::Set(SetError::EmptySet))
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_files() {
+        let mut bytes = Vec::new();
+        example().write_mapped(&mut bytes).unwrap();
+
+        let result =
+            MappedContainer::<Peak, String, u32, _>::from_bytes(b"chr1\t10\t20\n".to_vec());
+        assert!(matches!(result, Err(MappedError::InvalidMagic)));
+
+        let mut version = bytes.clone();
+        version[8] = 9;
+        let result = MappedContainer::<Peak, String, u32, _>::from_bytes(version);
+        assert!(matches!(
+            result,
+            Err(MappedError::UnsupportedVersion { version: 9 })
+        ));
+
+        let truncated = bytes[..bytes.len() - 1].to_vec();
+        let result = MappedContainer::<Peak, String, u32, _>::from_bytes(truncated);
+        assert!(matches!(result, Err(MappedError::Malformed { .. })));
+
+        let result = MappedContainer::<Bed6<String, u8, String>, String, u8, _>::from_bytes(bytes);
+        assert!(matches!(
+            result,
+            Err(MappedError::UnrepresentableValue { .. })
+        ));
+    }
+
+    #[test]
+    fn save_and_open() {
+        let path = std::env::temp_dir().join(format!("bedrs-mapped-{}.bin", std::process::id()));
+        example().save_mapped(&path).unwrap();
+        let mapped: MappedContainer<Peak, String, u32> = MappedContainer::open(&path).unwrap();
+        let query = Bed3::new("chr1".to_string(), 55, 75);
+        let observed = mapped
+            .query_iter(&query, Query::default())
+            .unwrap()
+            .map(|record| record.unwrap().name().clone())
+            .collect::<Vec<_>>();
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(observed, vec!["chr1:50", "chr1:70"]);
+    }
+}
@@ -0,0 +1,34 @@
+mod bbi;
+mod bgzf;
+mod bigbed;
+mod bigwig;
+#[cfg(feature = "arrow")]
+mod columnar;
+mod decoder;
+mod gff3;
+mod indexed;
+mod lines;
+mod mapped;
+mod psl;
+mod reader;
+mod sam;
+mod tabix;
+mod wig;
+mod writer;
+pub use bbi::{BbiIter, BbiSummary, ZoomRecord};
+pub use bgzf::{split_virtual_offset, virtual_offset, BgzfReader, BgzfWriter};
+pub use bigbed::BigBedReader;
+pub use bigwig::BigWigReader;
+#[cfg(feature = "arrow")]
+pub use columnar::{ArrowRecord, ColumnKind};
+pub use decoder::{AutoDecoder, CompressionFormat};
+pub use gff3::{FeatureGraph, Gff3Reader};
+pub use indexed::{IndexedQueryIter, IndexedReader};
+pub(crate) use lines::LineReader;
+pub use mapped::{MappedContainer, MappedFindIter};
+pub use psl::PslReader;
+pub use reader::{BedReader, GtfReader, InternedBedReader};
+pub use sam::SamReader;
+pub use tabix::{Chunk, IndexConfig, TabixIndex};
+pub use wig::WigReader;
+pub use writer::BedWriter;
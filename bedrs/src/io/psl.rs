@@ -0,0 +1,205 @@
+use super::{
+    decoder::AutoDecoder,
+    lines::{parse_record, LineReader},
+};
+use crate::{
+    traits::{fields::parse_field, ChromBounds, MetaBounds, ParseError, ValueBounds},
+    types::ErrorPolicy,
+    Psl,
+};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    marker::PhantomData,
+    path::Path,
+    str::FromStr,
+};
+
+/// A streaming reader of [`Psl`] alignment records.
+///
+/// Both headerless PSL and files written with the `psLayout` header are
+/// accepted: every line that does not start with a digit (the header,
+/// column titles, separator, comments, and empty lines) is skipped.
+///
+/// Malformed lines are handled according to the [`ErrorPolicy`] of the
+/// reader.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::PslReader, Bed6, Coordinates};
+///
+/// let text = "psLayout version 3\n\n\
+///     match\tmis-\trep.\tN's\tQ gap\tQ gap\tT gap\tT gap\tstrand\tQ\tQ\tQ\tQ\tT\tT\tT\tT\tblock\tblockSizes\tqStarts\ttStarts\n\
+///     --------------------------------------------------------------------------\n\
+///     100\t0\t0\t0\t0\t0\t1\t50\t+\tread1\t100\t0\t100\tchr1\t1000\t200\t350\t2\t60,40,\t0,60,\t200,310,\n";
+///
+/// let reader: PslReader<_, String, u64, String> = PslReader::new(text.as_bytes());
+/// let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].end(), 350);
+///
+/// let blocks: Vec<Bed6<String, u64, String>> = records[0].blocks().collect();
+/// assert_eq!(blocks[1].start(), 310);
+/// ```
+pub struct PslReader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    lines: LineReader<R>,
+    phantom_c: PhantomData<C>,
+    phantom_t: PhantomData<T>,
+    phantom_n: PhantomData<N>,
+}
+
+impl<R, C, T, N> PslReader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    pub fn new(reader: R) -> Self {
+        Self::from_lines(LineReader::new(reader))
+    }
+
+    fn from_lines(lines: LineReader<R>) -> Self {
+        Self {
+            lines,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+            phantom_n: PhantomData,
+        }
+    }
+
+    /// Sets how malformed lines are handled
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.lines.set_policy(policy);
+        self
+    }
+
+    /// Returns the number of malformed lines skipped so far
+    pub fn skipped(&self) -> usize {
+        self.lines.skipped()
+    }
+
+    /// Returns the number of malformed lines repaired so far
+    pub fn repaired(&self) -> usize {
+        self.lines.repaired()
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        self.lines.get_ref()
+    }
+
+    /// Consumes the reader returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.lines.into_inner()
+    }
+
+    /// Parses the current alignment line
+    fn parse_line(&mut self) -> Option<Result<Psl<C, T, N>, ParseError>> {
+        let repair = self.lines.policy() == ErrorPolicy::Repair;
+        let mut fields: Vec<&str> = self.lines.line().split('\t').collect();
+        let result = parse_field(&fields, 13, "t_name")
+            .and_then(|chr| parse_record(chr, &mut fields, repair));
+        self.lines.resolve(result)
+    }
+}
+
+impl<C, T, N> PslReader<AutoDecoder<BufReader<File>>, C, T, N>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    /// Opens a file for reading alignments, decompressing gzip and BGZF
+    /// files on the fly
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let reader = AutoDecoder::from_path(&path)?;
+        let lines = LineReader::new(reader).with_path(path.as_ref().to_path_buf());
+        Ok(Self::from_lines(lines))
+    }
+}
+
+impl<R, C, T, N> Iterator for PslReader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    type Item = Result<Psl<C, T, N>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self
+                .lines
+                .next_line(|line| !line.starts_with(|c: char| c.is_ascii_digit()))
+            {
+                Ok(true) => {
+                    if let Some(result) = self.parse_line() {
+                        return Some(result);
+                    }
+                }
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Bed12, Coordinates, Strand};
+
+    const TEXT: &str = "# alignments\n\
+        100\t0\t0\t0\t0\t0\t1\t50\t-\tread1\t100\t0\t100\tchr1\t1000\t200\t350\t2\t60,40,\t0,60,\t200,310,\n\
+        \n\
+        50\t0\t0\t0\t0\t0\t0\t0\t+\tread2\t50\t0\t50\tchr2\t1000\t10\t60\t1\t50,\t0,\t10,\n";
+
+    type Reader<'a> = PslReader<&'a [u8], String, u64, String>;
+    type Gene = Bed12<String, u64, String, u64, u64, String, Vec<u64>, Vec<u64>>;
+
+    #[test]
+    fn test_reader() {
+        let reader: Reader = PslReader::new(TEXT.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].chr(), "chr1");
+        assert_eq!(records[0].strand(), Some(Strand::Reverse));
+        assert_eq!(records[1].chr(), "chr2");
+        assert_eq!((records[1].start(), records[1].end()), (10, 60));
+    }
+
+    #[test]
+    fn test_reader_bed12() {
+        let reader: Reader = PslReader::new(TEXT.as_bytes());
+        let beds: Vec<Gene> = reader.map(|r| r.unwrap().into()).collect();
+        assert_eq!(beds[0].block_starts(), &vec![0, 110]);
+        assert_eq!(beds[1].block_sizes(), &vec![50]);
+    }
+
+    #[test]
+    fn test_reader_invalid() {
+        let text = "100\t0\t0\n";
+        let mut reader: Reader = PslReader::new(text.as_bytes());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(1));
+        assert!(matches!(
+            err.inner(),
+            ParseError::MissingField { field: "t_name" }
+        ));
+        assert!(reader.next().is_none());
+
+        let text = format!("100\t0\t0\n{TEXT}");
+        let mut reader: Reader = PslReader::new(text.as_bytes()).error_policy(ErrorPolicy::Skip);
+        assert_eq!(reader.by_ref().count(), 2);
+        assert_eq!(reader.skipped(), 1);
+    }
+}
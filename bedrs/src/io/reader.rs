@@ -0,0 +1,570 @@
+use super::{
+    decoder::AutoDecoder,
+    lines::{parse_record, LineReader},
+};
+use crate::{
+    traits::{fields::parse_value, ChromBounds, FromFields, ParseError, ValueBounds},
+    types::ErrorPolicy,
+    ChromDict, Gtf,
+};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    marker::PhantomData,
+    path::Path,
+    str::FromStr,
+};
+
+/// A streaming reader of tab-delimited interval records.
+///
+/// Wraps any [`BufRead`] and lazily parses each line into a record
+/// implementing [`FromFields`].
+/// Empty lines, comment lines (`#`), and `track` or `browser` header
+/// lines are skipped.
+///
+/// Records are yielded as `Result`s so that malformed lines can be
+/// handled by the caller.
+/// Errors carry the number of the offending line (see [`ParseError::line`]),
+/// and an [`ErrorPolicy`] can be set to skip or repair malformed lines
+/// instead.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::BedReader, Bed6, Coordinates, MergeIter, Strand};
+///
+/// let text = "track name=example\n\
+///     chr1\t10\t20\ta\t0\t+\n\
+///     chr1\t15\t30\tb\t.\t-\n\
+///     chr2\t10\t20\tc\t5\t.\n";
+///
+/// let reader = BedReader::new(text.as_bytes());
+/// let records: Vec<Bed6<String, u64, String>> = reader.collect::<Result<_, _>>().unwrap();
+/// assert_eq!(records.len(), 3);
+/// assert_eq!(records[1].strand(), Some(Strand::Reverse));
+///
+/// let merged: Vec<_> = MergeIter::new(records.into_iter()).collect();
+/// assert_eq!(merged.len(), 2);
+/// ```
+pub struct BedReader<R, I, C, T>
+where
+    R: BufRead,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    lines: LineReader<R>,
+    phantom_i: PhantomData<I>,
+    phantom_c: PhantomData<C>,
+    phantom_t: PhantomData<T>,
+}
+
+/// A streaming reader of [`Gtf`] records.
+///
+/// GTF coordinates are converted from the 1-based inclusive convention
+/// to the half-open convention used throughout the library.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::GtfReader, Coordinates};
+///
+/// let text = "#!genome-build GRCh38\n\
+///     chr1\tHAVANA\tgene\t11869\t14409\t.\t+\t.\tgene_id \"ENSG00000223972\"; gene_name \"DDX11L1\";\n";
+///
+/// let reader: GtfReader<_, String, u64, String> = GtfReader::new(text.as_bytes());
+/// let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(records[0].start(), 11868);
+/// assert_eq!(records[0].end(), 14409);
+///
+/// let attr = records[0].parse_attributes().unwrap();
+/// assert_eq!(attr.get("gene_name"), Some("DDX11L1"));
+/// ```
+pub type GtfReader<R, C, T, N> = BedReader<R, Gtf<C, T, N>, C, T>;
+
+impl<R, I, C, T> BedReader<R, I, C, T>
+where
+    R: BufRead,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    pub fn new(reader: R) -> Self {
+        Self::from_lines(LineReader::new(reader))
+    }
+
+    fn from_lines(lines: LineReader<R>) -> Self {
+        Self {
+            lines,
+            phantom_i: PhantomData,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+        }
+    }
+
+    /// Sets how malformed lines are handled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{io::BedReader, Bed6, Coordinates, ErrorPolicy, Strand};
+    ///
+    /// let text = "chr1\t10\t20\ta\t0\t?\nchr1\t40\t30\tb\t0\t+\nchr1\tx\t50\tc\t0\t+\n";
+    ///
+    /// let mut reader: BedReader<_, Bed6<String, u64, String>, _, _> =
+    ///     BedReader::new(text.as_bytes()).error_policy(ErrorPolicy::Repair);
+    /// let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(records.len(), 2);
+    /// assert_eq!(records[0].strand(), Some(Strand::Unknown));
+    /// assert_eq!((records[1].start(), records[1].end()), (30, 40));
+    /// assert_eq!(reader.repaired(), 2);
+    /// assert_eq!(reader.skipped(), 1);
+    /// ```
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.lines.set_policy(policy);
+        self
+    }
+
+    /// Returns the number of malformed lines skipped so far
+    pub fn skipped(&self) -> usize {
+        self.lines.skipped()
+    }
+
+    /// Returns the number of malformed lines repaired so far
+    pub fn repaired(&self) -> usize {
+        self.lines.repaired()
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        self.lines.get_ref()
+    }
+
+    /// Consumes the reader returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.lines.into_inner()
+    }
+
+    /// Reads the next non-skipped line.
+    ///
+    /// Returns `false` once the reader is exhausted.
+    fn next_line(&mut self) -> Result<bool, ParseError> {
+        self.lines.next_line(is_skipped)
+    }
+}
+
+impl<R, I, C, T> BedReader<R, I, C, T>
+where
+    R: BufRead,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+{
+    /// Parses the current line with a chromosome provided by `chr`
+    fn parse_line<F>(&mut self, chr: F) -> Option<Result<I, ParseError>>
+    where
+        F: FnOnce(&str) -> Result<C, ParseError>,
+    {
+        let repair = self.lines.policy() == ErrorPolicy::Repair;
+        let mut fields: Vec<&str> = self.lines.line().split('\t').collect();
+        let result = chr(fields[0]).and_then(|chr| parse_record(chr, &mut fields, repair));
+        self.lines.resolve(result)
+    }
+}
+
+impl<I, C, T> BedReader<AutoDecoder<BufReader<File>>, I, C, T>
+where
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds,
+{
+    /// Opens a file for reading records, decompressing gzip and BGZF
+    /// files on the fly
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let reader = AutoDecoder::from_path(&path)?;
+        let lines = LineReader::new(reader).with_path(path.as_ref().to_path_buf());
+        Ok(Self::from_lines(lines))
+    }
+}
+
+impl<R, I, C, T> Iterator for BedReader<R, I, C, T>
+where
+    R: BufRead,
+    I: FromFields<C, T>,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+{
+    type Item = Result<I, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_line() {
+                Ok(true) => {
+                    if let Some(result) = self.parse_line(|chr| parse_value(chr, "chr")) {
+                        return Some(result);
+                    }
+                }
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// A streaming reader of tab-delimited interval records whose chromosome
+/// names are interned as compact `u32` ids.
+///
+/// Names are translated through a [`ChromDict`]: names already present
+/// in the dictionary keep their id, and unknown names are assigned the
+/// next id as they are first seen.
+/// The dictionary can be retrieved to restore the names on output (see
+/// [`BedWriter::write_record_with_dict`](crate::io::BedWriter::write_record_with_dict)).
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::InternedBedReader, Bed3, ChromDict, Coordinates};
+///
+/// let text = "chr2\t10\t20\nchr1\t10\t20\nchr2\t30\t40\n";
+/// let dict = ChromDict::from_names(["chr1", "chr2"]);
+///
+/// let mut reader: InternedBedReader<_, Bed3<u32, u64>, _> =
+///     InternedBedReader::with_chrom_dict(text.as_bytes(), dict);
+/// let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(*records[0].chr(), 1);
+/// assert_eq!(*records[1].chr(), 0);
+///
+/// let dict = reader.into_chrom_dict();
+/// assert_eq!(dict.name(*records[2].chr()), Some("chr2"));
+/// ```
+pub struct InternedBedReader<R, I, T>
+where
+    R: BufRead,
+    I: FromFields<u32, T>,
+    T: ValueBounds,
+{
+    inner: BedReader<R, I, u32, T>,
+    dict: ChromDict,
+}
+
+impl<R, I, T> InternedBedReader<R, I, T>
+where
+    R: BufRead,
+    I: FromFields<u32, T>,
+    T: ValueBounds,
+{
+    /// Creates a reader assigning ids in the order names are first seen
+    pub fn new(reader: R) -> Self {
+        Self::with_chrom_dict(reader, ChromDict::new())
+    }
+
+    /// Creates a reader using the ids of an existing dictionary
+    pub fn with_chrom_dict(reader: R, dict: ChromDict) -> Self {
+        Self {
+            inner: BedReader::new(reader),
+            dict,
+        }
+    }
+
+    /// Returns the dictionary of the names seen so far
+    pub fn chrom_dict(&self) -> &ChromDict {
+        &self.dict
+    }
+
+    /// Consumes the reader returning the dictionary of the names seen
+    pub fn into_chrom_dict(self) -> ChromDict {
+        self.dict
+    }
+
+    /// Sets how malformed lines are handled
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.inner = self.inner.error_policy(policy);
+        self
+    }
+
+    /// Returns the number of malformed lines skipped so far
+    pub fn skipped(&self) -> usize {
+        self.inner.skipped()
+    }
+
+    /// Returns the number of malformed lines repaired so far
+    pub fn repaired(&self) -> usize {
+        self.inner.repaired()
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Consumes the reader returning the underlying reader and the
+    /// dictionary
+    pub fn into_inner(self) -> (R, ChromDict) {
+        (self.inner.into_inner(), self.dict)
+    }
+}
+
+impl<I, T> InternedBedReader<AutoDecoder<BufReader<File>>, I, T>
+where
+    I: FromFields<u32, T>,
+    T: ValueBounds,
+{
+    /// Opens a file for reading records, decompressing gzip and BGZF
+    /// files on the fly
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        Ok(Self {
+            inner: BedReader::from_path(path)?,
+            dict: ChromDict::new(),
+        })
+    }
+}
+
+impl<R, I, T> Iterator for InternedBedReader<R, I, T>
+where
+    R: BufRead,
+    I: FromFields<u32, T>,
+    T: ValueBounds + FromStr,
+{
+    type Item = Result<I, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next_line() {
+                Ok(true) => {
+                    let dict = &mut self.dict;
+                    if let Some(result) = self.inner.parse_line(|chr| Ok(dict.get_or_insert(chr))) {
+                        return Some(result);
+                    }
+                }
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Returns true if the line holds no record
+pub(super) fn is_skipped(line: &str) -> bool {
+    line.trim().is_empty()
+        || line.starts_with('#')
+        || is_header(line, "track")
+        || is_header(line, "browser")
+}
+
+/// Returns true if the line starts with the provided header keyword
+fn is_header(line: &str, keyword: &str) -> bool {
+    line.strip_prefix(keyword)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Bed12, Bed3, Bed4, Bed6, BedGraph, Coordinates, IntervalContainer, Score, Strand};
+
+    const BED6: &str = "browser position chr1:1-100\n\
+        track name=test\n\
+        # a comment\n\
+        chr1\t10\t20\ta\t0\t+\n\
+        \n\
+        chr1\t30\t40\tb\t.\t-\n\
+        chr2\t10\t20\tc\t1.5\t.\n";
+
+    #[test]
+    fn read_bed3() {
+        let reader: BedReader<_, Bed3<String, u64>, _, _> = BedReader::new(BED6.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records[0].eq(&Bed3::new("chr1".to_string(), 10, 20)));
+        assert!(records[1].eq(&Bed3::new("chr1".to_string(), 30, 40)));
+        assert!(records[2].eq(&Bed3::new("chr2".to_string(), 10, 20)));
+    }
+
+    #[test]
+    fn read_bed3_numeric_chr() {
+        let text = "1\t10\t20\n2\t30\t40\n";
+        let reader: BedReader<_, Bed3<u32, u32>, _, _> = BedReader::new(text.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(records[0].eq(&Bed3::new(1, 10, 20)));
+        assert!(records[1].eq(&Bed3::new(2, 30, 40)));
+    }
+
+    #[test]
+    fn read_bed4() {
+        let reader: BedReader<_, Bed4<String, u64, String>, _, _> = BedReader::new(BED6.as_bytes());
+        let names: Vec<_> = reader.map(|r| r.unwrap().name().clone()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn read_bed6() {
+        let reader: BedReader<_, Bed6<String, u64, String>, _, _> = BedReader::new(BED6.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records[0].score(), Score::new(0.0));
+        assert_eq!(records[0].strand(), Some(Strand::Forward));
+        assert_eq!(records[1].score(), Score(None));
+        assert_eq!(records[1].strand(), Some(Strand::Reverse));
+        assert_eq!(records[2].score(), Score::new(1.5));
+        assert_eq!(records[2].strand(), Some(Strand::Unknown));
+    }
+
+    #[test]
+    fn read_bed12() {
+        type Record = Bed12<String, u64, String, u64, u64, String, Vec<u64>, Vec<u64>>;
+        let text = "chr1\t10\t100\ttx\t0\t+\t20\t90\t0,0,0\t2\t30,40,\t0,50,\r\n";
+        let reader: BedReader<_, Record, _, _> = BedReader::new(text.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].block_count(), 2);
+        assert_eq!(records[0].block_sizes(), &vec![30, 40]);
+        assert_eq!(records[0].block_starts(), &vec![0, 50]);
+    }
+
+    #[test]
+    fn read_bedgraph() {
+        let text = "track type=bedGraph\nchr1\t0\t10\t1.5\nchr1\t10\t20\t0\n";
+        let reader: BedReader<_, BedGraph<String, u64>, _, _> = BedReader::new(text.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert!((records[0].score() - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn read_into_container() {
+        let reader: BedReader<_, Bed3<String, u64>, _, _> = BedReader::new(BED6.as_bytes());
+        let set: IntervalContainer<_, _, _> = reader.map(Result::unwrap).collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.max_len(), Some(10));
+    }
+
+    #[test]
+    fn read_malformed() {
+        let text = "chr1\t10\t20\nchr1\tabc\t20\nchr1\t10\n";
+        let mut reader: BedReader<_, Bed3<String, u64>, _, _> = BedReader::new(text.as_bytes());
+        assert!(reader.next().unwrap().is_ok());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        match err.inner() {
+            ParseError::InvalidField { field, value } => {
+                assert_eq!(*field, "start");
+                assert_eq!(value, "abc");
+            }
+            _ => panic!("Expected invalid field"),
+        }
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(3));
+        match err.inner() {
+            ParseError::MissingField { field } => assert_eq!(*field, "end"),
+            _ => panic!("Expected missing field"),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn read_malformed_line_numbers_skip_headers() {
+        let text = "track name=x\n# comment\n\nchr1\t10\t20\ta\t0\t*\n";
+        let mut reader: BedReader<_, Bed6<String, u64, String>, _, _> =
+            BedReader::new(text.as_bytes());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(4));
+        assert_eq!(
+            err.to_string(),
+            "line 4: Unable to parse field strand from: *"
+        );
+    }
+
+    #[test]
+    fn read_inverted_interval() {
+        let text = "chr1\t20\t10\n";
+        let mut reader: BedReader<_, Bed3<String, u64>, _, _> = BedReader::new(text.as_bytes());
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(matches!(
+            err.inner(),
+            ParseError::InvalidInterval { start_field: "start", start, end_field: "end", end }
+                if start == "20" && end == "10"
+        ));
+    }
+
+    #[test]
+    fn read_policy_skip() {
+        let text = "chr1\t10\t20\nchr1\tabc\t20\nchr1\t20\t10\nchr1\t30\t40\n";
+        let mut reader: BedReader<_, Bed3<String, u64>, _, _> =
+            BedReader::new(text.as_bytes()).error_policy(ErrorPolicy::Skip);
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].start(), 30);
+        assert_eq!(reader.skipped(), 2);
+        assert_eq!(reader.repaired(), 0);
+    }
+
+    #[test]
+    fn read_policy_repair() {
+        let text = "chr1\t20\t10\tg1\t0\tx\nchr1\tabc\t20\tg2\t0\t+\n";
+        let mut reader: GtfReader<_, String, u64, String> =
+            GtfReader::new("chr1\tsrc\tgene\t11\t20\t.\t?\t.\tgene_id \"g\";\n".as_bytes())
+                .error_policy(ErrorPolicy::Repair);
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(record.strand(), Some(Strand::Unknown));
+        assert_eq!(reader.repaired(), 1);
+
+        let mut reader: BedReader<_, Bed6<String, u64, String>, _, _> =
+            BedReader::new(text.as_bytes()).error_policy(ErrorPolicy::Repair);
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].start(), records[0].end()), (10, 20));
+        assert_eq!(records[0].strand(), Some(Strand::Unknown));
+        assert_eq!(reader.repaired(), 1);
+        assert_eq!(reader.skipped(), 1);
+    }
+
+    #[test]
+    fn read_interned() {
+        let mut reader: InternedBedReader<_, Bed6<u32, u64, String>, _> =
+            InternedBedReader::new(BED6.as_bytes());
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(*records[0].chr(), 0);
+        assert_eq!(*records[1].chr(), 0);
+        assert_eq!(*records[2].chr(), 1);
+        assert_eq!(records[2].name(), "c");
+        let dict = reader.chrom_dict();
+        assert_eq!(dict.name(1), Some("chr2"));
+    }
+
+    #[test]
+    fn read_interned_predefined_order() {
+        let dict = ChromDict::from_names(["chr2", "chr1"]);
+        let text = "chr1\t10\t20\nchrX\t1\t5\n";
+        let mut reader: InternedBedReader<_, Bed3<u32, u64>, _> =
+            InternedBedReader::with_chrom_dict(text.as_bytes(), dict);
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        let dict = reader.into_chrom_dict();
+        assert_eq!(*records[0].chr(), 1);
+        assert_eq!(*records[1].chr(), 2);
+        assert_eq!(dict.name(2), Some("chrX"));
+    }
+
+    #[test]
+    fn read_interned_malformed() {
+        let text = "chr1\tabc\t20\n";
+        let mut reader: InternedBedReader<_, Bed3<u32, u64>, _> =
+            InternedBedReader::new(text.as_bytes());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        let text = "chr1\tabc\t20\nchr2\t10\t20\n";
+        let mut reader: InternedBedReader<_, Bed3<u32, u64>, _> =
+            InternedBedReader::new(text.as_bytes()).error_policy(ErrorPolicy::Skip);
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(reader.skipped(), 1);
+    }
+
+    #[test]
+    fn skip_header_prefixes_only() {
+        assert!(is_skipped("track"));
+        assert!(is_skipped("track name=x"));
+        assert!(is_skipped("browser hide all"));
+        assert!(!is_skipped("tracked\t10\t20"));
+    }
+}
@@ -0,0 +1,273 @@
+use super::{decoder::AutoDecoder, lines::LineReader};
+use crate::{
+    traits::{fields::parse_value, ChromBounds, MetaBounds, ParseError, ValueBounds},
+    types::{record::is_unmapped, ErrorPolicy},
+    Coordinates, Sam,
+};
+use num_traits::One;
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+/// A streaming reader of the reference footprints of SAM alignments.
+///
+/// Every mapped alignment is yielded as a [`Sam`] record spanning the
+/// reference bases covered by its CIGAR.
+/// With [`SamReader::split_blocks`] the alignment is instead yielded as
+/// one record per aligned block, splitting at deletions (`D`) and skipped
+/// regions (`N`) such as introns.
+///
+/// Header lines (`@`), empty lines, and unmapped reads are skipped.
+/// Malformed lines are handled according to the [`ErrorPolicy`] of the
+/// reader, where repairing a line is the same as skipping it.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::SamReader, Coordinates, Strand};
+///
+/// let text = "@SQ\tSN:chr1\tLN:1000\n\
+///     read1\t0\tchr1\t101\t60\t10M50N10M\t*\t0\t0\t*\t*\n\
+///     read2\t4\t*\t0\t0\t*\t*\t0\t0\t*\t*\n\
+///     read3\t16\tchr1\t201\t30\t5S20M\t*\t0\t0\t*\t*\n";
+///
+/// let reader: SamReader<_, String, u64, String> = SamReader::new(text.as_bytes());
+/// let spans = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(spans.len(), 2);
+/// assert_eq!((spans[0].start(), spans[0].end()), (100, 170));
+/// assert_eq!(spans[1].strand(), Some(Strand::Reverse));
+///
+/// let reader: SamReader<_, String, u64, String> =
+///     SamReader::new(text.as_bytes()).split_blocks(true);
+/// let blocks = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(blocks.len(), 3);
+/// assert_eq!((blocks[1].start(), blocks[1].end()), (160, 170));
+/// assert_eq!(blocks[1].name(), "read1");
+/// ```
+pub struct SamReader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    lines: LineReader<R>,
+    split_blocks: bool,
+    pending: VecDeque<Sam<C, T, N>>,
+}
+
+impl<R, C, T, N> SamReader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    pub fn new(reader: R) -> Self {
+        Self::from_lines(LineReader::new(reader))
+    }
+
+    fn from_lines(lines: LineReader<R>) -> Self {
+        Self {
+            lines,
+            split_blocks: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Sets whether alignments are split into their aligned blocks
+    #[must_use]
+    pub fn split_blocks(mut self, split_blocks: bool) -> Self {
+        self.split_blocks = split_blocks;
+        self
+    }
+
+    /// Sets how malformed lines are handled
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.lines.set_policy(policy);
+        self
+    }
+
+    /// Returns the number of malformed lines skipped so far
+    pub fn skipped(&self) -> usize {
+        self.lines.skipped()
+    }
+
+    /// Returns the number of malformed lines repaired so far
+    pub fn repaired(&self) -> usize {
+        self.lines.repaired()
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        self.lines.get_ref()
+    }
+
+    /// Consumes the reader returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.lines.into_inner()
+    }
+
+    /// Reads the next mapped alignment.
+    ///
+    /// Returns `false` once the reader is exhausted.
+    fn next_line(&mut self) -> Result<bool, ParseError> {
+        self.lines.next_line(|line| {
+            line.is_empty()
+                || line.starts_with('@')
+                || is_unmapped(&line.split('\t').collect::<Vec<_>>())
+        })
+    }
+
+    /// Parses the current alignment, queueing its blocks if requested
+    fn parse_line(&mut self) -> Result<(Sam<C, T, N>, bool), ParseError> {
+        let fields: Vec<&str> = self.lines.line().split('\t').collect();
+        let raw_chr = fields.get(2).copied().unwrap_or_default();
+        let chr = parse_value(raw_chr, "rname")?;
+        let (record, cigar) = Sam::parse_alignment(chr, &fields)?;
+        if !self.split_blocks {
+            return Ok((record, false));
+        }
+        let invalid = || ParseError::InvalidField {
+            field: "cigar",
+            value: fields[5].to_string(),
+        };
+        let mut blocks = cigar
+            .blocks()
+            .into_iter()
+            .map(|(start, end)| {
+                let (start, end) = T::from_u64(start)
+                    .zip(T::from_u64(end))
+                    .ok_or_else(invalid)?;
+                let mut block = record.clone();
+                block.update_endpoints(&(record.start() + start), &(record.start() + end));
+                Ok(block)
+            })
+            .collect::<Result<VecDeque<_>, ParseError>>()?;
+        let first = blocks.pop_front().ok_or_else(invalid)?;
+        self.pending = blocks;
+        Ok((first, false))
+    }
+}
+
+impl<C, T, N> SamReader<AutoDecoder<BufReader<File>>, C, T, N>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    /// Opens a file for reading alignments, decompressing gzip and BGZF
+    /// files on the fly
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let reader = AutoDecoder::from_path(&path)?;
+        let lines = LineReader::new(reader).with_path(path.as_ref().to_path_buf());
+        Ok(Self::from_lines(lines))
+    }
+}
+
+impl<R, C, T, N> Iterator for SamReader<R, C, T, N>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    type Item = Result<Sam<C, T, N>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(block) = self.pending.pop_front() {
+            return Some(Ok(block));
+        }
+        loop {
+            match self.next_line() {
+                Ok(true) => {
+                    let result = self.parse_line();
+                    if let Some(result) = self.lines.resolve(result) {
+                        return Some(result);
+                    }
+                }
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Bed6, Strand};
+
+    const TEXT: &str = "@HD\tVN:1.6\n\
+        r1\t0\tchr1\t1\t60\t10M2I10M\t*\t0\t0\t*\t*\n\
+        r2\t16\tchr1\t51\t20\t3S10M5D10M100N5M\t*\t0\t0\t*\t*\n\
+        r3\t4\tchr1\t51\t0\t*\t*\t0\t0\t*\t*\n\
+        \n\
+        r4\t256\tchr2\t5\t0\t4M\t*\t0\t0\t*\t*\n";
+
+    type Reader<'a> = SamReader<&'a [u8], String, u64, String>;
+
+    #[test]
+    fn test_spans() {
+        let reader: Reader = SamReader::new(TEXT.as_bytes());
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let spans: Vec<_> = records.iter().map(|r| (r.start(), r.end())).collect();
+        assert_eq!(spans, vec![(0, 20), (50, 180), (4, 8)]);
+        assert_eq!(records[1].strand(), Some(Strand::Reverse));
+        assert!(records[2].is_secondary());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let reader: Reader = SamReader::new(TEXT.as_bytes()).split_blocks(true);
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let blocks: Vec<_> = records
+            .iter()
+            .map(|r| (r.name().as_str(), r.start(), r.end()))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                ("r1", 0, 20),
+                ("r2", 50, 60),
+                ("r2", 65, 75),
+                ("r2", 175, 180),
+                ("r4", 4, 8),
+            ]
+        );
+        assert!(records[1..4]
+            .iter()
+            .all(|r| r.mapq() == 20 && r.flag() == 16));
+    }
+
+    #[test]
+    fn test_to_bed6() {
+        let reader: Reader = SamReader::new(TEXT.as_bytes());
+        let beds: Vec<Bed6<String, u64, String>> = reader.map(|r| r.unwrap().into()).collect();
+        assert_eq!(format!("{}", beds[1]), "chr1\t50\t180\tr2\t20\t-");
+    }
+
+    #[test]
+    fn test_invalid() {
+        let text = "r1\t0\tchr1\t1\t60\t10Z\n";
+        let mut reader: Reader = SamReader::new(text.as_bytes());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(1));
+        assert!(matches!(
+            err.inner(),
+            ParseError::InvalidField { field: "cigar", .. }
+        ));
+        assert!(reader.next().is_none());
+
+        let text = format!("{text}{TEXT}");
+        let mut reader: Reader = SamReader::new(text.as_bytes())
+            .split_blocks(true)
+            .error_policy(ErrorPolicy::Skip);
+        assert_eq!(reader.by_ref().count(), 5);
+        assert_eq!(reader.skipped(), 1);
+    }
+}
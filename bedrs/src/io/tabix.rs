@@ -0,0 +1,924 @@
+use super::bgzf::{BgzfReader, BgzfWriter};
+use crate::traits::ParseError;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::Path,
+};
+
+/// Magic bytes of a tabix index
+const TBI_MAGIC: &[u8; 4] = b"TBI\x01";
+
+/// Magic bytes of a coordinate-sorted index
+const CSI_MAGIC: &[u8; 4] = b"CSI\x01";
+
+/// The window size (as a power of two) of tabix indexes
+const TBI_MIN_SHIFT: u32 = 14;
+
+/// The number of binning levels of tabix indexes
+const TBI_DEPTH: u32 = 5;
+
+/// The flag set on the index format for 0-based half-open files
+const UCSC_FLAG: i32 = 0x10000;
+
+fn invalid_data(msg: &str) -> ParseError {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string()).into()
+}
+
+/// Returns the first bin of a level of the binning scheme
+fn bin_first(level: u32) -> u32 {
+    ((1 << (3 * level)) - 1) / 7
+}
+
+/// Validates that bins of a scheme fit in 32 bits and its positions in
+/// 64 bits
+fn check_scheme(min_shift: u32, depth: u32) -> Result<(), ParseError> {
+    if depth > 10 || min_shift + 3 * depth > 63 {
+        return Err(invalid_data("unsupported index binning scheme"));
+    }
+    Ok(())
+}
+
+/// Returns the offset of a position within a level of windows of
+/// `2^shift` bases
+fn bin_offset(pos: u64, shift: u32) -> u32 {
+    u32::try_from(pos >> shift).unwrap_or(u32::MAX)
+}
+
+/// Returns the level of a bin in a scheme of `depth` levels
+fn bin_level(bin: u32, depth: u32) -> u32 {
+    (0..=depth)
+        .rev()
+        .find(|&l| bin >= bin_first(l))
+        .unwrap_or(0)
+}
+
+/// Returns the smallest bin fully containing `[beg, end)`
+fn reg2bin(beg: u64, end: u64, min_shift: u32, depth: u32) -> u32 {
+    let end = end.saturating_sub(1).max(beg);
+    let mut shift = min_shift;
+    for level in (1..=depth).rev() {
+        if beg >> shift == end >> shift {
+            return bin_first(level) + bin_offset(beg, shift);
+        }
+        shift += 3;
+    }
+    0
+}
+
+/// Returns all bins which may contain intervals overlapping `[beg, end)`
+fn reg2bins(beg: u64, end: u64, min_shift: u32, depth: u32) -> Vec<u32> {
+    let max_pos = 1u64 << (min_shift + 3 * depth);
+    let end = end.min(max_pos);
+    if beg >= end {
+        return Vec::new();
+    }
+    let end = end - 1;
+    let mut bins = Vec::new();
+    let mut shift = min_shift + 3 * depth;
+    for level in 0..=depth {
+        let first = bin_first(level);
+        let lo = first + bin_offset(beg, shift);
+        let hi = first + bin_offset(end, shift);
+        bins.extend(lo..=hi);
+        shift = shift.saturating_sub(3);
+    }
+    bins
+}
+
+/// A little-endian cursor over a decompressed index
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + n)
+            .ok_or_else(|| invalid_data("truncated index"))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+    fn i32(&mut self) -> Result<i32, ParseError> {
+        let b = self.take(4)?;
+        Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+    fn u64(&mut self) -> Result<u64, ParseError> {
+        let b = self.take(8)?;
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(b);
+        Ok(u64::from_le_bytes(buf))
+    }
+    fn count(&mut self) -> Result<usize, ParseError> {
+        usize::try_from(self.i32()?).map_err(|_| invalid_data("negative count in index"))
+    }
+    /// Reads a count of entries taking at least `size` bytes each, failing
+    /// if the remaining data cannot hold them
+    fn bounded_count(&mut self, size: usize) -> Result<usize, ParseError> {
+        let count = self.count()?;
+        let remaining = self.data.len().saturating_sub(self.pos);
+        if count > remaining / size {
+            return Err(invalid_data("index count exceeds the index size"));
+        }
+        Ok(count)
+    }
+}
+
+/// Describes the columns of the text file covered by a [`TabixIndex`].
+///
+/// Column numbers are 1-based as in the tabix format, and an end column
+/// of 0 marks a file whose records span a single base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexConfig {
+    preset: i32,
+    col_seq: usize,
+    col_beg: usize,
+    col_end: usize,
+    meta: u8,
+    skip: usize,
+    zero_based: bool,
+}
+
+impl IndexConfig {
+    #[must_use]
+    pub fn new(col_seq: usize, col_beg: usize, col_end: usize, zero_based: bool) -> Self {
+        Self {
+            preset: 0,
+            col_seq,
+            col_beg,
+            col_end,
+            meta: b'#',
+            skip: 0,
+            zero_based,
+        }
+    }
+
+    /// The layout of BED and bedGraph files
+    #[must_use]
+    pub fn bed() -> Self {
+        Self::new(1, 2, 3, true)
+    }
+
+    /// The layout of GFF3 and GTF files
+    #[must_use]
+    pub fn gff() -> Self {
+        Self::new(1, 4, 5, false)
+    }
+
+    /// Sets the leading character of header lines
+    #[must_use]
+    pub fn with_meta(mut self, meta: u8) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    /// Sets the number of leading lines to skip
+    #[must_use]
+    pub fn with_skip(mut self, skip: usize) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    #[must_use]
+    pub fn col_seq(&self) -> usize {
+        self.col_seq
+    }
+    #[must_use]
+    pub fn col_beg(&self) -> usize {
+        self.col_beg
+    }
+    #[must_use]
+    pub fn col_end(&self) -> usize {
+        self.col_end
+    }
+    #[must_use]
+    pub fn meta(&self) -> u8 {
+        self.meta
+    }
+    #[must_use]
+    pub fn skip(&self) -> usize {
+        self.skip
+    }
+    #[must_use]
+    pub fn zero_based(&self) -> bool {
+        self.zero_based
+    }
+
+    /// Returns whether a line is a header line
+    pub(crate) fn is_meta(&self, line: &str) -> bool {
+        line.as_bytes().first() == Some(&self.meta)
+    }
+
+    /// Extracts the chromosome and half-open span of a line
+    fn parse_line<'a>(&self, line: &'a str) -> Result<(&'a str, u64, u64), ParseError> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let field = |col: usize, name: &'static str| {
+            col.checked_sub(1)
+                .and_then(|idx| fields.get(idx))
+                .copied()
+                .ok_or(ParseError::MissingField { field: name })
+        };
+        let position = |col: usize, name: &'static str| {
+            let value = field(col, name)?;
+            value.parse::<u64>().map_err(|_| ParseError::InvalidField {
+                field: name,
+                value: value.to_string(),
+            })
+        };
+        let seq = field(self.col_seq, "chr")?;
+        let mut beg = position(self.col_beg, "start")?;
+        let mut end = if self.col_end == 0 {
+            beg + 1
+        } else {
+            position(self.col_end, "end")?
+        };
+        if !self.zero_based {
+            beg = beg.saturating_sub(1);
+            if self.col_end == 0 {
+                end -= 1;
+            }
+        }
+        end = end.max(beg + 1);
+        Ok((seq, beg, end))
+    }
+
+    fn read_from(bytes: &mut ByteReader) -> Result<(Self, Vec<String>), ParseError> {
+        let format = bytes.i32()?;
+        let col_seq = bytes.count()?;
+        let col_beg = bytes.count()?;
+        let col_end = bytes.count()?;
+        let meta = u8::try_from(bytes.i32()?).unwrap_or(b'#');
+        let skip = bytes.count()?;
+        let l_nm = bytes.count()?;
+        let names = bytes
+            .take(l_nm)?
+            .split(|&b| b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect();
+        let config = Self {
+            preset: format & 0xffff,
+            col_seq,
+            col_beg,
+            col_end,
+            meta,
+            skip,
+            zero_based: format & UCSC_FLAG != 0,
+        };
+        Ok((config, names))
+    }
+
+    fn write_to(&self, names: &[String], out: &mut Vec<u8>) -> Result<(), ParseError> {
+        let int = |value: usize| {
+            i32::try_from(value).map_err(|_| invalid_data("value does not fit in the index"))
+        };
+        let format = if self.zero_based {
+            self.preset | UCSC_FLAG
+        } else {
+            self.preset
+        };
+        let name_bytes: Vec<u8> = names
+            .iter()
+            .flat_map(|name| name.bytes().chain(std::iter::once(0)))
+            .collect();
+        out.extend_from_slice(&format.to_le_bytes());
+        out.extend_from_slice(&int(self.col_seq)?.to_le_bytes());
+        out.extend_from_slice(&int(self.col_beg)?.to_le_bytes());
+        out.extend_from_slice(&int(self.col_end)?.to_le_bytes());
+        out.extend_from_slice(&i32::from(self.meta).to_le_bytes());
+        out.extend_from_slice(&int(self.skip)?.to_le_bytes());
+        out.extend_from_slice(&int(name_bytes.len())?.to_le_bytes());
+        out.extend_from_slice(&name_bytes);
+        Ok(())
+    }
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self::bed()
+    }
+}
+
+/// A contiguous range of BGZF virtual offsets holding indexed records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Chunk {
+    start: u64,
+    end: u64,
+}
+
+impl Chunk {
+    #[must_use]
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+    /// The virtual offset of the first record of the chunk
+    #[must_use]
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+    /// The virtual offset following the last record of the chunk
+    #[must_use]
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Bin {
+    loffset: u64,
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ReferenceIndex {
+    bins: BTreeMap<u32, Bin>,
+    linear: Vec<u64>,
+}
+
+impl ReferenceIndex {
+    /// Registers a record spanning `[beg, end)` stored at `chunk`
+    fn insert(&mut self, beg: u64, end: u64, chunk: Chunk, min_shift: u32, depth: u32) {
+        let bin = self
+            .bins
+            .entry(reg2bin(beg, end, min_shift, depth))
+            .or_default();
+        match bin.chunks.last_mut() {
+            Some(last) if last.end == chunk.start => last.end = chunk.end,
+            _ => bin.chunks.push(chunk),
+        }
+
+        let first = usize::try_from(beg >> min_shift).unwrap_or(usize::MAX);
+        let last = usize::try_from((end - 1) >> min_shift).unwrap_or(usize::MAX);
+        if self.linear.len() <= last {
+            self.linear.resize(last + 1, u64::MAX);
+        }
+        for window in &mut self.linear[first..=last] {
+            if *window == u64::MAX {
+                *window = chunk.start;
+            }
+        }
+    }
+
+    /// Fills the gaps of the linear index and derives the linear offset
+    /// of every bin from it.
+    fn finish(&mut self, depth: u32) {
+        let mut previous = 0;
+        for window in &mut self.linear {
+            if *window == u64::MAX {
+                *window = previous;
+            }
+            previous = *window;
+        }
+        if self.linear.is_empty() {
+            return;
+        }
+        for (&bin, entry) in &mut self.bins {
+            let level = bin_level(bin, depth);
+            let offset = u64::from(bin - bin_first(level)) << (3 * (depth - level));
+            let window = usize::try_from(offset)
+                .map_or(self.linear.len() - 1, |w| w.min(self.linear.len() - 1));
+            entry.loffset = self.linear[window];
+        }
+    }
+}
+
+/// A [tabix](https://samtools.github.io/hts-specs/tabix.pdf) or
+/// [CSI](https://samtools.github.io/hts-specs/CSIv1.pdf) index over a
+/// sorted BGZF-compressed text file.
+///
+/// The index maps regions to the chunks of the compressed file which may
+/// contain records overlapping them, so that a region can be fetched
+/// without decompressing the whole file.
+/// Indexes can be read from `.tbi` and `.csi` files, or built from a
+/// sorted file with [`TabixIndex::build`] and written back out.
+///
+/// Records are usually fetched through an [`IndexedReader`](super::IndexedReader).
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::io::{BgzfWriter, IndexConfig, TabixIndex};
+/// use std::io::Write;
+///
+/// let mut writer = BgzfWriter::new(Vec::new());
+/// writer.write_all(b"chr1\t10\t20\nchr1\t30\t40\nchr2\t5\t15\n").unwrap();
+/// let bytes = writer.finish().unwrap();
+///
+/// let index = TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).unwrap();
+/// assert_eq!(index.names(), ["chr1", "chr2"]);
+/// assert_eq!(index.chunks("chr1", 0, 100).len(), 1);
+/// assert!(index.chunks("chr3", 0, 100).is_empty());
+///
+/// let mut tbi = Vec::new();
+/// index.write_tbi(&mut tbi).unwrap();
+/// let reread = TabixIndex::read(tbi.as_slice()).unwrap();
+/// assert_eq!(reread.names(), index.names());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabixIndex {
+    config: IndexConfig,
+    min_shift: u32,
+    depth: u32,
+    names: Vec<String>,
+    references: Vec<ReferenceIndex>,
+    unplaced: Option<u64>,
+}
+
+impl TabixIndex {
+    /// Builds a tabix index over a sorted BGZF-compressed stream
+    pub fn build<R: Read>(reader: R, config: IndexConfig) -> Result<Self, ParseError> {
+        Self::build_csi(reader, config, TBI_MIN_SHIFT, TBI_DEPTH)
+    }
+
+    /// Builds an index over a sorted BGZF-compressed stream using a
+    /// custom binning scheme of `depth` levels over windows of
+    /// `2^min_shift` bases.
+    ///
+    /// Schemes other than the tabix defaults (14 and 5) can only be
+    /// written as CSI indexes, but allow for chromosomes longer than
+    /// 512Mbp.
+    pub fn build_csi<R: Read>(
+        reader: R,
+        config: IndexConfig,
+        min_shift: u32,
+        depth: u32,
+    ) -> Result<Self, ParseError> {
+        check_scheme(min_shift, depth)?;
+        let max_pos = 1u64 << (min_shift + 3 * depth);
+        let mut reader = BgzfReader::new(reader);
+        let mut index = Self {
+            config,
+            min_shift,
+            depth,
+            names: Vec::new(),
+            references: Vec::new(),
+            unplaced: Some(0),
+        };
+        let mut buffer = String::new();
+        let mut line_number = 0;
+        let mut last_beg = 0;
+        loop {
+            let start = reader.virtual_offset();
+            buffer.clear();
+            if reader.read_line(&mut buffer)? == 0 {
+                break;
+            }
+            let end = reader.virtual_offset();
+            line_number += 1;
+            let line = buffer.trim_end_matches(['\n', '\r']);
+            if line_number <= index.config.skip || line.is_empty() || index.config.is_meta(line) {
+                continue;
+            }
+            let (seq, beg, stop) = index.config.parse_line(line)?;
+            if stop > max_pos {
+                return Err(ParseError::InvalidField {
+                    field: "end",
+                    value: stop.to_string(),
+                });
+            }
+            if index.names.last().map(String::as_str) != Some(seq) {
+                if index.names.iter().any(|name| name == seq) {
+                    return Err(ParseError::InvalidField {
+                        field: "chr",
+                        value: format!("{seq} (records are not grouped by chromosome)"),
+                    });
+                }
+                index.names.push(seq.to_string());
+                index.references.push(ReferenceIndex::default());
+                last_beg = 0;
+            }
+            if beg < last_beg {
+                return Err(ParseError::InvalidField {
+                    field: "start",
+                    value: format!("{beg} (records are not sorted on {seq})"),
+                });
+            }
+            last_beg = beg;
+            if let Some(reference) = index.references.last_mut() {
+                reference.insert(beg, stop, Chunk::new(start, end), min_shift, depth);
+            }
+        }
+        for reference in &mut index.references {
+            reference.finish(depth);
+        }
+        Ok(index)
+    }
+
+    /// Reads a BGZF-compressed tabix or CSI index
+    pub fn read<R: Read>(reader: R) -> Result<Self, ParseError> {
+        let mut data = Vec::new();
+        BgzfReader::new(reader).read_to_end(&mut data)?;
+        let mut bytes = ByteReader::new(&data);
+        match bytes.take(4)? {
+            magic if magic == TBI_MAGIC => Self::read_tbi(&mut bytes),
+            magic if magic == CSI_MAGIC => Self::read_csi(&mut bytes),
+            _ => Err(invalid_data("not a tabix or CSI index")),
+        }
+    }
+
+    /// Opens a `.tbi` or `.csi` index file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let file = File::open(path)?;
+        Self::read(BufReader::new(file))
+    }
+
+    fn read_tbi(bytes: &mut ByteReader) -> Result<Self, ParseError> {
+        // Each reference holds at least its bin and interval counts
+        let n_ref = bytes.bounded_count(8)?;
+        let (config, names) = IndexConfig::read_from(bytes)?;
+        if names.len() != n_ref {
+            return Err(invalid_data("index name count does not match references"));
+        }
+        let mut references = Vec::with_capacity(n_ref);
+        for _ in 0..n_ref {
+            let mut reference = ReferenceIndex::default();
+            for _ in 0..bytes.count()? {
+                let bin = bytes.u32()?;
+                let chunks = Self::read_chunks(bytes)?;
+                reference.bins.insert(bin, Bin { loffset: 0, chunks });
+            }
+            let n_intv = bytes.count()?;
+            reference.linear = (0..n_intv).map(|_| bytes.u64()).collect::<Result<_, _>>()?;
+            references.push(reference);
+        }
+        let unplaced = if bytes.is_empty() {
+            None
+        } else {
+            Some(bytes.u64()?)
+        };
+        Ok(Self {
+            config,
+            min_shift: TBI_MIN_SHIFT,
+            depth: TBI_DEPTH,
+            names,
+            references,
+            unplaced,
+        })
+    }
+
+    fn read_csi(bytes: &mut ByteReader) -> Result<Self, ParseError> {
+        let min_shift =
+            u32::try_from(bytes.i32()?).map_err(|_| invalid_data("invalid min_shift"))?;
+        let depth = u32::try_from(bytes.i32()?).map_err(|_| invalid_data("invalid depth"))?;
+        check_scheme(min_shift, depth)?;
+        let l_aux = bytes.count()?;
+        let aux = bytes.take(l_aux)?;
+        // Each reference holds at least its bin count
+        let n_ref = bytes.bounded_count(4)?;
+        let (config, names) = if l_aux >= 28 {
+            IndexConfig::read_from(&mut ByteReader::new(aux))?
+        } else {
+            (
+                IndexConfig::default(),
+                (0..n_ref).map(|i| i.to_string()).collect(),
+            )
+        };
+        if names.len() != n_ref {
+            return Err(invalid_data("index name count does not match references"));
+        }
+        let mut references = Vec::with_capacity(n_ref);
+        for _ in 0..n_ref {
+            let mut reference = ReferenceIndex::default();
+            for _ in 0..bytes.count()? {
+                let bin = bytes.u32()?;
+                let loffset = bytes.u64()?;
+                let chunks = Self::read_chunks(bytes)?;
+                reference.bins.insert(bin, Bin { loffset, chunks });
+            }
+            references.push(reference);
+        }
+        let unplaced = if bytes.is_empty() {
+            None
+        } else {
+            Some(bytes.u64()?)
+        };
+        Ok(Self {
+            config,
+            min_shift,
+            depth,
+            names,
+            references,
+            unplaced,
+        })
+    }
+
+    fn read_chunks(bytes: &mut ByteReader) -> Result<Vec<Chunk>, ParseError> {
+        (0..bytes.count()?)
+            .map(|_| Ok(Chunk::new(bytes.u64()?, bytes.u64()?)))
+            .collect()
+    }
+
+    fn write_chunks(chunks: &[Chunk], out: &mut Vec<u8>) -> Result<(), ParseError> {
+        let n_chunk =
+            i32::try_from(chunks.len()).map_err(|_| invalid_data("too many index chunks"))?;
+        out.extend_from_slice(&n_chunk.to_le_bytes());
+        for chunk in chunks {
+            out.extend_from_slice(&chunk.start.to_le_bytes());
+            out.extend_from_slice(&chunk.end.to_le_bytes());
+        }
+        Ok(())
+    }
+
+    fn write_count(count: usize, out: &mut Vec<u8>) -> Result<(), ParseError> {
+        let count = i32::try_from(count).map_err(|_| invalid_data("too many index entries"))?;
+        out.extend_from_slice(&count.to_le_bytes());
+        Ok(())
+    }
+
+    fn write_compressed<W: Write>(data: &[u8], writer: W) -> Result<(), ParseError> {
+        let mut writer = BgzfWriter::new(writer);
+        writer.write_all(data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes the index in the BGZF-compressed tabix format.
+    ///
+    /// Fails if the index uses a binning scheme other than the tabix one.
+    pub fn write_tbi<W: Write>(&self, writer: W) -> Result<(), ParseError> {
+        if self.min_shift != TBI_MIN_SHIFT || self.depth != TBI_DEPTH {
+            return Err(invalid_data(
+                "tabix indexes require a min_shift of 14 and a depth of 5",
+            ));
+        }
+        let mut out = TBI_MAGIC.to_vec();
+        Self::write_count(self.names.len(), &mut out)?;
+        self.config.write_to(&self.names, &mut out)?;
+        for reference in &self.references {
+            Self::write_count(reference.bins.len(), &mut out)?;
+            for (bin, entry) in &reference.bins {
+                out.extend_from_slice(&bin.to_le_bytes());
+                Self::write_chunks(&entry.chunks, &mut out)?;
+            }
+            Self::write_count(reference.linear.len(), &mut out)?;
+            for offset in &reference.linear {
+                out.extend_from_slice(&offset.to_le_bytes());
+            }
+        }
+        if let Some(unplaced) = self.unplaced {
+            out.extend_from_slice(&unplaced.to_le_bytes());
+        }
+        Self::write_compressed(&out, writer)
+    }
+
+    /// Writes the index in the BGZF-compressed CSI format
+    pub fn write_csi<W: Write>(&self, writer: W) -> Result<(), ParseError> {
+        let mut aux = Vec::new();
+        self.config.write_to(&self.names, &mut aux)?;
+        let mut out = CSI_MAGIC.to_vec();
+        Self::write_count(self.min_shift as usize, &mut out)?;
+        Self::write_count(self.depth as usize, &mut out)?;
+        Self::write_count(aux.len(), &mut out)?;
+        out.extend_from_slice(&aux);
+        Self::write_count(self.names.len(), &mut out)?;
+        for reference in &self.references {
+            Self::write_count(reference.bins.len(), &mut out)?;
+            for (bin, entry) in &reference.bins {
+                out.extend_from_slice(&bin.to_le_bytes());
+                out.extend_from_slice(&entry.loffset.to_le_bytes());
+                Self::write_chunks(&entry.chunks, &mut out)?;
+            }
+        }
+        if let Some(unplaced) = self.unplaced {
+            out.extend_from_slice(&unplaced.to_le_bytes());
+        }
+        Self::write_compressed(&out, writer)
+    }
+
+    /// The column layout of the indexed file
+    #[must_use]
+    pub fn config(&self) -> &IndexConfig {
+        &self.config
+    }
+
+    /// The chromosome names of the indexed file in file order
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The window size of the binning scheme as a power of two
+    #[must_use]
+    pub fn min_shift(&self) -> u32 {
+        self.min_shift
+    }
+
+    /// The number of levels of the binning scheme
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// The number of unplaced records if recorded by the index
+    #[must_use]
+    pub fn unplaced(&self) -> Option<u64> {
+        self.unplaced
+    }
+
+    /// Returns the smallest virtual offset at which records overlapping
+    /// a position can be found.
+    fn min_offset(&self, reference: &ReferenceIndex, beg: u64) -> u64 {
+        if !reference.linear.is_empty() {
+            let window = usize::try_from(beg >> self.min_shift).unwrap_or(usize::MAX);
+            return reference.linear[window.min(reference.linear.len() - 1)];
+        }
+        let max_window = (1 << (3 * self.depth)) - 1;
+        let mut bin = bin_first(self.depth) + bin_offset(beg, self.min_shift).min(max_window);
+        loop {
+            if let Some(entry) = reference.bins.get(&bin) {
+                return entry.loffset;
+            }
+            if bin == 0 {
+                return 0;
+            }
+            bin = (bin - 1) >> 3;
+        }
+    }
+
+    /// Returns the sorted and merged chunks which may contain records on
+    /// `chr` overlapping the half-open span `[start, end)`.
+    #[must_use]
+    pub fn chunks(&self, chr: &str, start: u64, end: u64) -> Vec<Chunk> {
+        let Some(reference) = self
+            .names
+            .iter()
+            .position(|name| name == chr)
+            .map(|idx| &self.references[idx])
+        else {
+            return Vec::new();
+        };
+        let min_offset = self.min_offset(reference, start);
+        let mut chunks: Vec<Chunk> = reg2bins(start, end, self.min_shift, self.depth)
+            .into_iter()
+            .filter_map(|bin| reference.bins.get(&bin))
+            .flat_map(|entry| entry.chunks.iter().copied())
+            .filter(|chunk| chunk.end > min_offset)
+            .collect();
+        chunks.sort_unstable();
+
+        let mut merged: Vec<Chunk> = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            match merged.last_mut() {
+                Some(last) if chunk.start <= last.end => last.end = last.end.max(chunk.end),
+                _ => merged.push(chunk),
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use std::fmt::Write as _;
+
+    fn compress(text: &str) -> Vec<u8> {
+        let mut writer = BgzfWriter::new(Vec::new());
+        writer.write_all(text.as_bytes()).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn binning_matches_spec() {
+        assert_eq!(reg2bin(0, 1, 14, 5), 4681);
+        assert_eq!(reg2bin(0, 1 << 14, 14, 5), 4681);
+        assert_eq!(reg2bin(0, (1 << 14) + 1, 14, 5), 585);
+        assert_eq!(reg2bin(0, 1 << 29, 14, 5), 0);
+        assert_eq!(reg2bin(1 << 14, (1 << 14) + 1, 14, 5), 4682);
+        assert_eq!(bin_level(0, 5), 0);
+        assert_eq!(bin_level(73, 5), 3);
+        assert_eq!(bin_level(585, 5), 4);
+        assert_eq!(bin_level(4681, 5), 5);
+    }
+
+    #[test]
+    fn reg2bins_covers_all_levels() {
+        let bins = reg2bins(0, 1, 14, 5);
+        assert_eq!(bins, vec![0, 1, 9, 73, 585, 4681]);
+        let bins = reg2bins(0, (1 << 14) + 1, 14, 5);
+        assert!(bins.contains(&4681));
+        assert!(bins.contains(&4682));
+        assert!(reg2bins(10, 10, 14, 5).is_empty());
+    }
+
+    #[test]
+    fn config_parse_line() {
+        let bed = IndexConfig::bed();
+        assert_eq!(bed.parse_line("chr1\t10\t20").unwrap(), ("chr1", 10, 20));
+        let gff = IndexConfig::gff();
+        let line = "chr1\tsrc\tgene\t11\t20\t.\t+\t.\tID=a";
+        assert_eq!(gff.parse_line(line).unwrap(), ("chr1", 10, 20));
+        let point = IndexConfig::new(1, 2, 0, false);
+        assert_eq!(point.parse_line("chr1\t5").unwrap(), ("chr1", 4, 5));
+        assert!(bed.parse_line("chr1\tx\t20").is_err());
+        assert!(bed.parse_line("chr1\t10").is_err());
+    }
+
+    #[test]
+    fn build_skips_headers() {
+        let bytes = compress("#header\nchr1\t10\t20\n\nchr2\t5\t15\n");
+        let index = TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).unwrap();
+        assert_eq!(index.names(), ["chr1", "chr2"]);
+        assert_eq!(index.unplaced(), Some(0));
+    }
+
+    #[test]
+    fn build_rejects_unsorted() {
+        let bytes = compress("chr1\t10\t20\nchr1\t5\t15\n");
+        assert!(TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).is_err());
+        let bytes = compress("chr1\t10\t20\nchr2\t5\t15\nchr1\t30\t40\n");
+        assert!(TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).is_err());
+    }
+
+    #[test]
+    fn chunks_respect_region() {
+        let text = (0..2000u64).fold(String::new(), |mut text, i| {
+            let _ = writeln!(text, "chr1\t{}\t{}", i * 1000, i * 1000 + 500);
+            text
+        });
+        let bytes = compress(&text);
+        let index = TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).unwrap();
+        let all = index.chunks("chr1", 0, 10_000_000);
+        let some = index.chunks("chr1", 1_500_000, 1_500_100);
+        assert!(!all.is_empty());
+        assert!(!some.is_empty());
+        assert!(some[0].start() > all[0].start());
+        assert!(index.chunks("chr1", 5_000_000, 6_000_000).is_empty());
+    }
+
+    #[test]
+    fn tbi_round_trip() {
+        let bytes = compress("chr1\t10\t20\nchr1\t30\t40\nchr2\t5\t15\n");
+        let index = TabixIndex::build(bytes.as_slice(), IndexConfig::bed()).unwrap();
+        let mut tbi = Vec::new();
+        index.write_tbi(&mut tbi).unwrap();
+        let reread = TabixIndex::read(tbi.as_slice()).unwrap();
+        assert_eq!(reread.config(), index.config());
+        assert_eq!(reread.names(), index.names());
+        assert_eq!(reread.chunks("chr2", 0, 10), index.chunks("chr2", 0, 10));
+        assert_eq!(reread.unplaced(), Some(0));
+    }
+
+    #[test]
+    fn csi_round_trip() {
+        let bytes = compress("chr1\t10\t20\nchr1\t30\t40\nchr2\t5\t15\n");
+        let index = TabixIndex::build_csi(bytes.as_slice(), IndexConfig::bed(), 12, 6).unwrap();
+        assert!(index.write_tbi(Vec::new()).is_err());
+        let mut csi = Vec::new();
+        index.write_csi(&mut csi).unwrap();
+        let reread = TabixIndex::read(csi.as_slice()).unwrap();
+        assert_eq!(reread.min_shift(), 12);
+        assert_eq!(reread.depth(), 6);
+        assert_eq!(reread.config(), index.config());
+        assert_eq!(reread.names(), index.names());
+        assert_eq!(reread.chunks("chr1", 0, 40), index.chunks("chr1", 0, 40));
+    }
+
+    #[test]
+    fn read_rejects_garbage() {
+        let bytes = compress("not an index");
+        assert!(TabixIndex::read(bytes.as_slice()).is_err());
+        let bytes = compress("TBI\x01\x01");
+        assert!(TabixIndex::read(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn read_rejects_oversized_counts() {
+        let header = |magic: &[u8], fields: &[i32]| {
+            let mut data = magic.to_vec();
+            for field in fields {
+                data.extend_from_slice(&field.to_le_bytes());
+            }
+            let mut writer = BgzfWriter::new(Vec::new());
+            writer.write_all(&data).unwrap();
+            writer.finish().unwrap()
+        };
+        for bytes in [
+            header(TBI_MAGIC, &[i32::MAX]),
+            header(CSI_MAGIC, &[14, 5, 0, i32::MAX]),
+            header(CSI_MAGIC, &[14, 5, 0, 2, 0]),
+        ] {
+            let err = TabixIndex::read(bytes.as_slice()).unwrap_err();
+            assert_eq!(err.to_string(), "index count exceeds the index size");
+        }
+        // References without bins fit in four bytes each
+        let bytes = header(CSI_MAGIC, &[14, 5, 0, 2, 0, 0]);
+        assert_eq!(
+            TabixIndex::read(bytes.as_slice()).unwrap().names(),
+            ["0", "1"]
+        );
+    }
+}
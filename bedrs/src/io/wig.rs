@@ -0,0 +1,433 @@
+use super::{decoder::AutoDecoder, lines::LineReader};
+use crate::{
+    io::reader::is_skipped,
+    traits::{fields::parse_value, ChromBounds, ParseError, ValueBounds},
+    types::ErrorPolicy,
+    BedGraph, Coordinates,
+};
+use num_traits::One;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+/// The declaration currently governing the data lines of a WIG stream
+enum Step<C, T> {
+    Fixed { chr: C, start: T, step: T, span: T },
+    Variable { chr: C, span: T },
+}
+
+/// A streaming reader of [WIG](https://genome.ucsc.edu/goldenPath/help/wiggle.html)
+/// signal tracks yielding [`BedGraph`] records.
+///
+/// Both `fixedStep` and `variableStep` declarations are supported along
+/// with their `chrom`, `start`, `step`, and `span` parameters (`span`
+/// defaults to 1).
+/// WIG positions are 1-based and are converted to the half-open
+/// convention, so that each data line becomes a record covering `span`
+/// bases.
+///
+/// Empty lines, comments, and `track` or `browser` lines are skipped.
+/// Malformed lines are handled according to the [`ErrorPolicy`] of the
+/// reader, where repairing a line is the same as skipping it.
+/// The data lines following a malformed declaration are malformed as
+/// well.
+///
+/// With [`WigReader::with_collapse`] adjacent records on the same
+/// chromosome with equal values are merged into a single record.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::WigReader, Coordinates};
+///
+/// let text = "track type=wiggle_0\n\
+///     fixedStep chrom=chr1 start=11 step=10 span=5\n\
+///     1.5\n\
+///     2.0\n\
+///     variableStep chrom=chr2 span=2\n\
+///     101 3.0\n";
+///
+/// let reader: WigReader<_, String, u32> = WigReader::new(text.as_bytes());
+/// let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(records.len(), 3);
+/// assert_eq!((records[0].start(), records[0].end()), (10, 15));
+/// assert_eq!((records[1].start(), records[1].end()), (20, 25));
+/// assert_eq!((records[2].start(), records[2].end()), (100, 102));
+/// assert_eq!(records[2].score(), 3.0);
+/// ```
+pub struct WigReader<R, C, T>
+where
+    R: BufRead,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    lines: LineReader<R>,
+    step: Option<Step<C, T>>,
+    collapse: bool,
+    pending: Option<BedGraph<C, T>>,
+}
+
+impl<R, C, T> WigReader<R, C, T>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    pub fn new(reader: R) -> Self {
+        Self::from_lines(LineReader::new(reader))
+    }
+
+    fn from_lines(lines: LineReader<R>) -> Self {
+        Self {
+            lines,
+            step: None,
+            collapse: false,
+            pending: None,
+        }
+    }
+
+    /// Sets whether adjacent records with equal values are merged
+    #[must_use]
+    pub fn with_collapse(mut self, collapse: bool) -> Self {
+        self.collapse = collapse;
+        self
+    }
+
+    /// Sets how malformed lines are handled
+    #[must_use]
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.lines.set_policy(policy);
+        self
+    }
+
+    /// Returns the number of malformed lines skipped so far
+    pub fn skipped(&self) -> usize {
+        self.lines.skipped()
+    }
+
+    /// Returns the number of malformed lines repaired so far
+    pub fn repaired(&self) -> usize {
+        self.lines.repaired()
+    }
+
+    /// Returns a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        self.lines.get_ref()
+    }
+
+    /// Consumes the reader returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.lines.into_inner()
+    }
+
+    /// Reads lines until the next data line and converts it to a record
+    fn next_record(&mut self) -> Result<Option<BedGraph<C, T>>, ParseError> {
+        while self.lines.read_line()? {
+            let line = self.lines.line().trim();
+            if is_skipped(line) {
+                continue;
+            }
+            let result = parse_line(line, &mut self.step).map(|record| (record, false));
+            match self.lines.resolve(result) {
+                Some(Ok(Some(record))) => return Ok(Some(record)),
+                Some(Ok(None)) | None => {}
+                Some(Err(e)) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Parses a declaration or data line, returning the record of a data line
+fn parse_line<C, T>(
+    line: &str,
+    declaration: &mut Option<Step<C, T>>,
+) -> Result<Option<BedGraph<C, T>>, ParseError>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    // A malformed declaration ends the previous one
+    if let Some(params) = line.strip_prefix("fixedStep") {
+        *declaration = None;
+        *declaration = Some(parse_fixed_step(params)?);
+        return Ok(None);
+    }
+    if let Some(params) = line.strip_prefix("variableStep") {
+        *declaration = None;
+        *declaration = Some(parse_variable_step(params)?);
+        return Ok(None);
+    }
+    match declaration {
+        Some(Step::Fixed {
+            chr,
+            start,
+            step,
+            span,
+        }) => {
+            let score = parse_value(line, "value")?;
+            let record = BedGraph::new(chr.clone(), *start, *start + *span, score);
+            *start = *start + *step;
+            Ok(Some(record))
+        }
+        Some(Step::Variable { chr, span }) => {
+            let mut tokens = line.split_whitespace();
+            let position = tokens
+                .next()
+                .ok_or(ParseError::MissingField { field: "position" })?;
+            let value = tokens
+                .next()
+                .ok_or(ParseError::MissingField { field: "value" })?;
+            let start = parse_position(position)?;
+            let score = parse_value(value, "value")?;
+            Ok(Some(BedGraph::new(
+                chr.clone(),
+                start,
+                start + *span,
+                score,
+            )))
+        }
+        None => Err(ParseError::MissingField {
+            field: "declaration",
+        }),
+    }
+}
+
+impl<C, T> WigReader<AutoDecoder<BufReader<File>>, C, T>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    /// Opens a file for reading records, decompressing gzip and BGZF
+    /// files on the fly
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseError> {
+        let reader = AutoDecoder::from_path(&path)?;
+        let lines = LineReader::new(reader).with_path(path.as_ref().to_path_buf());
+        Ok(Self::from_lines(lines))
+    }
+}
+
+impl<R, C, T> Iterator for WigReader<R, C, T>
+where
+    R: BufRead,
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    type Item = Result<BedGraph<C, T>, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.collapse {
+            return self.next_record().transpose();
+        }
+        loop {
+            let record = match self.next_record() {
+                Ok(Some(record)) => record,
+                Ok(None) => return self.pending.take().map(Ok),
+                Err(e) => return Some(Err(e)),
+            };
+            match self.pending.as_mut() {
+                #[allow(clippy::float_cmp)]
+                Some(pending)
+                    if pending.chr() == record.chr()
+                        && pending.end() == record.start()
+                        && pending.score() == record.score() =>
+                {
+                    pending.update_end(&record.end());
+                }
+                _ => {
+                    if let Some(pending) = self.pending.replace(record) {
+                        return Some(Ok(pending));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parses a 1-based WIG position into a 0-based start
+fn parse_position<T>(value: &str) -> Result<T, ParseError>
+where
+    T: ValueBounds + One + FromStr,
+{
+    let position: T = parse_value(value, "position")?;
+    if position < T::one() {
+        return Err(ParseError::InvalidField {
+            field: "position",
+            value: value.to_string(),
+        });
+    }
+    Ok(position - T::one())
+}
+
+/// Returns the value of a `key=value` parameter of a declaration line
+fn get_param<'a>(params: &'a str, key: &'static str) -> Option<&'a str> {
+    params
+        .split_whitespace()
+        .filter_map(|x| x.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+/// Parses the `span` parameter of a declaration line, defaulting to 1
+fn parse_span<T>(params: &str) -> Result<T, ParseError>
+where
+    T: ValueBounds + One + FromStr,
+{
+    get_param(params, "span").map_or(Ok(T::one()), |x| parse_value(x, "span"))
+}
+
+/// Parses the `chrom` parameter of a declaration line
+fn parse_chrom<C: FromStr>(params: &str) -> Result<C, ParseError> {
+    let chrom = get_param(params, "chrom").ok_or(ParseError::MissingField { field: "chrom" })?;
+    parse_value(chrom, "chrom")
+}
+
+fn parse_fixed_step<C, T>(params: &str) -> Result<Step<C, T>, ParseError>
+where
+    C: FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    let start = get_param(params, "start").ok_or(ParseError::MissingField { field: "start" })?;
+    let step = get_param(params, "step").ok_or(ParseError::MissingField { field: "step" })?;
+    Ok(Step::Fixed {
+        chr: parse_chrom(params)?,
+        start: parse_position(start)?,
+        step: parse_value(step, "step")?,
+        span: parse_span(params)?,
+    })
+}
+
+fn parse_variable_step<C, T>(params: &str) -> Result<Step<C, T>, ParseError>
+where
+    C: FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    Ok(Step::Variable {
+        chr: parse_chrom(params)?,
+        span: parse_span(params)?,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+
+    const WIG: &str = "browser position chr1:1-100\n\
+        track type=wiggle_0 name=test\n\
+        # a comment\n\
+        variableStep chrom=chr1\n\
+        1 1.0\n\
+        2 1.0\n\
+        10 2.0\n\
+        fixedStep chrom=chr1 start=11 step=5 span=5\n\
+        2.0\n\
+        2.0\n\
+        3.0\n\
+        fixedStep chrom=chr2 start=1 step=10\n\
+        3.0\n\
+        3.0\n";
+
+    fn read(text: &str, collapse: bool) -> Vec<BedGraph<String, u32>> {
+        WigReader::new(text.as_bytes())
+            .with_collapse(collapse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    fn coords(records: &[BedGraph<String, u32>]) -> Vec<(&str, u32, u32, f64)> {
+        records
+            .iter()
+            .map(|x| (x.chr().as_str(), x.start(), x.end(), x.score()))
+            .collect()
+    }
+
+    #[test]
+    fn read_wig() {
+        let records = read(WIG, false);
+        assert_eq!(
+            coords(&records),
+            vec![
+                ("chr1", 0, 1, 1.0),
+                ("chr1", 1, 2, 1.0),
+                ("chr1", 9, 10, 2.0),
+                ("chr1", 10, 15, 2.0),
+                ("chr1", 15, 20, 2.0),
+                ("chr1", 20, 25, 3.0),
+                ("chr2", 0, 1, 3.0),
+                ("chr2", 10, 11, 3.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_wig_collapsed() {
+        let records = read(WIG, true);
+        assert_eq!(
+            coords(&records),
+            vec![
+                ("chr1", 0, 2, 1.0),
+                ("chr1", 9, 20, 2.0),
+                ("chr1", 20, 25, 3.0),
+                ("chr2", 0, 1, 3.0),
+                ("chr2", 10, 11, 3.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_wig_missing_declaration() {
+        let mut reader: WigReader<_, String, u32> = WigReader::new("1.0\n".as_bytes());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(1));
+        match err.inner() {
+            ParseError::MissingField { field } => assert_eq!(*field, "declaration"),
+            _ => panic!("Expected missing declaration"),
+        }
+    }
+
+    #[test]
+    fn read_wig_invalid() {
+        let texts = [
+            "fixedStep chrom=chr1 step=10\n1.0\n",
+            "fixedStep start=1 step=10\n1.0\n",
+            "fixedStep chrom=chr1 start=0 step=10\n1.0\n",
+            "fixedStep chrom=chr1 start=1 step=10 span=x\n1.0\n",
+            "variableStep chrom=chr1\n0 1.0\n",
+            "variableStep chrom=chr1\n10\n",
+            "variableStep chrom=chr1\n10 abc\n",
+        ];
+        for text in texts {
+            let mut reader: WigReader<_, String, u32> = WigReader::new(text.as_bytes());
+            assert!(reader.next().unwrap().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn read_wig_error_policy() {
+        let text = "variableStep chrom=chr1\n\
+            1 1.0\n\
+            2 abc\n\
+            fixedStep chrom=chr1 start=0 step=10\n\
+            2.0\n\
+            fixedStep chrom=chr2 start=1 step=10\n\
+            3.0\n";
+        for policy in [ErrorPolicy::Skip, ErrorPolicy::Repair] {
+            let mut reader: WigReader<_, String, u32> =
+                WigReader::new(text.as_bytes()).error_policy(policy);
+            let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(
+                coords(&records),
+                vec![("chr1", 0, 1, 1.0), ("chr2", 0, 1, 3.0)]
+            );
+            assert_eq!(reader.skipped(), 3);
+            assert_eq!(reader.repaired(), 0);
+        }
+
+        let mut reader: WigReader<_, String, u32> = WigReader::new(text.as_bytes());
+        assert!(reader.next().unwrap().is_ok());
+        assert_eq!(reader.next().unwrap().unwrap_err().line(), Some(3));
+    }
+}
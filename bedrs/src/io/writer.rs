@@ -0,0 +1,382 @@
+use super::bgzf::BgzfWriter;
+use crate::{
+    traits::{ChromBounds, ToFields, ValueBounds},
+    ChromDict,
+};
+use std::{
+    fmt::{Display, Write as FmtWrite},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// A writer of tab-delimited interval records.
+///
+/// Wraps any [`Write`] and writes each record implementing [`ToFields`]
+/// as a single line.
+/// Missing scores and unknown strands are written as `.` and block lists
+/// are written comma-terminated, so that the output can be read back with
+/// the [`BedReader`](crate::io::BedReader).
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{io::BedWriter, Bed3, IntervalContainer};
+///
+/// let set = IntervalContainer::from_unsorted(vec![
+///     Bed3::new("chr1", 10, 20),
+///     Bed3::new("chr1", 15, 30),
+///     Bed3::new("chr2", 10, 20),
+/// ]);
+/// let merged = set.merge().unwrap();
+///
+/// let mut writer = BedWriter::new(Vec::new());
+/// writer.write_records(merged.records()).unwrap();
+///
+/// let text = String::from_utf8(writer.into_inner()).unwrap();
+/// assert_eq!(text, "chr1\t10\t30\nchr2\t10\t20\n");
+/// ```
+pub struct BedWriter<W: Write> {
+    writer: W,
+    buffer: String,
+}
+
+impl<W: Write> BedWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: String::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the writer returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes a single record as a line
+    pub fn write_record<I, C, T>(&mut self, record: &I) -> io::Result<()>
+    where
+        I: ToFields<C, T>,
+        C: ChromBounds + Display,
+        T: ValueBounds,
+    {
+        self.buffer.clear();
+        write!(self.buffer, "{}", record.chr()).map_err(io::Error::other)?;
+        record
+            .write_fields(&mut self.buffer)
+            .map_err(io::Error::other)?;
+        self.buffer.push('\n');
+        self.writer.write_all(self.buffer.as_bytes())
+    }
+
+    /// Writes every record of an iterator, each as a line
+    pub fn write_records<It, I, C, T>(&mut self, records: It) -> io::Result<()>
+    where
+        It: IntoIterator<Item = I>,
+        I: ToFields<C, T>,
+        C: ChromBounds + Display,
+        T: ValueBounds,
+    {
+        for record in records {
+            self.write_record(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single record whose chromosome is an interned id, restoring
+    /// its name from the provided [`ChromDict`].
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the id is
+    /// not present in the dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{io::BedWriter, Bed3, ChromDict};
+    ///
+    /// let mut dict = ChromDict::new();
+    /// let record = Bed3::new(dict.get_or_insert("chr1"), 10, 20);
+    ///
+    /// let mut writer = BedWriter::new(Vec::new());
+    /// writer.write_record_with_dict(&record, &dict).unwrap();
+    ///
+    /// let text = String::from_utf8(writer.into_inner()).unwrap();
+    /// assert_eq!(text, "chr1\t10\t20\n");
+    /// ```
+    pub fn write_record_with_dict<I, T>(&mut self, record: &I, dict: &ChromDict) -> io::Result<()>
+    where
+        I: ToFields<u32, T>,
+        T: ValueBounds,
+    {
+        let Some(name) = dict.name(*record.chr()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Chromosome id {} is not present in the dictionary",
+                    record.chr()
+                ),
+            ));
+        };
+        self.buffer.clear();
+        self.buffer.push_str(name);
+        record
+            .write_fields(&mut self.buffer)
+            .map_err(io::Error::other)?;
+        self.buffer.push('\n');
+        self.writer.write_all(self.buffer.as_bytes())
+    }
+
+    /// Writes every record of an iterator, each as a line, restoring the
+    /// chromosome names from the provided [`ChromDict`]
+    pub fn write_records_with_dict<It, I, T>(
+        &mut self,
+        records: It,
+        dict: &ChromDict,
+    ) -> io::Result<()>
+    where
+        It: IntoIterator<Item = I>,
+        I: ToFields<u32, T>,
+        T: ValueBounds,
+    {
+        for record in records {
+            self.write_record_with_dict(&record, dict)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl BedWriter<BufWriter<File>> {
+    /// Creates a file for writing records
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl BedWriter<BgzfWriter<File>> {
+    /// Creates a BGZF compressed file for writing records.
+    ///
+    /// The output can be indexed with a [`TabixIndex`](crate::io::TabixIndex)
+    /// once the writer is finished.
+    pub fn from_path_bgzf<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BgzfWriter::from_path(path)?))
+    }
+}
+
+impl<W: Write> BedWriter<BgzfWriter<W>> {
+    /// Finishes the compressed stream returning the underlying writer
+    pub fn finish(self) -> io::Result<W> {
+        self.writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        io::{AutoDecoder, BedReader, InternedBedReader},
+        Bed12, Bed3, Bed4, Bed6, BedGraph, Coordinates, Gtf, IntervalContainer, MetaInterval,
+        Score, Strand,
+    };
+
+    fn write_to_string<I, C, T>(records: &[I]) -> String
+    where
+        I: ToFields<C, T>,
+        for<'a> &'a I: ToFields<C, T>,
+        C: ChromBounds + Display,
+        T: ValueBounds,
+    {
+        let mut writer = BedWriter::new(Vec::new());
+        writer.write_records(records).unwrap();
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn write_bed3() {
+        let records = vec![Bed3::new("chr1", 10, 20), Bed3::new("chr2", 30, 40)];
+        let text = write_to_string(&records);
+        assert_eq!(text, "chr1\t10\t20\nchr2\t30\t40\n");
+    }
+
+    #[test]
+    fn write_bed4() {
+        let records = vec![Bed4::new("chr1", 10, 20, "a")];
+        let text = write_to_string(&records);
+        assert_eq!(text, "chr1\t10\t20\ta\n");
+    }
+
+    #[test]
+    fn write_bed6() {
+        let records = vec![
+            Bed6::new("chr1", 10, 20, "a", Score(None), Strand::Unknown),
+            Bed6::new("chr1", 30, 40, "b", Score::new(5.0), Strand::Forward),
+        ];
+        let text = write_to_string(&records);
+        assert_eq!(text, "chr1\t10\t20\ta\t.\t.\nchr1\t30\t40\tb\t5\t+\n");
+    }
+
+    #[test]
+    fn write_bedgraph() {
+        let records = vec![BedGraph::new("chr1", 10, 20, 1.5)];
+        let text = write_to_string(&records);
+        assert_eq!(text, "chr1\t10\t20\t1.5\n");
+    }
+
+    #[test]
+    fn write_gtf() {
+        let records = vec![Gtf::new(
+            "chr1",
+            "src",
+            "gene",
+            0,
+            100,
+            Score(None),
+            Strand::Forward,
+            0.into(),
+            "gene_id \"g1\";",
+        )];
+        let text = write_to_string(&records);
+        assert_eq!(text, "chr1\tsrc\tgene\t1\t100\t.\t+\t0\tgene_id \"g1\";\n");
+    }
+
+    #[test]
+    fn write_meta_interval() {
+        let records = vec![MetaInterval::new("chr1", 10, 20, 42)];
+        let text = write_to_string(&records);
+        assert_eq!(text, "chr1\t10\t20\t42\n");
+    }
+
+    #[test]
+    fn write_owned_iterator() {
+        let set = IntervalContainer::from_unsorted(vec![
+            Bed3::new("chr1", 10, 20),
+            Bed3::new("chr1", 30, 40),
+        ]);
+        let mut writer = BedWriter::new(Vec::new());
+        writer.write_records(set.complement().unwrap()).unwrap();
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(text, "chr1\t20\t30\n");
+    }
+
+    #[test]
+    fn round_trip_bed6() {
+        let records = vec![
+            Bed6::new(
+                "chr1".to_string(),
+                10,
+                20,
+                "a".to_string(),
+                Score(None),
+                Strand::Unknown,
+            ),
+            Bed6::new(
+                "chr1".to_string(),
+                30,
+                40,
+                "b".to_string(),
+                Score::new(2.5),
+                Strand::Reverse,
+            ),
+        ];
+        let text = write_to_string(&records);
+        let reader: BedReader<_, Bed6<String, u64, String>, _, _> = BedReader::new(text.as_bytes());
+        let observed = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(observed.len(), records.len());
+        for (obs, exp) in observed.iter().zip(records.iter()) {
+            assert!(obs.eq(exp));
+            assert_eq!(obs.name(), exp.name());
+            assert_eq!(obs.score(), exp.score());
+        }
+    }
+
+    #[test]
+    fn round_trip_bed12() {
+        type Record = Bed12<String, u64, String, u64, u64, String, Vec<u64>, Vec<u64>>;
+        let records: Vec<Record> = vec![Bed12::new(
+            "chr1".to_string(),
+            10,
+            100,
+            "tx".to_string(),
+            Score::new(1.0),
+            Strand::Forward,
+            20,
+            90,
+            "0,0,0".to_string(),
+            2,
+            vec![30, 40],
+            vec![0, 50],
+        )];
+        let text = write_to_string(&records);
+        let reader: BedReader<_, Record, _, _> = BedReader::new(text.as_bytes());
+        let observed = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(observed[0].block_sizes(), records[0].block_sizes());
+        assert_eq!(observed[0].block_starts(), records[0].block_starts());
+        assert_eq!(write_to_string(&observed), text);
+    }
+
+    #[test]
+    fn round_trip_interned() {
+        let text = "chr1\t10\t20\tA\t0\t+\nchr2\t30\t40\tB\t.\t-\nchr1\t50\t60\tC\t1\t.\n";
+        let mut reader: InternedBedReader<_, Bed6<u32, u64, String>, _> =
+            InternedBedReader::new(text.as_bytes());
+        let records = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        let dict = reader.into_chrom_dict();
+
+        let mut writer = BedWriter::new(Vec::new());
+        writer.write_records_with_dict(&records, &dict).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), text);
+    }
+
+    #[test]
+    fn write_interned_unknown_id() {
+        let dict = ChromDict::from_names(["chr1"]);
+        let mut writer = BedWriter::new(Vec::new());
+        assert!(writer
+            .write_record_with_dict(&Bed3::new(0, 10, 20), &dict)
+            .is_ok());
+        let err = writer
+            .write_record_with_dict(&Bed3::new(1, 10, 20), &dict)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn round_trip_bgzf() {
+        let records = vec![Bed3::new("chr1", 10, 20), Bed3::new("chr2", 30, 40)];
+        let mut writer = BedWriter::new(BgzfWriter::new(Vec::new()));
+        writer.write_records(&records).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let decoder = AutoDecoder::new(bytes.as_slice()).unwrap();
+        let reader: BedReader<_, Bed3<String, u32>, _, _> = BedReader::new(decoder);
+        let observed = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(write_to_string(&observed), "chr1\t10\t20\nchr2\t30\t40\n");
+    }
+
+    #[test]
+    fn round_trip_bgzf_path() {
+        let path = std::env::temp_dir().join(format!("bedrs-writer-{}.bed.gz", std::process::id()));
+        let mut writer = BedWriter::from_path_bgzf(&path).unwrap();
+        writer
+            .write_records([Bed3::new("chr1", 10, 20), Bed3::new("chr1", 30, 40)])
+            .unwrap();
+        writer.finish().unwrap();
+
+        let reader: BedReader<_, Bed3<String, u32>, _, _> = BedReader::from_path(&path).unwrap();
+        let observed = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(observed.len(), 2);
+        assert_eq!(observed[1].start(), 30);
+    }
+}
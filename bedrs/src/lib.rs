@@ -78,9 +78,17 @@
 //! - [`Bed6`]
 //! - [`Bed12`]
 //! - [`BedGraph`]
+//! - [`BedPe`]
+//! - [`BroadPeak`]
+//! - [`Gff3`]
 //! - [`Gtf`]
 //! - [`MetaInterval`]
+//! - [`MultiSegment`]
+//! - [`NarrowPeak`]
+//! - [`Psl`]
+//! - [`Sam`]
 //! - [`StrandedBed3`]
+//! - [`UnionBedGraph`]
 //!
 //! These are pre-built interval types and can be used in many usecases:
 //!
@@ -102,6 +110,11 @@
 //! assert_eq!(c.end(), 40);
 //! ```
 //!
+//! All interval types use 0-based half-open coordinates.
+//! Formats using 1-based closed coordinates (such as GTF and GFF3) are
+//! converted when read and written, and 1-based values can be handled
+//! explicitly with the [`OneBased`] wrapper.
+//!
 //! ## Interval Operations
 //!
 //! - [`Overlap`]
@@ -134,13 +147,58 @@
 //! - Bound
 //! - Closest
 //! - Complement
+//! - Coverage
 //! - Find
+//! - Genome Coverage
 //! - Internal
+//! - Map
 //! - Merge
+//! - Multi Intersect
 //! - Sample
 //! - Intersect
 //! - Segment
 //! - Subtract
+//! - Union `BedGraph`
+//!
+//! ## Reading and Writing Records
+//!
+//! Records implementing [`FromFields`] can be streamed from any tab-delimited
+//! source with the [`io::BedReader`], and records implementing [`ToFields`]
+//! can be written back out with the [`io::BedWriter`]:
+//!
+//! ``` rust
+//! use bedrs::prelude::*;
+//!
+//! let text = "chr1\t20\t30\nchr1\t25\t40\n";
+//! let reader = BedReader::new(text.as_bytes());
+//! let set: IntervalContainer<Bed3<String, u32>, _, _> = reader
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! assert_eq!(set.len(), 2);
+//!
+//! let mut writer = BedWriter::new(Vec::new());
+//! writer.write_records(set.records()).unwrap();
+//! assert_eq!(writer.into_inner(), text.as_bytes());
+//! ```
+//!
+//! Malformed lines are reported as a [`ParseError`] carrying their line
+//! number (and path, for readers opened with `from_path`), or can be
+//! skipped or repaired by setting an [`ErrorPolicy`] on the reader.
+//!
+//! Files opened with `from_path` may be gzip or BGZF compressed and are
+//! decompressed on the fly (see [`io::AutoDecoder`]), and the writer can
+//! produce BGZF output suitable for a [`io::TabixIndex`] with
+//! [`io::BedWriter::from_path_bgzf`].
+//! Binary bigWig and bigBed files are read with the [`io::BigWigReader`]
+//! and [`io::BigBedReader`], which use their R-tree index for region queries.
+//! Sorted containers can be saved with [`IntervalContainer::save_mapped`]
+//! and searched straight from a memory-mapped [`io::MappedContainer`].
+//! PSL alignments are streamed with the [`io::PslReader`] and expand into
+//! their aligned blocks, and the [`io::SamReader`] turns SAM alignments
+//! into their reference footprints or CIGAR blocks.
+//! With the `arrow` feature containers of the BED record types convert to
+//! and from Arrow record batches, and the `parquet` feature adds Parquet
+//! reading and writing on top.
 //!
 //! ## Other Work
 //!
@@ -160,14 +218,19 @@ pub mod traits;
 /// Types used within the library
 pub mod types;
 
+/// Reading and writing of interval records
+pub mod io;
+
 /// Prelude for the library
 pub mod prelude;
 
 pub use traits::{
-    Coordinates, Distance, Intersect, Overlap, Segment, StrandedOverlap, Subtract,
-    UnstrandedOverlap,
+    AggregateError, Coordinates, Distance, FromFields, GenomeError, Intersect, MappedError,
+    Overlap, ParseError, Segment, StrandedOverlap, Subtract, ToFields, ToRegion, UnstrandedOverlap,
 };
 pub use types::{
-    BaseInterval, Bed12, Bed3, Bed4, Bed6, BedGraph, Frame, Gtf, IntersectIter, IntervalContainer,
-    IntervalIterOwned, IntervalIterRef, MergeIter, MetaInterval, Score, Strand, StrandedBed3,
+    Attributes, BaseInterval, Bed12, Bed3, Bed4, Bed6, BedGraph, BedPe, BedPeContainer, BroadPeak,
+    ChromDict, Coverage, ErrorPolicy, Frame, Genome, Gff3, Gtf, IntersectIter, IntervalContainer,
+    IntervalIterOwned, IntervalIterRef, MergeIter, MetaInterval, MultiSegment, NarrowPeak,
+    OneBased, PairMethod, Psl, Sam, Score, Strand, StrandedBed3, UnionBedGraph, UnionBedGraphIter,
 };
@@ -1,3 +1,4 @@
+pub use super::io::*;
 pub use super::traits::*;
 pub use super::types::*;
 pub use bedrs_derive::Coordinates;
@@ -1,3 +1,5 @@
+use crate::types::Aggregator;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -26,11 +28,159 @@ pub enum SetError {
 
     #[error("Cannot accept a strand input that is unknown")]
     CannotAcceptUnknownStrand,
+
+    #[error("The interval set spans multiple chromosomes.")]
+    MultipleChromosomes,
+}
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Missing required field: {field}")]
+    MissingField { field: &'static str },
+
+    #[error("Unable to parse field {field} from: {value}")]
+    InvalidField { field: &'static str, value: String },
+
+    #[error("Invalid region {region}: {reason}")]
+    InvalidRegion {
+        region: String,
+        reason: &'static str,
+    },
+
+    #[error("Interval {end_field} {end} is before its {start_field} {start}")]
+    InvalidInterval {
+        start_field: &'static str,
+        start: String,
+        end_field: &'static str,
+        end: String,
+    },
+
+    #[error(
+        "{}line {line}: {source}",
+        .path.as_ref().map(|p| format!("{}: ", p.display())).unwrap_or_default()
+    )]
+    Line {
+        path: Option<PathBuf>,
+        line: usize,
+        source: Box<ParseError>,
+    },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl ParseError {
+    /// Returns the 1-based number of the line the error occurred on, if
+    /// it was raised by a reader
+    #[must_use]
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseError::Line { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// Returns the path of the file the error occurred in, if it was
+    /// raised by a reader opened from a path
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            ParseError::Line { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error without its location
+    #[must_use]
+    pub fn inner(&self) -> &ParseError {
+        match self {
+            ParseError::Line { source, .. } => source.inner(),
+            _ => self,
+        }
+    }
+}
+
+/// Errors raised when building a [`Genome`](crate::Genome) or validating
+/// intervals against it.
+#[derive(Error, Debug)]
+pub enum GenomeError {
+    #[error("Chromosome {chr} is defined more than once")]
+    DuplicateChrom { chr: String },
+
+    #[error("Chromosome {chr} is not present in the genome")]
+    UnknownChrom { chr: String },
+
+    #[error("Interval on {chr} ends at {end} past the chromosome size {size}")]
+    OutOfBounds {
+        chr: String,
+        end: String,
+        size: String,
+    },
+
+    #[error(transparent)]
+    Set(#[from] SetError),
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AggregateError {
+    #[error("Aggregator {aggregator:?} requires numeric values")]
+    NumericAggregator { aggregator: Aggregator },
+
+    #[error(transparent)]
+    Set(#[from] SetError),
+}
+
+#[derive(Error, Debug)]
+pub enum MappedError {
+    #[error("Not a bedrs interval file")]
+    InvalidMagic,
+
+    #[error("Unsupported interval file version {version}")]
+    UnsupportedVersion { version: u32 },
+
+    #[error("Malformed interval file: {reason}")]
+    Malformed { reason: &'static str },
+
+    #[error("Value {value} cannot be stored in an interval file")]
+    UnrepresentableValue { value: String },
+
+    #[error(transparent)]
+    Set(#[from] SetError),
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(feature = "arrow")]
+#[derive(Error, Debug)]
+pub enum ColumnarError {
+    #[error("Missing column: {column}")]
+    MissingColumn { column: &'static str },
+
+    #[error("Column {column} holds a null value")]
+    NullValue { column: &'static str },
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error(transparent)]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    #[cfg(feature = "parquet")]
+    #[error(transparent)]
+    Parquet(#[from] parquet::errors::ParquetError),
 }
 
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::{Bed3, Genome};
 
     #[test]
     fn test_set_error() {
@@ -59,6 +209,134 @@ mod testing {
             format!("{err}"),
             "Cannot accept a strand input that is unknown"
         );
+        let err = SetError::MultipleChromosomes;
+        assert_eq!(
+            format!("{err}"),
+            "The interval set spans multiple chromosomes."
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = ParseError::MissingField { field: "end" };
+        assert_eq!(format!("{err}"), "Missing required field: end");
+        let err = ParseError::InvalidField {
+            field: "start",
+            value: "abc".to_string(),
+        };
+        assert_eq!(format!("{err}"), "Unable to parse field start from: abc");
+        let err = ParseError::InvalidRegion {
+            region: "chr1:0-10".to_string(),
+            reason: "positions are 1-based",
+        };
+        assert_eq!(
+            format!("{err}"),
+            "Invalid region chr1:0-10: positions are 1-based"
+        );
+        let err = ParseError::InvalidInterval {
+            start_field: "start2",
+            start: "20".to_string(),
+            end_field: "end2",
+            end: "10".to_string(),
+        };
+        assert_eq!(format!("{err}"), "Interval end2 10 is before its start2 20");
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = ParseError::Line {
+            path: None,
+            line: 3,
+            source: Box::new(ParseError::InvalidField {
+                field: "strand",
+                value: "?".to_string(),
+            }),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "line 3: Unable to parse field strand from: ?"
+        );
+        assert_eq!(err.line(), Some(3));
+        assert!(err.path().is_none());
+        assert!(matches!(
+            err.inner(),
+            ParseError::InvalidField {
+                field: "strand",
+                ..
+            }
+        ));
+
+        let err = ParseError::Line {
+            path: Some(PathBuf::from("peaks.bed")),
+            line: 12,
+            source: Box::new(ParseError::MissingField { field: "end" }),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "peaks.bed: line 12: Missing required field: end"
+        );
+        assert_eq!(err.path(), Some(Path::new("peaks.bed")));
+        let err = ParseError::MissingField { field: "end" };
+        assert!(err.line().is_none());
+        assert!(matches!(err.inner(), ParseError::MissingField { .. }));
+    }
+
+    #[test]
+    fn test_genome_error() {
+        let mut genome = Genome::from_sizes(vec![("chr1".to_string(), 100_u32)]).unwrap();
+        let err = genome.insert("chr1".to_string(), 50).unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "Chromosome chr1 is defined more than once"
+        );
+        let err = genome
+            .validate_interval(&Bed3::new("chrZ".to_string(), 0, 10))
+            .unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "Chromosome chrZ is not present in the genome"
+        );
+        let err = genome
+            .validate_interval(&Bed3::new("chr1".to_string(), 90, 120))
+            .unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "Interval on chr1 ends at 120 past the chromosome size 100"
+        );
+        let genome = Genome::from_sizes(vec![(1, 100_u32)]).unwrap();
+        let err = genome.validate_interval(&Bed3::new(2, 0, 10)).unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "Chromosome 2 is not present in the genome"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_error() {
+        let err = AggregateError::NumericAggregator {
+            aggregator: Aggregator::Median,
+        };
+        assert_eq!(
+            format!("{err}"),
+            "Aggregator Median requires numeric values"
+        );
+        let err: AggregateError = SetError::UnsortedSet.into();
+        assert_eq!(format!("{err}"), "The interval set is unsorted.");
+    }
+
+    #[test]
+    fn test_mapped_error() {
+        let err = MappedError::UnsupportedVersion { version: 7 };
+        assert_eq!(format!("{err}"), "Unsupported interval file version 7");
+        let err = MappedError::Malformed {
+            reason: "truncated header",
+        };
+        assert_eq!(
+            format!("{err}"),
+            "Malformed interval file: truncated header"
+        );
+        let err = MappedError::from(SetError::UnsortedSet);
+        assert_eq!(format!("{err}"), "The interval set is unsorted.");
     }
 
     #[test]
@@ -0,0 +1,189 @@
+use super::{ChromBounds, Coordinates, ParseError, ValueBounds};
+use std::{fmt, str::FromStr};
+
+/// The index and name of the start column and of the end column of an
+/// interval of a record (see [`FromFields::INTERVAL_FIELDS`])
+pub type IntervalFields = ((usize, &'static str), (usize, &'static str));
+
+/// A trait for records that can be built from the tab-delimited fields of a line.
+///
+/// The chromosome is parsed by the caller and provided separately so that
+/// readers can decide how chromosome names are represented.
+/// The `fields` slice still holds every column of the line (including the
+/// chromosome) so that implementations can index columns by their position
+/// in the file format.
+///
+/// # Examples
+/// ```
+/// use bedrs::{Bed3, Coordinates, FromFields};
+///
+/// let fields = ["chr1", "10", "20"];
+/// let record: Bed3<String, u64> = Bed3::from_fields("chr1".to_string(), &fields).unwrap();
+/// assert_eq!(record.chr(), "chr1");
+/// assert_eq!(record.start(), 10);
+/// assert_eq!(record.end(), 20);
+/// ```
+pub trait FromFields<C, T>
+where
+    Self: Sized,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// The index and name of the start and end columns of every interval
+    /// of the record, by default the second and third columns of BED.
+    ///
+    /// Readers reject lines whose start is past their end, naming both
+    /// columns in the error, or swap them when using
+    /// [`ErrorPolicy::Repair`](crate::ErrorPolicy::Repair).
+    const INTERVAL_FIELDS: &'static [IntervalFields] = &[((1, "start"), (2, "end"))];
+
+    /// The index of the strand column, if the record has one.
+    ///
+    /// Readers using [`ErrorPolicy::Repair`](crate::ErrorPolicy::Repair)
+    /// read an unrecognized strand in this column as unknown.
+    const STRAND_FIELD: Option<usize> = None;
+
+    /// Builds a record from its chromosome and the fields of its line.
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError>;
+}
+
+/// A trait for records that can be written as the tab-delimited fields of a line.
+///
+/// Implementations write every field *following* the chromosome, each
+/// preceded by a tab, so that writers can decide how chromosome names are
+/// represented.
+///
+/// # Examples
+/// ```
+/// use bedrs::{Bed3, ToFields};
+///
+/// let record = Bed3::new("chr1", 10, 20);
+/// let mut fields = String::new();
+/// record.write_fields(&mut fields).unwrap();
+/// assert_eq!(fields, "\t10\t20");
+/// ```
+pub trait ToFields<C, T>: Coordinates<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Writes the fields following the chromosome, each preceded by a tab.
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result;
+}
+
+impl<I, C, T> ToFields<C, T> for &I
+where
+    I: ToFields<C, T>,
+    for<'a> &'a I: Coordinates<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        (*self).write_fields(wtr)
+    }
+}
+
+/// Writes a comma-terminated list field as used by BED12 block lists.
+pub(crate) fn write_list<W: fmt::Write, V: fmt::Display>(wtr: &mut W, values: &[V]) -> fmt::Result {
+    wtr.write_char('\t')?;
+    for value in values {
+        write!(wtr, "{value},")?;
+    }
+    Ok(())
+}
+
+/// Returns the raw text of a field or an error if it is missing.
+pub(crate) fn get_field<'a>(
+    fields: &[&'a str],
+    idx: usize,
+    name: &'static str,
+) -> Result<&'a str, ParseError> {
+    fields
+        .get(idx)
+        .copied()
+        .ok_or(ParseError::MissingField { field: name })
+}
+
+/// Parses a single value from a field.
+pub(crate) fn parse_value<V: FromStr>(value: &str, name: &'static str) -> Result<V, ParseError> {
+    value.parse().map_err(|_| ParseError::InvalidField {
+        field: name,
+        value: value.to_string(),
+    })
+}
+
+/// Parses the field at the provided index.
+pub(crate) fn parse_field<V: FromStr>(
+    fields: &[&str],
+    idx: usize,
+    name: &'static str,
+) -> Result<V, ParseError> {
+    parse_value(get_field(fields, idx, name)?, name)
+}
+
+/// Parses the field at the provided index, falling back to the default
+/// value if the field is absent.
+pub(crate) fn parse_optional_field<V: FromStr + Default>(
+    fields: &[&str],
+    idx: usize,
+    name: &'static str,
+) -> Result<V, ParseError> {
+    match fields.get(idx) {
+        Some(value) => parse_value(value, name),
+        None => Ok(V::default()),
+    }
+}
+
+/// Parses a comma-separated list field, ignoring a trailing comma.
+pub(crate) fn parse_list<V: FromStr>(
+    fields: &[&str],
+    idx: usize,
+    name: &'static str,
+) -> Result<Vec<V>, ParseError> {
+    get_field(fields, idx, name)?
+        .split(',')
+        .filter(|x| !x.is_empty())
+        .map(|x| parse_value(x, name))
+        .collect()
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_parse_field() {
+        let fields = ["chr1", "10", "abc"];
+        let val: u32 = parse_field(&fields, 1, "start").unwrap();
+        assert_eq!(val, 10);
+        assert!(parse_field::<u32>(&fields, 2, "end").is_err());
+        assert!(parse_field::<u32>(&fields, 3, "name").is_err());
+    }
+
+    #[test]
+    fn test_parse_optional_field() {
+        let fields = ["chr1", "10", "abc"];
+        let val: u32 = parse_optional_field(&fields, 1, "start").unwrap();
+        assert_eq!(val, 10);
+        let val: u32 = parse_optional_field(&fields, 3, "score").unwrap();
+        assert_eq!(val, 0);
+        assert!(parse_optional_field::<u32>(&fields, 2, "end").is_err());
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let fields = ["10,20,30,", "10,20", "10,a"];
+        let val: Vec<u32> = parse_list(&fields, 0, "block_sizes").unwrap();
+        assert_eq!(val, vec![10, 20, 30]);
+        let val: Vec<u32> = parse_list(&fields, 1, "block_sizes").unwrap();
+        assert_eq!(val, vec![10, 20]);
+        assert!(parse_list::<u32>(&fields, 2, "block_sizes").is_err());
+    }
+
+    #[test]
+    fn test_write_list() {
+        let mut buf = String::new();
+        write_list(&mut buf, &[10, 20, 30]).unwrap();
+        assert_eq!(buf, "\t10,20,30,");
+    }
+}
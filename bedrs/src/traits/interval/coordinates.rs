@@ -15,6 +15,11 @@ use super::{
 /// the fields {chr, start, end} and those fields meet the
 /// minimum required bounds of `ChromBounds` and `ValueBounds`.
 ///
+/// Coordinates are always 0-based and half-open: `start` is the first
+/// base of the interval and `end` is one past its last base.
+/// Values in the 1-based closed convention should be wrapped in a
+/// [`OneBased`](crate::OneBased) and converted before use.
+///
 /// # Examples
 /// ```
 /// use bedrs::prelude::*;
@@ -343,7 +348,7 @@ where
     ///
     /// iv.extend(&5, None);
     /// assert!(iv.eq(&Bed3::new(1, 5, 25)));
-
+    ///
     /// iv.extend(&5, Some(27));
     /// assert!(iv.eq(&Bed3::new(1, 0, 27)));
     /// ```
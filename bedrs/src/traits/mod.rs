@@ -2,12 +2,18 @@ use num_traits::{Bounded, FromPrimitive, NumOps, ToPrimitive, Zero};
 use std::fmt::Debug;
 
 pub mod errors;
+pub mod fields;
 pub mod interval;
-pub use errors::SetError;
+pub mod region;
+#[cfg(feature = "arrow")]
+pub use errors::ColumnarError;
+pub use errors::{AggregateError, GenomeError, MappedError, ParseError, SetError};
+pub use fields::{FromFields, IntervalFields, ToFields};
 pub use interval::{
     Coordinates, Distance, Intersect, Overlap, Segment, StrandedOverlap, Subtract,
     UnstrandedOverlap,
 };
+pub use region::ToRegion;
 
 /// Generic bounds for types to be used for [Coordinates] in the context
 /// of Chromosome coordinates
@@ -0,0 +1,294 @@
+use crate::{
+    traits::{fields::parse_value, ChromBounds, Coordinates, ParseError, ValueBounds},
+    Strand,
+};
+use num_traits::One;
+use std::{
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+/// Parses a samtools / UCSC style region string into its chromosome,
+/// half-open coordinates, and strand.
+///
+/// Accepted forms are `chr`, `chr:start`, and `chr:start-end`, each
+/// optionally followed by a strand suffix `(+)`, `(-)`, or `(.)`.
+/// The range follows the last colon, so chromosome names containing
+/// colons (such as `HLA-A*01:01`) must be wrapped in braces as with
+/// samtools: `{HLA-A*01:01}` or `{HLA-A*01:01}:1-100`.
+/// Positions are 1-based and inclusive and may contain thousands
+/// separators (`1,000`).
+/// A missing end extends the region to the end of the chromosome, which
+/// is represented by the maximum value of `T`.
+pub(crate) fn parse_region<C, T>(text: &str) -> Result<(C, T, T, Strand), ParseError>
+where
+    C: FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    let invalid = |reason| ParseError::InvalidRegion {
+        region: text.to_string(),
+        reason,
+    };
+    let mut body = text.trim();
+
+    let mut strand = Strand::Unknown;
+    if let Some(prefix) = body.strip_suffix(')') {
+        let (prefix, suffix) = prefix
+            .rsplit_once('(')
+            .ok_or_else(|| invalid("unbalanced strand suffix"))?;
+        strand = suffix.parse().map_err(|_| invalid("invalid strand"))?;
+        body = prefix.trim_end();
+    }
+
+    let (chr, range) = if let Some(rest) = body.strip_prefix('{') {
+        let (chr, rest) = rest
+            .split_once('}')
+            .ok_or_else(|| invalid("unbalanced chromosome braces"))?;
+        if rest.is_empty() {
+            (chr, None)
+        } else {
+            let range = rest
+                .strip_prefix(':')
+                .ok_or_else(|| invalid("missing range separator"))?;
+            (chr, Some(range))
+        }
+    } else {
+        match body.rsplit_once(':') {
+            Some((chr, range)) => (chr, Some(range)),
+            None => (body, None),
+        }
+    };
+    if chr.is_empty() {
+        return Err(invalid("missing chromosome"));
+    }
+    let chr = parse_value(chr, "chr")?;
+
+    let Some(range) = range else {
+        return Ok((chr, T::zero(), T::max_value(), strand));
+    };
+    let range = range.replace(',', "");
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (range.as_str(), None),
+    };
+    let start: T = start.parse().map_err(|_| invalid("invalid start"))?;
+    if start < T::one() {
+        return Err(invalid("positions are 1-based"));
+    }
+    let end = match end {
+        Some(end) => end.parse().map_err(|_| invalid("invalid end"))?,
+        None => T::max_value(),
+    };
+    if start > end {
+        return Err(invalid("start is greater than end"));
+    }
+    Ok((chr, start - T::one(), end, strand))
+}
+
+/// A [`Display`] adapter formatting an interval as a region string.
+///
+/// Created by [`ToRegion::region`].
+pub struct RegionDisplay<'a, I, C, T>
+where
+    I: Coordinates<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    iv: &'a I,
+    phantom_c: PhantomData<C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<I, C, T> Display for RegionDisplay<'_, I, C, T>
+where
+    I: Coordinates<C, T>,
+    C: ChromBounds + Display,
+    T: ValueBounds + One + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let chr = self.iv.chr().to_string();
+        if chr.contains(':') {
+            write!(f, "{{{chr}}}")?;
+        } else {
+            write!(f, "{chr}")?;
+        }
+        if self.iv.start() != T::zero() || self.iv.end() != T::max_value() {
+            write!(f, ":{}-{}", self.iv.start() + T::one(), self.iv.end())?;
+        }
+        match self.iv.strand() {
+            Some(Strand::Forward) => write!(f, "(+)"),
+            Some(Strand::Reverse) => write!(f, "(-)"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Formats intervals as samtools / UCSC style region strings.
+///
+/// Coordinates are written 1-based and inclusive (`chr1:1001-2000` for
+/// the half-open interval `[1000, 2000)`), known strands are written as
+/// a `(+)` or `(-)` suffix, and intervals spanning a whole chromosome
+/// (from zero to the maximum value of `T`) are written as the chromosome
+/// name alone.
+/// Chromosome names containing colons are wrapped in braces
+/// (`{HLA-A*01:01}:1-100`).
+/// This is the reverse of parsing a region with `FromStr`, except for
+/// zero-length intervals: these have no 1-based inclusive form and are
+/// written with their end before their start (`chr1:11-10` for
+/// `[10, 10)`), which is rejected when parsed.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{Bed3, Strand, StrandedBed3, ToRegion};
+///
+/// let iv: Bed3<String, u32> = "chr1:1,001-2,000".parse().unwrap();
+/// assert_eq!(iv.region().to_string(), "chr1:1001-2000");
+///
+/// let iv = StrandedBed3::new("chr2", 0, 10, Strand::Reverse);
+/// assert_eq!(iv.region().to_string(), "chr2:1-10(-)");
+/// ```
+pub trait ToRegion<C, T>: Coordinates<C, T> + Sized
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    fn region(&self) -> RegionDisplay<'_, Self, C, T> {
+        RegionDisplay {
+            iv: self,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+impl<I, C, T> ToRegion<C, T> for I
+where
+    I: Coordinates<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    fn parse(text: &str) -> Result<(String, u32, u32, Strand), ParseError> {
+        parse_region(text)
+    }
+
+    #[test]
+    fn parse_full_region() {
+        let (chr, start, end, strand) = parse("chr1:1000-2000").unwrap();
+        assert_eq!(chr, "chr1");
+        assert_eq!(start, 999);
+        assert_eq!(end, 2000);
+        assert_eq!(strand, Strand::Unknown);
+    }
+
+    #[test]
+    fn parse_thousands_separator() {
+        let (_, start, end, _) = parse("chr1:1,000-2,000").unwrap();
+        assert_eq!(start, 999);
+        assert_eq!(end, 2000);
+    }
+
+    #[test]
+    fn parse_strand_suffix() {
+        assert_eq!(parse("chr1:1-10(+)").unwrap().3, Strand::Forward);
+        assert_eq!(parse("chr1:1-10(-)").unwrap().3, Strand::Reverse);
+        assert_eq!(parse("chr1:1-10(.)").unwrap().3, Strand::Unknown);
+        assert_eq!(parse("chr1(-)").unwrap().3, Strand::Reverse);
+    }
+
+    #[test]
+    fn parse_whole_chromosome() {
+        let (chr, start, end, _) = parse(" chrX ").unwrap();
+        assert_eq!(chr, "chrX");
+        assert_eq!(start, 0);
+        assert_eq!(end, u32::MAX);
+    }
+
+    #[test]
+    fn parse_open_end() {
+        let (_, start, end, _) = parse("chr1:100").unwrap();
+        assert_eq!(start, 99);
+        assert_eq!(end, u32::MAX);
+    }
+
+    #[test]
+    fn parse_colon_in_chromosome() {
+        let (chr, start, end, _) = parse("HLA-A*01:01:1-100").unwrap();
+        assert_eq!(chr, "HLA-A*01:01");
+        assert_eq!(start, 0);
+        assert_eq!(end, 100);
+    }
+
+    #[test]
+    fn parse_braced_chromosome() {
+        let (chr, start, end, _) = parse("{HLA-A*01:01}").unwrap();
+        assert_eq!(chr, "HLA-A*01:01");
+        assert_eq!((start, end), (0, u32::MAX));
+        let (chr, start, end, strand) = parse("{chrUn:1}:11-20(-)").unwrap();
+        assert_eq!(chr, "chrUn:1");
+        assert_eq!((start, end), (10, 20));
+        assert_eq!(strand, Strand::Reverse);
+    }
+
+    #[test]
+    fn parse_invalid_regions() {
+        for text in [
+            "",
+            ":1-10",
+            "chr1:0-10",
+            "chr1:20-10",
+            "chr1:a-10",
+            "chr1:1-b",
+            "chr1:1-10(x)",
+            "chr1:1-10+)",
+            "chr1:-5-10",
+            "{chr1",
+            "{chr1}1-10",
+            "{}:1-10",
+        ] {
+            match parse(text) {
+                Err(ParseError::InvalidRegion { region, .. }) => assert_eq!(region, text),
+                other => panic!("Expected invalid region for {text}: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn format_region() {
+        let iv = crate::Bed3::new("chr1", 999, 2000);
+        assert_eq!(iv.region().to_string(), "chr1:1000-2000");
+        let iv = crate::Bed3::new("chr1", 0_u32, u32::MAX);
+        assert_eq!(iv.region().to_string(), "chr1");
+        let iv = crate::StrandedBed3::new("chr1", 0, 1, Strand::Forward);
+        assert_eq!(iv.region().to_string(), "chr1:1-1(+)");
+        let iv = crate::StrandedBed3::new("chr1", 0, 1, Strand::Unknown);
+        assert_eq!(iv.region().to_string(), "chr1:1-1");
+        let iv = crate::Bed3::new("chr1", 10, 10);
+        assert_eq!(iv.region().to_string(), "chr1:11-10");
+        assert!(parse("chr1:11-10").is_err());
+    }
+
+    #[test]
+    fn format_region_round_trip() {
+        let hla = "HLA-A*01:01".to_string();
+        let iv = crate::StrandedBed3::new(hla.clone(), 99_u32, 200, Strand::Forward);
+        assert_eq!(iv.region().to_string(), "{HLA-A*01:01}:100-200(+)");
+        for iv in [
+            iv,
+            crate::StrandedBed3::new(hla, 0, u32::MAX, Strand::Unknown),
+            crate::StrandedBed3::new("chr1".to_string(), 0, 10, Strand::Reverse),
+        ] {
+            let parsed: crate::StrandedBed3<String, u32> = iv.region().to_string().parse().unwrap();
+            assert_eq!(parsed.chr(), iv.chr());
+            assert_eq!((parsed.start(), parsed.end()), (iv.start(), iv.end()));
+            assert_eq!(parsed.strand(), iv.strand());
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    traits::{ChromBounds, ValueBounds},
+    Genome,
+};
+use std::collections::HashMap;
+
+/// An interner mapping chromosome names to compact integer ids.
+///
+/// Using `u32` ids as the chromosome type of a record avoids allocating
+/// and comparing strings in every chromosome comparison, while the
+/// dictionary keeps the names so that they can be restored on output.
+///
+/// Ids are assigned consecutively starting at `0` and never change once
+/// assigned, so the order of the ids (and therefore the sort order of the
+/// records) is the order in which names were added to the dictionary.
+/// This order can be configured upfront with [`ChromDict::from_names`] or
+/// [`ChromDict::from_genome`]; names not known upfront are appended in
+/// the order they are first seen.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::ChromDict;
+///
+/// let mut dict = ChromDict::from_names(["chr1", "chr2", "chr10"]);
+/// assert_eq!(dict.id("chr10"), Some(2));
+/// assert_eq!(dict.get_or_insert("chrM"), 3);
+/// assert_eq!(dict.name(3), Some("chrM"));
+/// assert_eq!(dict.len(), 4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChromDict {
+    names: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+impl ChromDict {
+    /// Creates an empty dictionary
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a dictionary whose ids follow the order of the provided
+    /// names.
+    ///
+    /// Duplicate names keep the id of their first occurrence.
+    pub fn from_names<It, S>(names: It) -> Self
+    where
+        It: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut dict = Self::new();
+        for name in names {
+            dict.get_or_insert(name.as_ref());
+        }
+        dict
+    }
+
+    /// Creates a dictionary whose ids follow the canonical chromosome
+    /// order of a [`Genome`]
+    #[must_use]
+    pub fn from_genome<C, T>(genome: &Genome<C, T>) -> Self
+    where
+        C: ChromBounds + AsRef<str>,
+        T: ValueBounds,
+    {
+        Self::from_names(genome.chroms())
+    }
+
+    /// Returns the id of a name, assigning the next id if the name is not
+    /// yet known.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` names are interned
+    pub fn get_or_insert(&mut self, name: &str) -> u32 {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = u32::try_from(self.names.len()).expect("Too many chromosome names to intern");
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// Returns the id of a name if it is known
+    #[must_use]
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of an id if it is known
+    #[must_use]
+    pub fn name(&self, id: u32) -> Option<&str> {
+        self.names.get(id as usize).map(String::as_str)
+    }
+
+    /// Returns the number of interned names
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns true if no names are interned
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Iterates over the names in id order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Bed3, Coordinates, IntervalContainer};
+
+    #[test]
+    fn chrom_dict_insertion_order() {
+        let mut dict = ChromDict::new();
+        assert!(dict.is_empty());
+        assert_eq!(dict.get_or_insert("chr2"), 0);
+        assert_eq!(dict.get_or_insert("chr1"), 1);
+        assert_eq!(dict.get_or_insert("chr2"), 0);
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.names().collect::<Vec<_>>(), vec!["chr2", "chr1"]);
+        assert_eq!(dict.id("chr3"), None);
+        assert_eq!(dict.name(2), None);
+    }
+
+    #[test]
+    fn chrom_dict_from_names() {
+        let dict = ChromDict::from_names(vec!["chr1", "chr2", "chr1", "chr10"]);
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict.id("chr1"), Some(0));
+        assert_eq!(dict.id("chr10"), Some(2));
+    }
+
+    #[test]
+    fn chrom_dict_from_genome() {
+        let genome =
+            Genome::from_sizes(vec![("chr2".to_string(), 100), ("chr10".to_string(), 50)]).unwrap();
+        let dict = ChromDict::from_genome(&genome);
+        assert_eq!(dict.id("chr2"), Some(0));
+        assert_eq!(dict.id("chr10"), Some(1));
+    }
+
+    #[test]
+    fn chrom_dict_sort_order() {
+        let mut dict = ChromDict::from_names(["chr1", "chr2", "chr10"]);
+        let set = IntervalContainer::from_unsorted(vec![
+            Bed3::new(dict.get_or_insert("chr10"), 10, 20),
+            Bed3::new(dict.get_or_insert("chr2"), 10, 20),
+            Bed3::new(dict.get_or_insert("chr1"), 10, 20),
+        ]);
+        let names: Vec<_> = set
+            .records()
+            .iter()
+            .map(|iv| dict.name(*iv.chr()).unwrap())
+            .collect();
+        assert_eq!(names, vec!["chr1", "chr2", "chr10"]);
+    }
+}
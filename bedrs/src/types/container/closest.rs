@@ -3,7 +3,6 @@ use crate::{
     types::StrandMethod,
     Distance, IntervalContainer, Strand,
 };
-use anyhow::Result;
 
 impl<I, C, T> IntervalContainer<I, C, T>
 where
@@ -1,12 +1,15 @@
-use anyhow::{bail, Result};
-
 use crate::{
-    traits::{ChromBounds, IntervalBounds, SetError, ValueBounds},
-    types::{iterator::ComplementIter, IntervalIterOwned},
-    IntervalContainer,
+    traits::{ChromBounds, GenomeError, IntervalBounds, SetError, ValueBounds},
+    types::{
+        iterator::{ComplementIter, GenomeComplementIter},
+        IntervalIterOwned,
+    },
+    Genome, IntervalContainer,
 };
+use std::fmt::Display;
 
 type ComplementIterOwned<I, C, T> = ComplementIter<IntervalIterOwned<I, C, T>, I, C, T>;
+type GenomeComplementIterOwned<I, C, T> = GenomeComplementIter<IntervalIterOwned<I, C, T>, I, C, T>;
 
 /// A trait for interval containers that generates an iterator over the
 /// complement of the intervals in the container.
@@ -50,24 +53,89 @@ where
     C: ChromBounds,
     T: ValueBounds,
 {
-    pub fn complement(self) -> Result<ComplementIterOwned<I, C, T>> {
+    pub fn complement(self) -> Result<ComplementIterOwned<I, C, T>, SetError> {
         if self.is_sorted() {
             Ok(self.complement_unchecked())
         } else {
-            bail!(SetError::UnsortedSet)
+            Err(SetError::UnsortedSet)
         }
     }
 
     pub fn complement_unchecked(self) -> ComplementIterOwned<I, C, T> {
         ComplementIter::new(self.into_iter())
     }
+
+    /// Generates an iterator over the complement of the intervals with
+    /// respect to the provided [`Genome`].
+    ///
+    /// This includes the regions between the start of each chromosome and
+    /// its first interval, between its last interval and its end, and
+    /// chromosomes without any intervals (equivalent to
+    /// `bedtools complement -g`).
+    ///
+    /// Complements are reported in sorted chromosome order.
+    ///
+    /// Returns an error if the container is unsorted or if any interval
+    /// lies on an unknown chromosome or past a chromosome end.
+    ///
+    /// ```text
+    /// |1|   x----y  x------y   |2|          |3|  x----y
+    /// ==================================================
+    /// |1| 0-x    y--x      y-E |2| 0------E |3| 0-x    y-E
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{Bed3, Coordinates, Genome, IntervalContainer};
+    ///
+    /// let genome = Genome::from_sizes(vec![(1, 100), (2, 50)]).unwrap();
+    /// let set = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 10, 20),
+    ///     Bed3::new(1, 30, 40),
+    /// ]);
+    ///
+    /// let expected = vec![
+    ///     Bed3::new(1, 0, 10),
+    ///     Bed3::new(1, 20, 30),
+    ///     Bed3::new(1, 40, 100),
+    ///     Bed3::new(2, 0, 50),
+    /// ];
+    ///
+    /// let complements: Vec<_> = set.complement_genome(&genome).unwrap().collect();
+    /// assert_eq!(complements.len(), expected.len());
+    /// for (obs, exp) in complements.iter().zip(expected.iter()) {
+    ///    assert!(obs.eq(exp));
+    /// }
+    /// ```
+    pub fn complement_genome(
+        self,
+        genome: &Genome<C, T>,
+    ) -> Result<GenomeComplementIterOwned<I, C, T>, GenomeError>
+    where
+        C: Display,
+        T: Display,
+    {
+        if !self.is_sorted() {
+            return Err(SetError::UnsortedSet.into());
+        }
+        genome.validate(&self)?;
+        Ok(self.complement_genome_unchecked(genome))
+    }
+
+    pub fn complement_genome_unchecked(
+        self,
+        genome: &Genome<C, T>,
+    ) -> GenomeComplementIterOwned<I, C, T> {
+        GenomeComplementIter::new(self.into_iter(), genome)
+    }
 }
 
 #[cfg(test)]
 mod testing {
     use crate::{
         traits::{ChromBounds, IntervalBounds, ValueBounds},
-        BaseInterval, Bed3, IntervalContainer,
+        BaseInterval, Bed3, Genome, IntervalContainer,
     };
 
     fn validate_records<I, C, T>(obs: &[I], exp: &[I])
@@ -169,4 +237,41 @@ mod testing {
         let complements: Vec<_> = comp_iter.collect();
         validate_records(&complements, &expected);
     }
+
+    #[test]
+    fn complement_genome() {
+        let genome = Genome::from_sizes(vec![(1, 100), (2, 50), (3, 70)]).unwrap();
+        let intervals = vec![
+            Bed3::new(3, 0, 70),
+            Bed3::new(1, 30, 40),
+            Bed3::new(1, 10, 20),
+        ];
+        let expected = vec![
+            Bed3::new(1, 0, 10),
+            Bed3::new(1, 20, 30),
+            Bed3::new(1, 40, 100),
+            Bed3::new(2, 0, 50),
+        ];
+        let set = IntervalContainer::from_unsorted(intervals);
+        let comp_iter = set.complement_genome(&genome).unwrap();
+        let complements: Vec<_> = comp_iter.collect();
+        validate_records(&complements, &expected);
+    }
+
+    #[test]
+    fn complement_genome_unsorted() {
+        let genome = Genome::from_sizes(vec![(1, 100)]).unwrap();
+        let intervals = vec![Bed3::new(1, 30, 40), Bed3::new(1, 10, 20)];
+        let set = IntervalContainer::new(intervals);
+        assert!(set.complement_genome(&genome).is_err());
+    }
+
+    #[test]
+    fn complement_genome_invalid() {
+        let genome = Genome::from_sizes(vec![(1, 100)]).unwrap();
+        let set = IntervalContainer::from_unsorted(vec![Bed3::new(2, 10, 20)]);
+        assert!(set.complement_genome(&genome).is_err());
+        let set = IntervalContainer::from_unsorted(vec![Bed3::new(1, 90, 120)]);
+        assert!(set.complement_genome(&genome).is_err());
+    }
 }
@@ -0,0 +1,291 @@
+use crate::{
+    traits::{ChromBounds, IntervalBounds, SetError, ValueBounds},
+    types::{Query, StrandMethod},
+    IntervalContainer,
+};
+use getset::CopyGetters;
+use num_traits::zero;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The coverage of a target interval by a set of query intervals,
+/// mirroring a line of `bedtools coverage`.
+#[derive(Debug, Clone, PartialEq, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coverage<T: ValueBounds> {
+    /// The number of query intervals overlapping the target
+    #[getset(get_copy = "pub")]
+    count: usize,
+    /// The number of target bases covered by at least one query interval
+    #[getset(get_copy = "pub")]
+    covered: T,
+    /// The length of the target
+    #[getset(get_copy = "pub")]
+    length: T,
+    histogram: Option<Vec<T>>,
+}
+
+impl<T: ValueBounds> Coverage<T> {
+    /// Returns the fraction of the target covered by the query intervals
+    ///
+    /// Empty targets have a fraction of zero.
+    pub fn fraction(&self) -> f64 {
+        let length = self.length.to_f64().unwrap_or_default();
+        if length > 0.0 {
+            self.covered.to_f64().unwrap_or_default() / length
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the number of target bases at each depth, indexed by depth
+    /// starting at zero, if requested.
+    pub fn histogram(&self) -> Option<&[T]> {
+        self.histogram.as_deref()
+    }
+}
+
+/// Computes the coverage of a target interval by the query intervals
+fn target_coverage<I, Iq, C, T>(
+    target: &I,
+    queries: &IntervalContainer<Iq, C, T>,
+    query: Query<T>,
+    histogram: bool,
+) -> Result<Coverage<T>, SetError>
+where
+    I: IntervalBounds<C, T>,
+    Iq: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    let mut count = 0;
+    let mut events = Vec::new();
+    for hit in queries.query_iter(target, query)? {
+        count += 1;
+        let start = hit.start().max(target.start());
+        let end = hit.end().min(target.end());
+        if start < end {
+            events.push((start, true));
+            events.push((end, false));
+        }
+    }
+    // Ends sort before starts at the same position as intervals are half-open
+    events.sort_unstable();
+
+    let length = target.len();
+    let mut depths: Vec<T> = vec![zero()];
+    let mut depth = 0;
+    let mut last = target.start();
+    for (pos, is_start) in events {
+        if depth >= depths.len() {
+            depths.resize(depth + 1, zero());
+        }
+        depths[depth] = depths[depth] + (pos - last);
+        last = pos;
+        if is_start {
+            depth += 1;
+        } else {
+            depth -= 1;
+        }
+    }
+    depths[0] = depths[0] + (target.end() - last);
+    let covered = length - depths[0];
+
+    Ok(Coverage {
+        count,
+        covered,
+        length,
+        histogram: histogram.then_some(depths),
+    })
+}
+
+/// Reports the coverage of the intervals of a container by another container
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Reports the coverage of every interval of the container by a set of
+    /// query intervals, mirroring `bedtools coverage`.
+    ///
+    /// For each interval (the target) the number of overlapping query
+    /// intervals, the number of target bases covered by at least one of them
+    /// (overlapping query intervals are merged), the target length, and the
+    /// covered fraction are reported as a [`Coverage`].
+    /// Query intervals are compared with the targets according to the
+    /// [`StrandMethod`].
+    ///
+    /// Targets are reported in container order, and the query container must
+    /// be sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{types::StrandMethod, Bed3, IntervalContainer};
+    ///
+    /// let targets = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 0, 100),
+    ///     Bed3::new(1, 200, 300),
+    /// ]);
+    /// let queries = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 10, 30),
+    ///     Bed3::new(1, 20, 40),
+    ///     Bed3::new(1, 90, 210),
+    /// ]);
+    ///
+    /// let coverage = targets.coverage(&queries, StrandMethod::Ignore).unwrap();
+    /// let (_, first) = &coverage[0];
+    /// assert_eq!(first.count(), 3);
+    /// assert_eq!(first.covered(), 40);
+    /// assert_eq!(first.length(), 100);
+    /// assert_eq!(first.fraction(), 0.4);
+    ///
+    /// let (_, second) = &coverage[1];
+    /// assert_eq!(second.count(), 1);
+    /// assert_eq!(second.covered(), 10);
+    /// ```
+    pub fn coverage<Iq>(
+        &self,
+        queries: &IntervalContainer<Iq, C, T>,
+        method: StrandMethod,
+    ) -> Result<Vec<(&I, Coverage<T>)>, SetError>
+    where
+        Iq: IntervalBounds<C, T>,
+    {
+        self.coverage_with(queries, method, false)
+    }
+
+    /// Reports the coverage of every interval of the container along with
+    /// the number of bases of each target at each depth (as with
+    /// `bedtools coverage -hist`).
+    ///
+    /// ```
+    /// use bedrs::{types::StrandMethod, Bed3, IntervalContainer};
+    ///
+    /// let targets = IntervalContainer::from_unsorted(vec![Bed3::new(1, 0, 100)]);
+    /// let queries = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 10, 30),
+    ///     Bed3::new(1, 20, 40),
+    /// ]);
+    ///
+    /// let coverage = targets.coverage_histogram(&queries, StrandMethod::Ignore).unwrap();
+    /// assert_eq!(coverage[0].1.histogram(), Some(&[70, 20, 10][..]));
+    /// ```
+    pub fn coverage_histogram<Iq>(
+        &self,
+        queries: &IntervalContainer<Iq, C, T>,
+        method: StrandMethod,
+    ) -> Result<Vec<(&I, Coverage<T>)>, SetError>
+    where
+        Iq: IntervalBounds<C, T>,
+    {
+        self.coverage_with(queries, method, true)
+    }
+
+    fn coverage_with<Iq>(
+        &self,
+        queries: &IntervalContainer<Iq, C, T>,
+        method: StrandMethod,
+        histogram: bool,
+    ) -> Result<Vec<(&I, Coverage<T>)>, SetError>
+    where
+        Iq: IntervalBounds<C, T>,
+    {
+        if !queries.is_sorted() {
+            return Err(SetError::UnsortedSet);
+        }
+        let query = Query::new_strandedness(method);
+        self.records()
+            .iter()
+            .map(|target| {
+                target_coverage(target, queries, query, histogram).map(|cov| (target, cov))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+    use crate::{Bed3, Coordinates, Strand, StrandedBed3};
+
+    #[test]
+    fn test_coverage() {
+        let targets = IntervalContainer::new(vec![
+            Bed3::new(1, 100, 200),
+            Bed3::new(1, 0, 50),
+            Bed3::new(2, 0, 50),
+        ]);
+        let queries = IntervalContainer::from_unsorted(vec![
+            Bed3::new(1, 0, 10),
+            Bed3::new(1, 40, 120),
+            Bed3::new(1, 150, 160),
+            Bed3::new(1, 155, 170),
+            Bed3::new(1, 200, 210),
+        ]);
+        let coverage = targets.coverage(&queries, StrandMethod::Ignore).unwrap();
+        let observed: Vec<_> = coverage
+            .iter()
+            .map(|(iv, cov)| (iv.start(), cov.count(), cov.covered(), cov.length()))
+            .collect();
+        assert_eq!(
+            observed,
+            vec![(100, 3, 40, 100), (0, 2, 20, 50), (0, 0, 0, 50)]
+        );
+        assert_eq!(coverage[0].1.fraction(), 0.4);
+        assert_eq!(coverage[2].1.fraction(), 0.0);
+        assert!(coverage[0].1.histogram().is_none());
+    }
+
+    #[test]
+    fn test_coverage_histogram() {
+        let targets = IntervalContainer::from_unsorted(vec![Bed3::new(1, 100, 200)]);
+        let queries = IntervalContainer::from_unsorted(vec![
+            Bed3::new(1, 50, 120),
+            Bed3::new(1, 110, 130),
+            Bed3::new(1, 115, 125),
+            Bed3::new(1, 125, 130),
+        ]);
+        let coverage = targets
+            .coverage_histogram(&queries, StrandMethod::Ignore)
+            .unwrap();
+        let cov = &coverage[0].1;
+        assert_eq!(cov.count(), 4);
+        assert_eq!(cov.covered(), 30);
+        // [100,110) 1, [110,115) 2, [115,120) 3, [120,125) 2, [125,130) 2
+        assert_eq!(cov.histogram(), Some(&[70, 10, 15, 5][..]));
+        let total: u32 = cov.histogram().unwrap().iter().sum();
+        assert_eq!(total, cov.length());
+    }
+
+    #[test]
+    fn test_coverage_stranded() {
+        let targets =
+            IntervalContainer::from_unsorted(vec![StrandedBed3::new(1, 0, 100, Strand::Forward)]);
+        let queries = IntervalContainer::from_unsorted(vec![
+            StrandedBed3::new(1, 0, 10, Strand::Forward),
+            StrandedBed3::new(1, 20, 50, Strand::Reverse),
+        ]);
+        let covered = |method| {
+            let coverage = targets.coverage(&queries, method).unwrap();
+            (coverage[0].1.count(), coverage[0].1.covered())
+        };
+        assert_eq!(covered(StrandMethod::Ignore), (2, 40));
+        assert_eq!(covered(StrandMethod::MatchStrand), (1, 10));
+        assert_eq!(covered(StrandMethod::OppositeStrand), (1, 30));
+    }
+
+    #[test]
+    fn test_coverage_unsorted_queries() {
+        let targets = IntervalContainer::from_unsorted(vec![Bed3::new(1, 0, 100)]);
+        let queries = IntervalContainer::new(vec![Bed3::new(1, 50, 60), Bed3::new(1, 0, 10)]);
+        assert_eq!(
+            targets
+                .coverage(&queries, StrandMethod::Ignore)
+                .unwrap_err(),
+            SetError::UnsortedSet
+        );
+    }
+}
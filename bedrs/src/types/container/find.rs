@@ -3,7 +3,6 @@ use crate::{
     traits::{errors::SetError, ChromBounds, IntervalBounds, ValueBounds},
     types::{FindIter, FindIterEnumerate, FindIterOwned, IntervalContainer, Query},
 };
-use anyhow::Result;
 
 /// A trait to query set overlaps through a container
 impl<I, C, T> IntervalContainer<I, C, T>
@@ -20,7 +19,7 @@ where
         &'a self,
         query: &'a Iv,
         method: Query<T>,
-    ) -> Result<FindIter<'_, C, T, I, Iv>, SetError>
+    ) -> Result<FindIter<'a, C, T, I, Iv>, SetError>
     where
         Iv: IntervalBounds<C, T>,
     {
@@ -46,7 +45,7 @@ where
         &'a self,
         query: &'a Iv,
         method: Query<T>,
-    ) -> Result<FindIterEnumerate<'_, C, T, I, Iv>, SetError>
+    ) -> Result<FindIterEnumerate<'a, C, T, I, Iv>, SetError>
     where
         Iv: IntervalBounds<C, T>,
     {
@@ -0,0 +1,351 @@
+use crate::{
+    traits::{ChromBounds, GenomeError, IntervalBounds, SetError, ValueBounds},
+    BedGraph, Coordinates, Genome, IntervalContainer, Strand,
+};
+use num_traits::zero;
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display};
+
+type DepthContainer<C, T> = IntervalContainer<BedGraph<C, T>, C, T>;
+
+/// Appends a run of constant depth, extending the previous run if it is
+/// adjacent and at the same depth.
+#[allow(clippy::cast_precision_loss, clippy::float_cmp)]
+fn push_run<C, T>(runs: &mut Vec<BedGraph<C, T>>, chr: &C, start: T, end: T, depth: usize)
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    if start >= end {
+        return;
+    }
+    let score = depth as f64;
+    if let Some(last) = runs.last_mut() {
+        if last.chr() == chr && last.end() == start && last.score() == score {
+            last.update_end(&end);
+            return;
+        }
+    }
+    runs.push(BedGraph::new(chr.clone(), start, end, score));
+}
+
+/// Sweeps over the sorted intervals of a single chromosome, appending the
+/// runs of constant depth.
+///
+/// If the chromosome size is provided the zero depth runs from the start
+/// to the end of the chromosome are reported as well.
+fn sweep_chrom<'a, I, C, T, It>(
+    chr: &C,
+    intervals: It,
+    size: Option<T>,
+    runs: &mut Vec<BedGraph<C, T>>,
+) where
+    I: IntervalBounds<C, T> + 'a,
+    C: ChromBounds,
+    T: ValueBounds,
+    It: Iterator<Item = &'a I>,
+{
+    let with_zeros = size.is_some();
+    let mut ends = BinaryHeap::new();
+    let mut last = size.map(|_| zero());
+
+    for iv in intervals {
+        // Close all intervals ending before the current one starts
+        while let Some(&Reverse(end)) = ends.peek() {
+            if end > iv.start() {
+                break;
+            }
+            if let Some(pos) = last {
+                push_run(runs, chr, pos, end, ends.len());
+            }
+            last = Some(end);
+            ends.pop();
+        }
+        if let Some(pos) = last {
+            if with_zeros || !ends.is_empty() {
+                push_run(runs, chr, pos, iv.start(), ends.len());
+            }
+        }
+        last = Some(iv.start());
+        ends.push(Reverse(iv.end()));
+    }
+
+    // Close the remaining intervals
+    while let Some(Reverse(end)) = ends.pop() {
+        if let Some(pos) = last {
+            push_run(runs, chr, pos, end, ends.len() + 1);
+        }
+        last = Some(end);
+    }
+    if let (Some(pos), Some(size)) = (last, size) {
+        push_run(runs, chr, pos, size, 0);
+    }
+}
+
+/// Computes the depth of coverage of the intervals of a container along
+/// the genome, mirroring `bedtools genomecov -bg` and `-bga`.
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Reports the depth of the intervals as [`BedGraph`] runs, where the
+    /// score of each run is the number of intervals covering it (as with
+    /// `bedtools genomecov -bg`).
+    ///
+    /// Adjacent runs of the same depth are joined and regions not covered
+    /// by any interval are not reported.
+    /// If a strand is provided only the intervals on that strand are
+    /// counted.
+    ///
+    /// ```text
+    /// (a)    i--------j
+    /// (b)        k--------l
+    /// (c)                       m----n
+    /// ===================================
+    /// (1)    i---k                        1
+    /// (2)        k----j                   2
+    /// (3)             j---l               1
+    /// (4)                       m----n    1
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{Bed3, Coordinates, IntervalContainer};
+    ///
+    /// let set = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 10, 30),
+    ///     Bed3::new(1, 20, 40),
+    ///     Bed3::new(1, 60, 70),
+    /// ]);
+    ///
+    /// let depth = set.genome_coverage(None).unwrap();
+    /// let runs: Vec<_> = depth
+    ///     .records()
+    ///     .iter()
+    ///     .map(|bg| (bg.start(), bg.end(), bg.score()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     runs,
+    ///     vec![(10, 20, 1.0), (20, 30, 2.0), (30, 40, 1.0), (60, 70, 1.0)]
+    /// );
+    /// ```
+    pub fn genome_coverage(
+        &self,
+        strand: Option<Strand>,
+    ) -> Result<DepthContainer<C, T>, SetError> {
+        if !self.is_sorted() {
+            return Err(SetError::UnsortedSet);
+        }
+        if strand == Some(Strand::Unknown) {
+            return Err(SetError::CannotAcceptUnknownStrand);
+        }
+        let mut runs = Vec::new();
+        for chrom in self.records().chunk_by(|a, b| a.chr() == b.chr()) {
+            let intervals = chrom
+                .iter()
+                .filter(|iv| strand.is_none() || iv.strand() == strand);
+            sweep_chrom(chrom[0].chr(), intervals, None, &mut runs);
+        }
+        Ok(IntervalContainer::from_sorted_unchecked(runs))
+    }
+
+    /// Reports the depth of the intervals as [`BedGraph`] runs including
+    /// the regions of zero depth, covering every chromosome of the
+    /// [`Genome`] from start to end (as with `bedtools genomecov -bga`).
+    ///
+    /// Runs are reported in sorted chromosome order.
+    /// If a strand is provided only the intervals on that strand are
+    /// counted.
+    ///
+    /// Returns an error if the container is unsorted or if any interval
+    /// lies on an unknown chromosome or past a chromosome end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{Bed3, Coordinates, Genome, IntervalContainer};
+    ///
+    /// let genome = Genome::from_sizes(vec![(1, 100), (2, 50)]).unwrap();
+    /// let set = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 10, 30),
+    ///     Bed3::new(1, 20, 40),
+    /// ]);
+    ///
+    /// let depth = set.genome_coverage_with_zeros(&genome, None).unwrap();
+    /// let runs: Vec<_> = depth
+    ///     .records()
+    ///     .iter()
+    ///     .map(|bg| (*bg.chr(), bg.start(), bg.end(), bg.score()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     runs,
+    ///     vec![
+    ///         (1, 0, 10, 0.0),
+    ///         (1, 10, 20, 1.0),
+    ///         (1, 20, 30, 2.0),
+    ///         (1, 30, 40, 1.0),
+    ///         (1, 40, 100, 0.0),
+    ///         (2, 0, 50, 0.0),
+    ///     ]
+    /// );
+    /// ```
+    pub fn genome_coverage_with_zeros(
+        &self,
+        genome: &Genome<C, T>,
+        strand: Option<Strand>,
+    ) -> Result<DepthContainer<C, T>, GenomeError>
+    where
+        C: Display,
+        T: Display,
+    {
+        if !self.is_sorted() {
+            return Err(SetError::UnsortedSet.into());
+        }
+        if strand == Some(Strand::Unknown) {
+            return Err(SetError::CannotAcceptUnknownStrand.into());
+        }
+        genome.validate(self)?;
+
+        let mut chroms: Vec<_> = genome.iter().collect();
+        chroms.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        let records = self.records();
+        let mut runs = Vec::new();
+        for (chr, size) in chroms {
+            let lower = records.partition_point(|iv| iv.chr() < chr);
+            let upper = records.partition_point(|iv| iv.chr() <= chr);
+            let intervals = records[lower..upper]
+                .iter()
+                .filter(|iv| strand.is_none() || iv.strand() == strand);
+            sweep_chrom(chr, intervals, Some(size), &mut runs);
+        }
+        Ok(IntervalContainer::from_sorted_unchecked(runs))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+    use crate::{Bed3, StrandedBed3};
+
+    fn runs<C: ChromBounds + Copy>(depth: &DepthContainer<C, u32>) -> Vec<(C, u32, u32, f64)> {
+        depth
+            .records()
+            .iter()
+            .map(|bg| (*bg.chr(), bg.start(), bg.end(), bg.score()))
+            .collect()
+    }
+
+    /// Container:
+    /// (a)    i------------------j
+    /// (b)      k----l
+    /// (c)      k----l
+    /// (d)              m----n
+    /// (e)                       j----o
+    /// ===================================
+    /// (1)    i-k                          1
+    /// (2)      k----l                     3
+    /// (3)           l--m                  1
+    /// (4)              m----n             2
+    /// (5)                   n---j----o    1
+    #[test]
+    fn genome_coverage_nested() {
+        let set = IntervalContainer::from_unsorted(vec![
+            Bed3::new(1, 10, 100),
+            Bed3::new(1, 20, 40),
+            Bed3::new(1, 20, 40),
+            Bed3::new(1, 50, 60),
+            Bed3::new(1, 100, 120),
+            Bed3::new(2, 5, 10),
+        ]);
+        let depth = set.genome_coverage(None).unwrap();
+        assert_eq!(
+            runs(&depth),
+            vec![
+                (1, 10, 20, 1.0),
+                (1, 20, 40, 3.0),
+                (1, 40, 50, 1.0),
+                (1, 50, 60, 2.0),
+                (1, 60, 120, 1.0),
+                (2, 5, 10, 1.0),
+            ]
+        );
+        assert!(depth.is_sorted());
+    }
+
+    #[test]
+    fn genome_coverage_gaps() {
+        let set = IntervalContainer::from_unsorted(vec![
+            Bed3::new(1, 10, 20),
+            Bed3::new(1, 30, 40),
+            Bed3::new(1, 35, 50),
+        ]);
+        let depth = set.genome_coverage(None).unwrap();
+        assert_eq!(
+            runs(&depth),
+            vec![
+                (1, 10, 20, 1.0),
+                (1, 30, 35, 1.0),
+                (1, 35, 40, 2.0),
+                (1, 40, 50, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn genome_coverage_stranded() {
+        let set = IntervalContainer::from_unsorted(vec![
+            StrandedBed3::new(1, 10, 30, Strand::Forward),
+            StrandedBed3::new(1, 20, 40, Strand::Reverse),
+            StrandedBed3::new(1, 25, 35, Strand::Forward),
+        ]);
+        let depth = set.genome_coverage(Some(Strand::Forward)).unwrap();
+        assert_eq!(
+            runs(&depth),
+            vec![(1, 10, 25, 1.0), (1, 25, 30, 2.0), (1, 30, 35, 1.0)]
+        );
+        let depth = set.genome_coverage(Some(Strand::Reverse)).unwrap();
+        assert_eq!(runs(&depth), vec![(1, 20, 40, 1.0)]);
+        assert_eq!(
+            set.genome_coverage(Some(Strand::Unknown)).unwrap_err(),
+            SetError::CannotAcceptUnknownStrand
+        );
+    }
+
+    #[test]
+    fn genome_coverage_with_zeros() {
+        let genome = Genome::from_sizes(vec![(3, 30), (1, 100), (2, 50)]).unwrap();
+        let set = IntervalContainer::from_unsorted(vec![
+            Bed3::new(1, 0, 20),
+            Bed3::new(1, 10, 20),
+            Bed3::new(3, 20, 30),
+        ]);
+        let depth = set.genome_coverage_with_zeros(&genome, None).unwrap();
+        assert_eq!(
+            runs(&depth),
+            vec![
+                (1, 0, 10, 1.0),
+                (1, 10, 20, 2.0),
+                (1, 20, 100, 0.0),
+                (2, 0, 50, 0.0),
+                (3, 0, 20, 0.0),
+                (3, 20, 30, 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn genome_coverage_errors() {
+        let set = IntervalContainer::new(vec![Bed3::new(1, 30, 40), Bed3::new(1, 10, 20)]);
+        assert_eq!(
+            set.genome_coverage(None).unwrap_err(),
+            SetError::UnsortedSet
+        );
+        let genome = Genome::from_sizes(vec![(1, 30)]).unwrap();
+        let set = IntervalContainer::from_unsorted(vec![Bed3::new(1, 10, 40)]);
+        assert!(set.genome_coverage_with_zeros(&genome, None).is_err());
+    }
+}
@@ -3,7 +3,6 @@ use crate::{
     types::SubtractFromIter,
     IntervalContainer,
 };
-use anyhow::{bail, Result};
 
 /// Identifies al non-overlapping intervals within the span of the interval set
 impl<I, C, T> IntervalContainer<I, C, T>
@@ -24,12 +23,12 @@ where
     /// (i)          j---k
     /// (ii)                  l--m
     /// ```
-    pub fn internal(&self) -> Result<SubtractFromIter<I, I, C, T>> {
+    pub fn internal(&self) -> Result<SubtractFromIter<I, I, C, T>, SetError> {
         if self.is_sorted() {
             let span = self.span()?;
             Ok(self.internal_unchecked(&span))
         } else {
-            bail!(SetError::UnsortedSet)
+            Err(SetError::UnsortedSet)
         }
     }
 
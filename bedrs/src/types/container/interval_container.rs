@@ -2,7 +2,6 @@ use crate::{
     traits::{ChromBounds, IntervalBounds, SetError, ValueBounds},
     Coordinates, IntervalIterOwned, IntervalIterRef,
 };
-use anyhow::{bail, Result};
 use num_traits::zero;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -33,9 +32,8 @@ where
         let mut max_len = zero::<T>();
         let records = iter
             .into_iter()
-            .map(|iv| {
+            .inspect(|iv| {
                 max_len = max_len.max(iv.len());
-                iv
             })
             .collect();
         let max_len = if max_len == zero::<T>() {
@@ -103,20 +101,18 @@ where
         &mut self.max_len
     }
     /// Returns the span of the interval set
-    pub fn span(&self) -> Result<I> {
-        if self.is_empty() {
-            bail!("Cannot get span of empty interval set")
-        } else if !self.is_sorted() {
-            bail!("Cannot get span of unsorted interval set")
-        }
-        let Some(first) = self.records().first() else {
-            bail!("Cannot recover the first interval")
-        };
-        let Some(last) = self.records().last() else {
-            bail!("Cannot recover the last interval")
+    ///
+    /// Returns an error if the set is empty, unsorted, or spans multiple
+    /// chromosomes.
+    pub fn span(&self) -> Result<I, SetError> {
+        let (Some(first), Some(last)) = (self.records().first(), self.records().last()) else {
+            return Err(SetError::EmptySet);
         };
+        if !self.is_sorted() {
+            return Err(SetError::UnsortedSet);
+        }
         if first.chr() != last.chr() {
-            bail!("Cannot get span of interval set spanning multiple chromosomes")
+            return Err(SetError::MultipleChromosomes);
         }
         let mut iv = I::empty();
         iv.update_chr(first.chr());
@@ -125,7 +121,7 @@ where
         Ok(iv)
     }
     #[allow(clippy::iter_without_into_iter)]
-    pub fn iter(&self) -> IntervalIterRef<I, C, T> {
+    pub fn iter(&self) -> IntervalIterRef<'_, I, C, T> {
         IntervalIterRef::new(self.records())
     }
     #[allow(clippy::should_implement_trait)]
@@ -369,18 +365,9 @@ mod testing {
     fn test_genomic_span_errors() {
         let intervals = vec![Bed3::new(1, 10, 100), Bed3::new(2, 20, 200)];
         let mut set = IntervalContainer::from_iter(intervals);
-        match set.span() {
-            Err(e) => assert_eq!(e.to_string(), "Cannot get span of unsorted interval set"),
-            _ => panic!("Expected error"),
-        };
+        assert!(matches!(set.span(), Err(SetError::UnsortedSet)));
         set.sort();
-        match set.span() {
-            Err(e) => assert_eq!(
-                e.to_string(),
-                "Cannot get span of interval set spanning multiple chromosomes"
-            ),
-            _ => panic!("Expected error"),
-        };
+        assert!(matches!(set.span(), Err(SetError::MultipleChromosomes)));
     }
 
     #[test]
@@ -475,7 +462,7 @@ mod testing {
         let set: IntervalContainer<StrandedBed3<u32, u32>, u32, u32> =
             IntervalContainer::new(vec![]);
         let span = set.span();
-        assert!(span.is_err());
+        assert!(matches!(span, Err(SetError::EmptySet)));
     }
 
     #[test]
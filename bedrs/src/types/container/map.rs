@@ -0,0 +1,278 @@
+use crate::{
+    traits::{AggregateError, ChromBounds, IntervalBounds, SetError, ValueBounds},
+    types::{AggregateValue, Aggregator, Query},
+    IntervalContainer,
+};
+use std::fmt::Display;
+
+/// Summarizes the query records overlapping each interval of a container,
+/// mirroring `bedtools map`.
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Collects the query records overlapping each interval of the
+    /// container (the target) according to the [`Query`], and summarizes
+    /// a numeric field of those records with the [`Aggregator`].
+    ///
+    /// The `field` closure selects the value of each query record, such as
+    /// its score, where `None` is a record without a value.
+    /// See [`IntervalContainer::map_text`] to summarize text fields.
+    /// As with [`IntervalContainer::query_iter`] each target is the query
+    /// interval of the [`Query`], so that for example
+    /// [`QueryMethod::CompareByTargetFraction`](crate::types::QueryMethod::CompareByTargetFraction)
+    /// requires a fraction of each query record to be overlapped.
+    /// Values are aggregated in the sorted order of the query records, and
+    /// targets are reported in container order.
+    ///
+    /// Returns an error if the query container is unsorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{
+    ///     types::{AggregateValue, Aggregator, Query},
+    ///     Bed3, BedGraph, IntervalContainer,
+    /// };
+    ///
+    /// let peaks = IntervalContainer::from_unsorted(vec![
+    ///     Bed3::new(1, 0, 100),
+    ///     Bed3::new(1, 500, 600),
+    /// ]);
+    /// let signal = IntervalContainer::from_unsorted(vec![
+    ///     BedGraph::new(1, 0, 50, 2.0),
+    ///     BedGraph::new(1, 50, 150, 4.0),
+    /// ]);
+    ///
+    /// let means = peaks
+    ///     .map(&signal, Query::default(), Aggregator::Mean, |bg| bg.score())
+    ///     .unwrap();
+    /// assert_eq!(means[0].1, AggregateValue::Number(3.0));
+    /// assert_eq!(means[1].1, AggregateValue::Missing);
+    /// ```
+    pub fn map<Iq, V, F>(
+        &self,
+        queries: &IntervalContainer<Iq, C, T>,
+        query: Query<T>,
+        aggregator: Aggregator,
+        field: F,
+    ) -> Result<Vec<(&I, AggregateValue)>, SetError>
+    where
+        Iq: IntervalBounds<C, T>,
+        V: Into<Option<f64>>,
+        F: Fn(&Iq) -> V,
+    {
+        if !queries.is_sorted() {
+            return Err(SetError::UnsortedSet);
+        }
+        self.records()
+            .iter()
+            .map(|target| {
+                let values = queries
+                    .query_iter(target, query)?
+                    .map(|iv| field(iv).into())
+                    .collect::<Vec<_>>();
+                Ok((target, aggregator.aggregate(&values)))
+            })
+            .collect()
+    }
+
+    /// Collects the query records overlapping each interval of the
+    /// container (the target) according to the [`Query`], and summarizes
+    /// a text field of those records with the [`Aggregator`].
+    ///
+    /// The `field` closure selects the value of each query record, such as
+    /// its name.
+    /// Records are selected and reported as with [`IntervalContainer::map`].
+    ///
+    /// Returns an error if the query container is unsorted or if the
+    /// aggregator is numeric (see [`Aggregator::is_numeric`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{
+    ///     types::{Aggregator, Query},
+    ///     Bed3, Bed4, IntervalContainer,
+    /// };
+    ///
+    /// let peaks = IntervalContainer::from_unsorted(vec![Bed3::new(1, 0, 100)]);
+    /// let genes = IntervalContainer::from_unsorted(vec![
+    ///     Bed4::new(1, 10, 20, "b"),
+    ///     Bed4::new(1, 30, 40, "a"),
+    ///     Bed4::new(1, 50, 60, "b"),
+    /// ]);
+    ///
+    /// let names = peaks
+    ///     .map_text(&genes, Query::default(), Aggregator::Distinct, |g| *g.name())
+    ///     .unwrap();
+    /// assert_eq!(names[0].1.to_string(), "a,b");
+    /// assert!(peaks
+    ///     .map_text(&genes, Query::default(), Aggregator::Sum, |g| *g.name())
+    ///     .is_err());
+    /// ```
+    pub fn map_text<Iq, V, F>(
+        &self,
+        queries: &IntervalContainer<Iq, C, T>,
+        query: Query<T>,
+        aggregator: Aggregator,
+        field: F,
+    ) -> Result<Vec<(&I, AggregateValue)>, AggregateError>
+    where
+        Iq: IntervalBounds<C, T>,
+        V: Display,
+        F: Fn(&Iq) -> V,
+    {
+        if aggregator.is_numeric() {
+            return Err(AggregateError::NumericAggregator { aggregator });
+        }
+        if !queries.is_sorted() {
+            return Err(SetError::UnsortedSet.into());
+        }
+        self.records()
+            .iter()
+            .map(|target| {
+                let values = queries
+                    .query_iter(target, query)?
+                    .map(|iv| field(iv).to_string())
+                    .collect::<Vec<_>>();
+                let value = aggregator.aggregate_text(&values)?;
+                Ok((target, value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        types::{QueryMethod, StrandMethod},
+        Bed3, Bed6, Coordinates, Score, Strand,
+    };
+
+    fn targets() -> IntervalContainer<Bed3<i32, u32>, i32, u32> {
+        IntervalContainer::new(vec![
+            Bed3::new(1, 100, 200),
+            Bed3::new(1, 0, 50),
+            Bed3::new(2, 0, 50),
+        ])
+    }
+
+    fn queries() -> IntervalContainer<Bed6<i32, u32, &'static str>, i32, u32> {
+        IntervalContainer::from_unsorted(vec![
+            Bed6::new(1, 10, 20, "a", Score(Some(1.0)), Strand::Forward),
+            Bed6::new(1, 40, 110, "b", Score(Some(5.0)), Strand::Reverse),
+            Bed6::new(1, 150, 160, "c", Score(None), Strand::Forward),
+            Bed6::new(1, 190, 250, "a", Score(Some(3.0)), Strand::Forward),
+        ])
+    }
+
+    fn values(result: &[(&Bed3<i32, u32>, AggregateValue)]) -> Vec<(u32, String)> {
+        result
+            .iter()
+            .map(|(iv, value)| (iv.start(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn map_aggregators() {
+        let targets = targets();
+        let queries = queries();
+        let map = |agg| {
+            let result = targets
+                .map(&queries, Query::default(), agg, Bed6::score)
+                .unwrap();
+            values(&result)
+        };
+        assert_eq!(
+            map(Aggregator::Count),
+            vec![(100, "3".into()), (0, "2".into()), (0, "0".into())]
+        );
+        assert_eq!(
+            map(Aggregator::Sum),
+            vec![(100, "8".into()), (0, "6".into()), (0, ".".into())]
+        );
+        assert_eq!(
+            map(Aggregator::Max),
+            vec![(100, "5".into()), (0, "5".into()), (0, ".".into())]
+        );
+        assert_eq!(
+            map(Aggregator::Collapse),
+            vec![(100, "5,.,3".into()), (0, "1,5".into()), (0, ".".into())]
+        );
+        assert_eq!(
+            map(Aggregator::First),
+            vec![(100, "5".into()), (0, "1".into()), (0, ".".into())]
+        );
+    }
+
+    #[test]
+    fn map_names() {
+        let targets = targets();
+        let queries = queries();
+        let result = targets
+            .map_text(&queries, Query::default(), Aggregator::Distinct, |q| {
+                *q.name()
+            })
+            .unwrap();
+        assert_eq!(values(&result)[0], (100, "a,b,c".into()));
+        let result = targets
+            .map_text(&queries, Query::default(), Aggregator::Count, |q| *q.name())
+            .unwrap();
+        assert_eq!(values(&result)[1], (0, "2".into()));
+        let result = targets
+            .map_text(&queries, Query::default(), Aggregator::Sum, |q| *q.name())
+            .map(|_| ());
+        assert_eq!(
+            result.unwrap_err(),
+            AggregateError::NumericAggregator {
+                aggregator: Aggregator::Sum
+            }
+        );
+    }
+
+    #[test]
+    fn map_respects_query() {
+        let targets = targets();
+        let queries = queries();
+        // Half of each query record must overlap the target
+        let query = Query::new(
+            QueryMethod::CompareByTargetFraction(0.5),
+            StrandMethod::Ignore,
+        );
+        let result = targets
+            .map_text(&queries, query, Aggregator::Collapse, |q| *q.name())
+            .unwrap();
+        assert_eq!(values(&result)[0], (100, "c".into()));
+
+        let query = Query::new_strandedness(StrandMethod::MatchStrand);
+        let stranded = IntervalContainer::new(vec![Bed6::new(
+            1,
+            0,
+            200,
+            "t",
+            Score(None),
+            Strand::Forward,
+        )]);
+        let result = stranded
+            .map_text(&queries, query, Aggregator::Collapse, |q| *q.name())
+            .unwrap();
+        assert_eq!(result[0].1.to_string(), "a,c,a");
+    }
+
+    #[test]
+    fn map_unsorted_queries() {
+        let queries = IntervalContainer::new(vec![Bed3::new(1, 50, 60), Bed3::new(1, 0, 10)]);
+        let result = targets()
+            .map(&queries, Query::default(), Aggregator::Count, |_| None)
+            .map(|_| ());
+        assert_eq!(result.unwrap_err(), SetError::UnsortedSet);
+        let result = targets()
+            .map_text(&queries, Query::default(), Aggregator::Count, |_| "")
+            .map(|_| ());
+        assert_eq!(result.unwrap_err(), SetError::UnsortedSet.into());
+    }
+}
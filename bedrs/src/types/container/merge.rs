@@ -212,7 +212,7 @@ where
         for iv in self.records() {
             match iv.strand() {
                 // Skip all intervals that have unknown strand
-                None | Some(Strand::Unknown) => continue,
+                None | Some(Strand::Unknown) => {}
 
                 // Forward strand processing
                 Some(Strand::Forward) => {
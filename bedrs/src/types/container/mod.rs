@@ -1,12 +1,20 @@
 mod bound;
 mod closest;
 mod complement;
+mod coverage;
 mod find;
+mod genome_coverage;
 mod internal;
 mod interval_container;
+mod map;
 mod merge;
+mod multi_intersect;
+mod pair;
 mod sample;
 mod set_intersect;
 mod set_segment;
 mod set_subtract;
+mod union_bedgraph;
+pub use coverage::Coverage;
 pub use interval_container::IntervalContainer;
+pub use pair::BedPeContainer;
@@ -0,0 +1,272 @@
+use crate::{
+    traits::{ChromBounds, IntervalBounds, SetError, ValueBounds},
+    Coordinates, IntervalContainer, MultiSegment,
+};
+
+type MultiContainer<C, T> = IntervalContainer<MultiSegment<C, T>, C, T>;
+
+/// Appends a segment covered by the members, extending the previous
+/// segment if it is adjacent and has the same members.
+fn push_segment<C, T>(
+    segments: &mut Vec<MultiSegment<C, T>>,
+    chr: &C,
+    start: T,
+    end: T,
+    members: Vec<usize>,
+    n_sets: usize,
+) where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    if let Some(last) = segments.last_mut() {
+        if last.chr() == chr && last.end() == start && *last.members() == members {
+            last.update_end(&end);
+            return;
+        }
+    }
+    segments.push(MultiSegment::new(chr.clone(), start, end, members, n_sets));
+}
+
+/// Sweeps over the intervals of every set on a single chromosome,
+/// appending the segments covered by at least one set.
+fn sweep_chrom<I, C, T>(chr: &C, sets: &[&[I]], segments: &mut Vec<MultiSegment<C, T>>)
+where
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    // Ends sort before starts at the same position as intervals are half-open,
+    // so empty intervals are skipped as they would be closed before opening
+    let mut events: Vec<(T, bool, usize)> = sets
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, records)| {
+            records
+                .iter()
+                .filter(|iv| iv.start() < iv.end())
+                .flat_map(move |iv| [(iv.start(), true, idx), (iv.end(), false, idx)])
+        })
+        .collect();
+    events.sort_unstable();
+
+    let mut depths = vec![0usize; sets.len()];
+    let mut last = None;
+    for (pos, is_start, idx) in events {
+        if let Some(start) = last.filter(|&start| start < pos) {
+            let members: Vec<usize> = (0..sets.len()).filter(|&i| depths[i] > 0).collect();
+            if !members.is_empty() {
+                push_segment(segments, chr, start, pos, members, sets.len());
+            }
+        }
+        if is_start {
+            depths[idx] += 1;
+        } else {
+            depths[idx] -= 1;
+        }
+        last = Some(pos);
+    }
+}
+
+/// Intersects multiple interval containers at once, mirroring
+/// `bedtools multiinter`.
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Splits the intervals of all sets into elementary segments, each
+    /// annotated with the indices of the sets covering it as a
+    /// [`MultiSegment`].
+    ///
+    /// Adjacent segments covered by the same sets are joined and regions
+    /// not covered by any set are not reported.
+    /// Zero-length intervals cover no bases and are ignored.
+    /// Consensus regions are those covered by every set
+    /// ([`MultiSegment::is_shared`]), and set specific regions those
+    /// covered by a single set ([`MultiSegment::is_unique_to`]).
+    ///
+    /// Returns an error if any of the sets is unsorted.
+    ///
+    /// ```text
+    /// (0)    i--------j       k----l
+    /// (1)        m--------n
+    /// (2)            o-----------------p
+    /// ======================================
+    /// (a)    i---m                        {0}
+    /// (b)        m---o                    {0, 1}
+    /// (c)            o---j                {0, 1, 2}
+    /// (d)                j----n           {1, 2}
+    /// (e)                     n---k       {2}
+    /// (f)                         k----l  {0, 2}
+    /// (g)                              l--p  {2}
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{Bed3, Coordinates, IntervalContainer};
+    ///
+    /// let sets = vec![
+    ///     IntervalContainer::from_unsorted(vec![Bed3::new(1, 10, 30)]),
+    ///     IntervalContainer::from_unsorted(vec![Bed3::new(1, 20, 40)]),
+    /// ];
+    ///
+    /// let segments = IntervalContainer::multi_intersect(&sets).unwrap();
+    /// let observed: Vec<_> = segments
+    ///     .records()
+    ///     .iter()
+    ///     .map(|seg| (seg.start(), seg.end(), seg.members().clone()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     observed,
+    ///     vec![(10, 20, vec![0]), (20, 30, vec![0, 1]), (30, 40, vec![1])]
+    /// );
+    ///
+    /// let consensus: Vec<_> = segments.records().iter().filter(|s| s.is_shared()).collect();
+    /// assert_eq!(consensus.len(), 1);
+    /// ```
+    pub fn multi_intersect(sets: &[Self]) -> Result<MultiContainer<C, T>, SetError> {
+        if sets.iter().any(|set| !set.is_sorted()) {
+            return Err(SetError::UnsortedSet);
+        }
+
+        let mut chroms: Vec<&C> = sets
+            .iter()
+            .flat_map(|set| {
+                set.records()
+                    .chunk_by(|a, b| a.chr() == b.chr())
+                    .map(|chunk| chunk[0].chr())
+            })
+            .collect();
+        chroms.sort_unstable();
+        chroms.dedup();
+
+        let mut segments = Vec::new();
+        for chr in chroms {
+            let slices: Vec<&[I]> = sets
+                .iter()
+                .map(|set| {
+                    let records = set.records();
+                    let lower = records.partition_point(|iv| iv.chr() < chr);
+                    let upper = records.partition_point(|iv| iv.chr() <= chr);
+                    &records[lower..upper]
+                })
+                .collect();
+            sweep_chrom(chr, &slices, &mut segments);
+        }
+        Ok(IntervalContainer::from_sorted_unchecked(segments))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::Bed3;
+
+    type Observed = Vec<(i32, u32, u32, Vec<usize>)>;
+
+    fn observed(segments: &MultiContainer<i32, u32>) -> Observed {
+        segments
+            .records()
+            .iter()
+            .map(|seg| (*seg.chr(), seg.start(), seg.end(), seg.members().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn multi_intersect_three_sets() {
+        let sets = vec![
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 10, 50), Bed3::new(1, 70, 80)]),
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 20, 60)]),
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 30, 90)]),
+        ];
+        let segments = IntervalContainer::multi_intersect(&sets).unwrap();
+        assert_eq!(
+            observed(&segments),
+            vec![
+                (1, 10, 20, vec![0]),
+                (1, 20, 30, vec![0, 1]),
+                (1, 30, 50, vec![0, 1, 2]),
+                (1, 50, 60, vec![1, 2]),
+                (1, 60, 70, vec![2]),
+                (1, 70, 80, vec![0, 2]),
+                (1, 80, 90, vec![2]),
+            ]
+        );
+        assert!(segments.records().iter().all(|s| s.n_sets() == 3));
+        assert_eq!(
+            segments.records().iter().filter(|s| s.is_shared()).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn multi_intersect_overlaps_within_set() {
+        let sets = vec![
+            IntervalContainer::from_unsorted(vec![
+                Bed3::new(1, 10, 30),
+                Bed3::new(1, 20, 40),
+                Bed3::new(1, 40, 50),
+            ]),
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 60, 70)]),
+        ];
+        let segments = IntervalContainer::multi_intersect(&sets).unwrap();
+        assert_eq!(
+            observed(&segments),
+            vec![(1, 10, 50, vec![0]), (1, 60, 70, vec![1])]
+        );
+    }
+
+    #[test]
+    fn multi_intersect_chromosomes() {
+        let sets = vec![
+            IntervalContainer::from_unsorted(vec![Bed3::new(2, 10, 20), Bed3::new(3, 0, 5)]),
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 0, 10), Bed3::new(2, 15, 25)]),
+        ];
+        let segments = IntervalContainer::multi_intersect(&sets).unwrap();
+        assert_eq!(
+            observed(&segments),
+            vec![
+                (1, 0, 10, vec![1]),
+                (2, 10, 15, vec![0]),
+                (2, 15, 20, vec![0, 1]),
+                (2, 20, 25, vec![1]),
+                (3, 0, 5, vec![0]),
+            ]
+        );
+        assert!(segments.is_sorted());
+    }
+
+    #[test]
+    fn multi_intersect_zero_length() {
+        let sets = vec![
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 5, 5), Bed3::new(1, 20, 30)]),
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 0, 10), Bed3::new(1, 25, 25)]),
+        ];
+        let segments = IntervalContainer::multi_intersect(&sets).unwrap();
+        assert_eq!(
+            observed(&segments),
+            vec![(1, 0, 10, vec![1]), (1, 20, 30, vec![0])]
+        );
+    }
+
+    #[test]
+    fn multi_intersect_empty() {
+        let sets: Vec<IntervalContainer<Bed3<i32, u32>, i32, u32>> = vec![];
+        let segments = IntervalContainer::multi_intersect(&sets).unwrap();
+        assert!(segments.is_empty());
+    }
+
+    #[test]
+    fn multi_intersect_unsorted() {
+        let sets = vec![
+            IntervalContainer::from_unsorted(vec![Bed3::new(1, 10, 20)]),
+            IntervalContainer::new(vec![Bed3::new(1, 30, 40), Bed3::new(1, 10, 20)]),
+        ];
+        assert_eq!(
+            IntervalContainer::multi_intersect(&sets).unwrap_err(),
+            SetError::UnsortedSet
+        );
+    }
+}
@@ -0,0 +1,427 @@
+use crate::{
+    traits::{ChromBounds, IntervalBounds, MetaBounds, SetError, ValueBounds},
+    types::{PairMethod, Query, Score},
+    Bed6, BedPe, Coordinates, IntervalContainer,
+};
+
+/// The pairs overlapping a query pair if it is reported
+type PairHits<'a, C, T, N> = Option<Vec<&'a BedPe<C, T, N>>>;
+
+/// Returns whether a pair is reported given whether its first and second
+/// anchor overlap
+fn is_reported(method: PairMethod, first: bool, second: bool) -> bool {
+    match method {
+        PairMethod::Either => first || second,
+        PairMethod::Both => first && second,
+        PairMethod::Neither => !first && !second,
+        PairMethod::NotBoth => !(first && second),
+    }
+}
+
+/// Finds the intervals overlapping the anchors of a [`BedPe`] record,
+/// mirroring `bedtools pairtobed`.
+///
+/// The pair is reported if the overlaps of its anchors satisfy the
+/// [`PairMethod`], in which case all intervals overlapping either anchor
+/// are returned (without duplicates and in container order).
+/// Pairs reported with [`PairMethod::Neither`] have no overlapping
+/// intervals.
+/// Returns `None` if the pair is not reported.
+///
+/// Overlaps (including strand rules) between each anchor and the
+/// intervals are determined by the [`Query`].
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{types::Query, Bed3, BedPe, IntervalContainer, PairMethod, Score, Strand, StrandedBed3};
+///
+/// let set = IntervalContainer::from_unsorted(vec![
+///     Bed3::new(1, 10, 20),
+///     Bed3::new(1, 500, 600),
+/// ]);
+/// let pair = BedPe::new(
+///     StrandedBed3::new(1, 15, 30, Strand::Forward),
+///     StrandedBed3::new(1, 300, 400, Strand::Reverse),
+///     "pair",
+///     Score(None),
+/// );
+///
+/// let hits = set.pair_to_bed(&pair, PairMethod::Either, Query::default()).unwrap();
+/// assert_eq!(hits.unwrap().len(), 1);
+///
+/// let hits = set.pair_to_bed(&pair, PairMethod::Both, Query::default()).unwrap();
+/// assert!(hits.is_none());
+/// ```
+impl<I, C, T> IntervalContainer<I, C, T>
+where
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    pub fn pair_to_bed<N>(
+        &self,
+        pair: &BedPe<C, T, N>,
+        method: PairMethod,
+        query: Query<T>,
+    ) -> Result<Option<Vec<&I>>, SetError>
+    where
+        N: MetaBounds,
+    {
+        query.validate()?;
+        let first: Vec<usize> = self
+            .query_iter_enumerate(pair.first(), query)?
+            .map(|(idx, _)| idx)
+            .collect();
+        let second: Vec<usize> = self
+            .query_iter_enumerate(pair.second(), query)?
+            .map(|(idx, _)| idx)
+            .collect();
+        if !is_reported(method, !first.is_empty(), !second.is_empty()) {
+            return Ok(None);
+        }
+        let mut indices = first;
+        indices.extend(second);
+        indices.sort_unstable();
+        indices.dedup();
+        let hits = indices
+            .into_iter()
+            .map(|idx| &self.records()[idx])
+            .collect();
+        Ok(Some(hits))
+    }
+}
+
+/// A collection of [`BedPe`] records indexed by their anchors for
+/// pair-to-pair overlap queries, mirroring `bedtools pairtopair`.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{types::Query, BedPe, BedPeContainer, PairMethod, Score, Strand, StrandedBed3};
+///
+/// let pair = |s1, e1, s2, e2| {
+///     BedPe::new(
+///         StrandedBed3::new(1, s1, e1, Strand::Unknown),
+///         StrandedBed3::new(1, s2, e2, Strand::Unknown),
+///         0,
+///         Score(None),
+///     )
+/// };
+///
+/// let set = BedPeContainer::new(vec![pair(10, 20, 100, 200), pair(10, 20, 1000, 1100)]);
+///
+/// let hits = set
+///     .pair_to_pair(&pair(15, 25, 150, 250), PairMethod::Both, Query::default())
+///     .unwrap();
+/// assert_eq!(hits.unwrap().len(), 1);
+///
+/// let hits = set
+///     .pair_to_pair(&pair(15, 25, 150, 250), PairMethod::Either, Query::default())
+///     .unwrap();
+/// assert_eq!(hits.unwrap().len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BedPeContainer<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    pairs: Vec<BedPe<C, T, N>>,
+    anchors: IntervalContainer<Bed6<C, T, usize>, C, T>,
+}
+
+impl<C, T, N> BedPeContainer<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    #[must_use]
+    pub fn new(pairs: Vec<BedPe<C, T, N>>) -> Self {
+        let anchors = pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, pair)| {
+                [pair.first(), pair.second()].map(|anchor| {
+                    Bed6::new(
+                        anchor.chr().clone(),
+                        anchor.start(),
+                        anchor.end(),
+                        idx,
+                        Score(None),
+                        anchor.strand().unwrap_or_default(),
+                    )
+                })
+            })
+            .collect();
+        Self {
+            pairs,
+            anchors: IntervalContainer::from_unsorted(anchors),
+        }
+    }
+
+    #[must_use]
+    pub fn pairs(&self) -> &[BedPe<C, T, N>] {
+        &self.pairs
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Finds the pairs overlapping a query pair.
+    ///
+    /// Two pairs overlap with both anchors if each anchor of the query
+    /// overlaps a different anchor of the target (in either orientation).
+    ///
+    /// The query pair is reported if the overlaps satisfy the
+    /// [`PairMethod`]:
+    /// - [`PairMethod::Either`] returns the pairs overlapping at least one
+    ///   anchor of the query.
+    /// - [`PairMethod::Both`] returns the pairs overlapping both anchors of
+    ///   the query.
+    /// - [`PairMethod::Neither`] returns no pairs and reports the query only
+    ///   if no pair overlaps any of its anchors.
+    /// - [`PairMethod::NotBoth`] returns the pairs overlapping at least one
+    ///   anchor of the query and reports the query only if no pair overlaps
+    ///   both of its anchors.
+    ///
+    /// Returns `None` if the query pair is not reported.
+    pub fn pair_to_pair<M>(
+        &self,
+        pair: &BedPe<C, T, M>,
+        method: PairMethod,
+        query: Query<T>,
+    ) -> Result<PairHits<'_, C, T, N>, SetError>
+    where
+        M: MetaBounds,
+    {
+        query.validate()?;
+        let mut candidates = Vec::new();
+        for anchor in [pair.first(), pair.second()] {
+            candidates.extend(
+                self.anchors
+                    .query_iter_owned(anchor.clone(), query)?
+                    .map(|iv| *iv.name()),
+            );
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut either = Vec::new();
+        let mut both = Vec::new();
+        for idx in candidates {
+            let target = &self.pairs[idx];
+            let (any, all) = pair.pair_overlaps(target, query);
+            if any {
+                either.push(target);
+            }
+            if all {
+                both.push(target);
+            }
+        }
+        let hits = match method {
+            PairMethod::Either => Some(either).filter(|x| !x.is_empty()),
+            PairMethod::Both => Some(both).filter(|x| !x.is_empty()),
+            PairMethod::Neither => either.is_empty().then(Vec::new),
+            PairMethod::NotBoth => both.is_empty().then_some(either),
+        };
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{types::StrandMethod, Bed3, Strand, StrandedBed3};
+
+    fn build_pair(s1: u32, e1: u32, s2: u32, e2: u32) -> BedPe<u32, u32, u32> {
+        BedPe::new(
+            StrandedBed3::new(1, s1, e1, Strand::Forward),
+            StrandedBed3::new(1, s2, e2, Strand::Reverse),
+            s1,
+            Score(None),
+        )
+    }
+
+    fn build_set() -> IntervalContainer<StrandedBed3<u32, u32>, u32, u32> {
+        IntervalContainer::from_unsorted(vec![
+            StrandedBed3::new(1, 10, 20, Strand::Forward),
+            StrandedBed3::new(1, 15, 25, Strand::Reverse),
+            StrandedBed3::new(1, 100, 200, Strand::Reverse),
+            StrandedBed3::new(1, 1000, 2000, Strand::Forward),
+        ])
+    }
+
+    #[test]
+    fn pair_to_bed_either() {
+        let set = build_set();
+        let pair = build_pair(18, 30, 500, 600);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Either, Query::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].start(), 10);
+        assert_eq!(hits[1].start(), 15);
+
+        let pair = build_pair(300, 400, 500, 600);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Either, Query::default())
+            .unwrap();
+        assert!(hits.is_none());
+    }
+
+    #[test]
+    fn pair_to_bed_both() {
+        let set = build_set();
+        let pair = build_pair(18, 30, 150, 1500);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Both, Query::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(hits.len(), 4);
+
+        let pair = build_pair(18, 30, 500, 600);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Both, Query::default())
+            .unwrap();
+        assert!(hits.is_none());
+    }
+
+    #[test]
+    fn pair_to_bed_shared_hit() {
+        let set = IntervalContainer::from_unsorted(vec![Bed3::new(1, 0, 1000)]);
+        let pair = build_pair(10, 20, 100, 200);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Both, Query::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn pair_to_bed_neither_not_both() {
+        let set = build_set();
+        let pair = build_pair(300, 400, 500, 600);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Neither, Query::default())
+            .unwrap();
+        assert_eq!(hits.unwrap().len(), 0);
+
+        let pair = build_pair(18, 30, 500, 600);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Neither, Query::default())
+            .unwrap();
+        assert!(hits.is_none());
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::NotBoth, Query::default())
+            .unwrap();
+        assert_eq!(hits.unwrap().len(), 2);
+
+        let pair = build_pair(18, 30, 150, 1500);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::NotBoth, Query::default())
+            .unwrap();
+        assert!(hits.is_none());
+    }
+
+    #[test]
+    fn pair_to_bed_stranded() {
+        let set = build_set();
+        let pair = build_pair(18, 30, 150, 1500);
+        let query = Query::new_strandedness(StrandMethod::MatchStrand);
+        let hits = set
+            .pair_to_bed(&pair, PairMethod::Either, query)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].start(), 10);
+        assert_eq!(hits[1].start(), 100);
+    }
+
+    #[test]
+    fn pair_to_bed_unsorted() {
+        let set = IntervalContainer::new(vec![Bed3::new(1, 100, 200), Bed3::new(1, 10, 20)]);
+        let pair = build_pair(18, 30, 150, 1500);
+        assert!(set
+            .pair_to_bed(&pair, PairMethod::Either, Query::default())
+            .is_err());
+    }
+
+    #[test]
+    fn pair_to_pair_methods() {
+        let set = BedPeContainer::new(vec![
+            build_pair(10, 20, 100, 200),
+            build_pair(150, 250, 15, 25),
+            build_pair(10, 20, 1000, 1100),
+            build_pair(5000, 6000, 7000, 8000),
+        ]);
+        assert_eq!(set.len(), 4);
+        let pair = build_pair(15, 25, 150, 250);
+
+        let hits = set
+            .pair_to_pair(&pair, PairMethod::Both, Query::default())
+            .unwrap()
+            .unwrap();
+        let names: Vec<_> = hits.iter().map(|x| *x.name()).collect();
+        assert_eq!(names, vec![10, 150]);
+
+        let hits = set
+            .pair_to_pair(&pair, PairMethod::Either, Query::default())
+            .unwrap()
+            .unwrap();
+        let names: Vec<_> = hits.iter().map(|x| *x.name()).collect();
+        assert_eq!(names, vec![10, 150, 10]);
+
+        assert!(set
+            .pair_to_pair(&pair, PairMethod::Neither, Query::default())
+            .unwrap()
+            .is_none());
+        assert!(set
+            .pair_to_pair(&pair, PairMethod::NotBoth, Query::default())
+            .unwrap()
+            .is_none());
+
+        let pair = build_pair(15, 25, 3000, 4000);
+        let hits = set
+            .pair_to_pair(&pair, PairMethod::NotBoth, Query::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(hits.len(), 3);
+        assert!(set
+            .pair_to_pair(&pair, PairMethod::Both, Query::default())
+            .unwrap()
+            .is_none());
+
+        let pair = build_pair(3000, 4000, 9000, 9100);
+        let hits = set
+            .pair_to_pair(&pair, PairMethod::Neither, Query::default())
+            .unwrap();
+        assert_eq!(hits.unwrap().len(), 0);
+    }
+
+    #[test]
+    fn pair_to_pair_stranded() {
+        let set = BedPeContainer::new(vec![
+            build_pair(10, 20, 100, 200),
+            build_pair(150, 250, 15, 25),
+        ]);
+        let pair = build_pair(15, 25, 150, 250);
+        let query = Query::new_strandedness(StrandMethod::MatchStrand);
+        let hits = set
+            .pair_to_pair(&pair, PairMethod::Both, query)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(*hits[0].name(), 10);
+    }
+}
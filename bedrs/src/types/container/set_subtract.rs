@@ -42,7 +42,10 @@ where
     ///
     /// assert!(subset.next().is_none());
     /// ```
-    pub fn subtract<'a, Iv>(&'a self, query: &'a Iv) -> Result<SubtractIter<I, Iv, C, T>, SetError>
+    pub fn subtract<'a, Iv>(
+        &'a self,
+        query: &'a Iv,
+    ) -> Result<SubtractIter<'a, I, Iv, C, T>, SetError>
     where
         Iv: IntervalBounds<C, T>,
     {
@@ -56,7 +59,7 @@ where
     /// Unchecked version of [subtract](Self::subtract).
     ///
     /// Does not check if the container is sorted
-    pub fn subtract_unchecked<'a, Iv>(&'a self, query: &'a Iv) -> SubtractIter<I, Iv, C, T>
+    pub fn subtract_unchecked<'a, Iv>(&'a self, query: &'a Iv) -> SubtractIter<'a, I, Iv, C, T>
     where
         Iv: IntervalBounds<C, T>,
     {
@@ -0,0 +1,119 @@
+use crate::{
+    traits::{ChromBounds, SetError, ValueBounds},
+    types::UnionBedGraphIter,
+    BedGraph, IntervalContainer,
+};
+use std::{iter::Cloned, slice::Iter};
+
+type UnionBedGraphIterRef<'a, C, T> = UnionBedGraphIter<Cloned<Iter<'a, BedGraph<C, T>>>, C, T>;
+
+/// Unions multiple `BedGraph` tracks onto their shared breakpoints,
+/// mirroring `bedtools unionbedg`.
+impl<C, T> IntervalContainer<BedGraph<C, T>, C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Generates an iterator over the union of the tracks, yielding the
+    /// score of every track for each interval covered by at least one
+    /// track, with `fill` for tracks without a record over the interval.
+    ///
+    /// See the [`UnionBedGraphIter`] to stream tracks that are not held
+    /// in memory, such as those read from files.
+    ///
+    /// Returns an error if any of the tracks is unsorted.
+    ///
+    /// ```text
+    /// (0)    i--------j----k
+    /// (1)        l-------------m
+    /// ==============================
+    /// (a)    i---l                 [0, fill]
+    /// (b)        l----j            [0, 1]
+    /// (c)             j----k       [0, 1]
+    /// (d)                  k---m   [fill, 1]
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bedrs::{BedGraph, Coordinates, IntervalContainer};
+    ///
+    /// let tracks = vec![
+    ///     IntervalContainer::from_unsorted(vec![
+    ///         BedGraph::new(1, 10, 30, 1.0),
+    ///         BedGraph::new(1, 30, 40, 2.0),
+    ///     ]),
+    ///     IntervalContainer::from_unsorted(vec![BedGraph::new(1, 20, 50, 5.0)]),
+    /// ];
+    ///
+    /// let rows: Vec<_> = IntervalContainer::union_bedgraph(&tracks, f64::NAN)
+    ///     .unwrap()
+    ///     .map(|row| format!("{row}"))
+    ///     .collect();
+    /// assert_eq!(
+    ///     rows,
+    ///     vec![
+    ///         "1\t10\t20\t1\tNaN",
+    ///         "1\t20\t30\t1\t5",
+    ///         "1\t30\t40\t2\t5",
+    ///         "1\t40\t50\tNaN\t5",
+    ///     ]
+    /// );
+    /// ```
+    pub fn union_bedgraph(
+        tracks: &[Self],
+        fill: f64,
+    ) -> Result<UnionBedGraphIterRef<'_, C, T>, SetError> {
+        if tracks.iter().any(|track| !track.is_sorted()) {
+            return Err(SetError::UnsortedSet);
+        }
+        let iters = tracks
+            .iter()
+            .map(|track| track.records().iter().cloned())
+            .collect();
+        Ok(UnionBedGraphIter::new(iters, fill))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+    use crate::Coordinates;
+
+    #[test]
+    fn union_bedgraph_containers() {
+        let tracks = vec![
+            IntervalContainer::from_unsorted(vec![
+                BedGraph::new(2, 0, 10, 3.0),
+                BedGraph::new(1, 0, 10, 1.0),
+            ]),
+            IntervalContainer::from_unsorted(vec![BedGraph::new(1, 5, 15, 2.0)]),
+        ];
+        let rows: Vec<_> = IntervalContainer::union_bedgraph(&tracks, 0.0)
+            .unwrap()
+            .map(|row| (*row.chr(), row.start(), row.end(), row.scores().clone()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (1, 0, 5, vec![1.0, 0.0]),
+                (1, 5, 10, vec![1.0, 2.0]),
+                (1, 10, 15, vec![0.0, 2.0]),
+                (2, 0, 10, vec![3.0, 0.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_bedgraph_unsorted() {
+        let tracks = vec![IntervalContainer::new(vec![
+            BedGraph::new(1, 20, 30, 1.0),
+            BedGraph::new(1, 0, 10, 1.0),
+        ])];
+        assert!(matches!(
+            IntervalContainer::union_bedgraph(&tracks, 0.0),
+            Err(SetError::UnsortedSet)
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::traits::AggregateError;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+/// The operation summarizing the values of the query records overlapping
+/// a target.
+///
+/// Mirrors the `-o` option of `bedtools map`.
+///
+/// Numeric values are summarized with [`Aggregator::aggregate`], where
+/// missing values (`None`) are counted but otherwise ignored.
+/// Text values are summarized with [`Aggregator::aggregate_text`], which
+/// only supports the operations that are not numeric.
+///
+/// ```
+/// use bedrs::types::{AggregateValue, Aggregator};
+///
+/// let values = [Some(3.0), Some(1.0), None, Some(2.0), Some(1.0)];
+/// assert_eq!(Aggregator::Count.aggregate(&values), AggregateValue::Count(5));
+/// assert_eq!(Aggregator::Mean.aggregate(&values), AggregateValue::Number(1.75));
+/// assert_eq!(Aggregator::Median.aggregate(&values), AggregateValue::Number(1.5));
+/// assert_eq!(Aggregator::Distinct.aggregate(&values).to_string(), "1,2,3");
+/// assert_eq!(Aggregator::Collapse.aggregate(&values).to_string(), "3,1,.,2,1");
+/// assert_eq!(Aggregator::Sum.aggregate(&[]), AggregateValue::Missing);
+///
+/// let names = ["b", "a", "b"];
+/// assert_eq!(Aggregator::Distinct.aggregate_text(&names).unwrap().to_string(), "a,b");
+/// assert!(Aggregator::Sum.aggregate_text(&names).is_err());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregator {
+    /// The number of overlapping records
+    #[default]
+    Count,
+    /// The sum of the values
+    Sum,
+    /// The mean of the values
+    Mean,
+    /// The median of the values
+    Median,
+    /// The smallest value
+    Min,
+    /// The largest value
+    Max,
+    /// The sorted unique values, comma separated
+    Distinct,
+    /// All values in order, comma separated
+    Collapse,
+    /// The value of the first overlapping record
+    First,
+    /// The value of the last overlapping record
+    Last,
+}
+
+/// The result of an [`Aggregator`].
+///
+/// Operations other than [`Aggregator::Count`] over no values are
+/// [`AggregateValue::Missing`], which is written as `.`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateValue {
+    /// A number of records
+    Count(usize),
+    /// The result of a numeric operation
+    Number(f64),
+    /// The result of a text operation
+    Text(String),
+    /// No values were available
+    Missing,
+}
+
+impl AggregateValue {
+    /// Returns the value as a number, if it is numeric
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AggregateValue::Count(n) => Some(*n as f64),
+            AggregateValue::Number(x) => Some(*x),
+            AggregateValue::Text(_) | AggregateValue::Missing => None,
+        }
+    }
+
+    /// Whether no values were available
+    #[must_use]
+    pub fn is_missing(&self) -> bool {
+        matches!(self, AggregateValue::Missing)
+    }
+}
+
+impl Display for AggregateValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateValue::Count(n) => write!(f, "{n}"),
+            AggregateValue::Number(x) => write!(f, "{x}"),
+            AggregateValue::Text(s) => write!(f, "{s}"),
+            AggregateValue::Missing => write!(f, "."),
+        }
+    }
+}
+
+/// Joins the values with commas, or [`AggregateValue::Missing`] if there
+/// are none
+fn join<S: AsRef<str>>(values: &[S]) -> AggregateValue {
+    if values.is_empty() {
+        AggregateValue::Missing
+    } else {
+        let values: Vec<&str> = values.iter().map(AsRef::as_ref).collect();
+        AggregateValue::Text(values.join(","))
+    }
+}
+
+impl Aggregator {
+    /// Whether the operation requires numeric values
+    #[must_use]
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Aggregator::Sum
+                | Aggregator::Mean
+                | Aggregator::Median
+                | Aggregator::Min
+                | Aggregator::Max
+        )
+    }
+
+    /// Summarizes numeric values in the order of their records, where
+    /// `None` is a record without a value
+    #[must_use]
+    pub fn aggregate(&self, values: &[Option<f64>]) -> AggregateValue {
+        let number = |value: Option<&Option<f64>>| {
+            value
+                .copied()
+                .flatten()
+                .map_or(AggregateValue::Missing, AggregateValue::Number)
+        };
+        let mut numbers: Vec<f64> = values.iter().copied().flatten().collect();
+        match self {
+            Aggregator::Count => AggregateValue::Count(values.len()),
+            Aggregator::First => number(values.first()),
+            Aggregator::Last => number(values.last()),
+            Aggregator::Collapse => join(
+                &values
+                    .iter()
+                    .map(|value| value.map_or_else(|| ".".to_string(), |x| x.to_string()))
+                    .collect::<Vec<_>>(),
+            ),
+            Aggregator::Distinct => {
+                numbers.sort_unstable_by(f64::total_cmp);
+                numbers.dedup();
+                join(&numbers.iter().map(f64::to_string).collect::<Vec<_>>())
+            }
+            Aggregator::Sum | Aggregator::Mean | Aggregator::Median => {
+                if numbers.is_empty() {
+                    return AggregateValue::Missing;
+                }
+                AggregateValue::Number(self.summarize(&mut numbers))
+            }
+            Aggregator::Min | Aggregator::Max => {
+                let fold = if *self == Aggregator::Min {
+                    f64::min
+                } else {
+                    f64::max
+                };
+                numbers
+                    .into_iter()
+                    .reduce(fold)
+                    .map_or(AggregateValue::Missing, AggregateValue::Number)
+            }
+        }
+    }
+
+    /// Summarizes text values in the order of their records.
+    ///
+    /// Returns an error for the numeric operations (see
+    /// [`Aggregator::is_numeric`]).
+    pub fn aggregate_text<S: AsRef<str>>(
+        &self,
+        values: &[S],
+    ) -> Result<AggregateValue, AggregateError> {
+        let text = |value: Option<&S>| {
+            value.map_or(AggregateValue::Missing, |v| {
+                AggregateValue::Text(v.as_ref().to_string())
+            })
+        };
+        let value = match self {
+            Aggregator::Count => AggregateValue::Count(values.len()),
+            Aggregator::First => text(values.first()),
+            Aggregator::Last => text(values.last()),
+            Aggregator::Collapse => join(values),
+            Aggregator::Distinct => join(
+                &values
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            ),
+            _ => return Err(AggregateError::NumericAggregator { aggregator: *self }),
+        };
+        Ok(value)
+    }
+
+    /// Computes the sum, mean or median of a non-empty set of numbers
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::manual_is_multiple_of,
+        clippy::manual_midpoint
+    )]
+    fn summarize(self, numbers: &mut [f64]) -> f64 {
+        let sum: f64 = numbers.iter().sum();
+        match self {
+            Aggregator::Mean => sum / numbers.len() as f64,
+            Aggregator::Median => {
+                numbers.sort_unstable_by(f64::total_cmp);
+                let mid = numbers.len() / 2;
+                if numbers.len() % 2 == 0 {
+                    (numbers[mid - 1] + numbers[mid]) / 2.0
+                } else {
+                    numbers[mid]
+                }
+            }
+            _ => sum,
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_numeric() {
+        let values = [Some(2.5), None, Some(-1.0), Some(4.0)];
+        let number = |agg: Aggregator| agg.aggregate(&values).as_f64();
+        assert_eq!(number(Aggregator::Sum), Some(5.5));
+        assert_eq!(number(Aggregator::Min), Some(-1.0));
+        assert_eq!(number(Aggregator::Max), Some(4.0));
+        assert_eq!(number(Aggregator::Median), Some(2.5));
+        assert_eq!(number(Aggregator::Count), Some(4.0));
+        assert_eq!(number(Aggregator::First), Some(2.5));
+        assert_eq!(number(Aggregator::Last), Some(4.0));
+    }
+
+    #[test]
+    fn test_numeric_text() {
+        let values = [Some(10.0), Some(9.0), None, Some(10.0)];
+        let text = |agg: Aggregator| agg.aggregate(&values).to_string();
+        assert_eq!(text(Aggregator::Distinct), "9,10");
+        assert_eq!(text(Aggregator::Collapse), "10,9,.,10");
+    }
+
+    #[test]
+    fn test_text() {
+        let values = ["b", "a", "b"];
+        let text = |agg: Aggregator| agg.aggregate_text(&values).unwrap().to_string();
+        assert_eq!(text(Aggregator::First), "b");
+        assert_eq!(text(Aggregator::Last), "b");
+        assert_eq!(text(Aggregator::Distinct), "a,b");
+        assert_eq!(text(Aggregator::Collapse), "b,a,b");
+        assert_eq!(text(Aggregator::Count), "3");
+    }
+
+    #[test]
+    fn test_missing() {
+        for agg in [
+            Aggregator::Sum,
+            Aggregator::Mean,
+            Aggregator::Median,
+            Aggregator::Min,
+            Aggregator::Max,
+            Aggregator::Distinct,
+            Aggregator::Collapse,
+            Aggregator::First,
+            Aggregator::Last,
+        ] {
+            assert!(agg.aggregate(&[]).is_missing());
+        }
+        for agg in [
+            Aggregator::Distinct,
+            Aggregator::Collapse,
+            Aggregator::First,
+            Aggregator::Last,
+        ] {
+            assert!(agg.aggregate_text::<&str>(&[]).unwrap().is_missing());
+        }
+        assert_eq!(Aggregator::Count.aggregate(&[]), AggregateValue::Count(0));
+        assert!(Aggregator::Mean.aggregate(&[None]).is_missing());
+        assert!(Aggregator::First.aggregate(&[None, Some(1.0)]).is_missing());
+        assert_eq!(AggregateValue::Missing.to_string(), ".");
+    }
+
+    #[test]
+    fn test_numeric_aggregator_on_text() {
+        assert!(Aggregator::Sum.is_numeric());
+        assert!(!Aggregator::Collapse.is_numeric());
+        assert_eq!(
+            Aggregator::Sum.aggregate_text(&["1", "gene"]).unwrap_err(),
+            AggregateError::NumericAggregator {
+                aggregator: Aggregator::Sum
+            }
+        );
+    }
+}
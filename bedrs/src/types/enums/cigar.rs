@@ -0,0 +1,251 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single operation of a SAM CIGAR string
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CigarOp {
+    /// `M` - alignment match (sequence match or mismatch)
+    Match,
+    /// `I` - insertion to the reference
+    Insertion,
+    /// `D` - deletion from the reference
+    Deletion,
+    /// `N` - skipped region from the reference (e.g. an intron)
+    Skip,
+    /// `S` - soft clipping
+    SoftClip,
+    /// `H` - hard clipping
+    HardClip,
+    /// `P` - padding
+    Padding,
+    /// `=` - sequence match
+    SequenceMatch,
+    /// `X` - sequence mismatch
+    SequenceMismatch,
+}
+
+impl CigarOp {
+    /// Whether the operation advances along the reference
+    #[must_use]
+    pub fn consumes_reference(&self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match
+                | CigarOp::Deletion
+                | CigarOp::Skip
+                | CigarOp::SequenceMatch
+                | CigarOp::SequenceMismatch
+        )
+    }
+
+    /// Whether the operation aligns query bases to reference bases
+    #[must_use]
+    pub fn is_aligned(&self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match | CigarOp::SequenceMatch | CigarOp::SequenceMismatch
+        )
+    }
+}
+
+impl TryFrom<char> for CigarOp {
+    type Error = &'static str;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'M' => Ok(CigarOp::Match),
+            'I' => Ok(CigarOp::Insertion),
+            'D' => Ok(CigarOp::Deletion),
+            'N' => Ok(CigarOp::Skip),
+            'S' => Ok(CigarOp::SoftClip),
+            'H' => Ok(CigarOp::HardClip),
+            'P' => Ok(CigarOp::Padding),
+            '=' => Ok(CigarOp::SequenceMatch),
+            'X' => Ok(CigarOp::SequenceMismatch),
+            _ => Err("CIGAR operation must be one of MIDNSHP=X"),
+        }
+    }
+}
+
+impl Display for CigarOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            CigarOp::Match => 'M',
+            CigarOp::Insertion => 'I',
+            CigarOp::Deletion => 'D',
+            CigarOp::Skip => 'N',
+            CigarOp::SoftClip => 'S',
+            CigarOp::HardClip => 'H',
+            CigarOp::Padding => 'P',
+            CigarOp::SequenceMatch => '=',
+            CigarOp::SequenceMismatch => 'X',
+        };
+        write!(f, "{c}")
+    }
+}
+
+/// A parsed SAM CIGAR string.
+///
+/// An unavailable CIGAR (`*`) is parsed as an empty list of operations.
+///
+/// ```
+/// use bedrs::types::{Cigar, CigarOp};
+///
+/// let cigar: Cigar = "5S10M100N20M2D5M".parse().unwrap();
+/// assert_eq!(cigar.ops()[0], (5, CigarOp::SoftClip));
+/// assert_eq!(cigar.reference_len(), 137);
+/// assert_eq!(cigar.blocks(), vec![(0, 10), (110, 130), (132, 137)]);
+/// ```
+#[derive(Debug, PartialEq, Clone, Default, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cigar(Vec<(u32, CigarOp)>);
+
+impl Cigar {
+    /// Returns the lengths and operations in order
+    #[must_use]
+    pub fn ops(&self) -> &[(u32, CigarOp)] {
+        &self.0
+    }
+
+    /// Whether the CIGAR has no operations
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of reference bases spanned by the alignment
+    #[must_use]
+    pub fn reference_len(&self) -> u64 {
+        self.0
+            .iter()
+            .filter(|(_, op)| op.consumes_reference())
+            .map(|(len, _)| u64::from(*len))
+            .sum()
+    }
+
+    /// Returns the aligned blocks as half-open offsets from the alignment
+    /// start.
+    ///
+    /// Blocks are split at deletions (`D`) and skipped regions (`N`),
+    /// while insertions, clipping and padding do not interrupt a block.
+    #[must_use]
+    pub fn blocks(&self) -> Vec<(u64, u64)> {
+        let mut blocks = Vec::new();
+        let mut pos = 0;
+        let mut block_start = None;
+        for (len, op) in &self.0 {
+            let len = u64::from(*len);
+            if op.is_aligned() {
+                block_start.get_or_insert(pos);
+                pos += len;
+            } else if op.consumes_reference() {
+                if let Some(start) = block_start.take() {
+                    blocks.push((start, pos));
+                }
+                pos += len;
+            }
+        }
+        if let Some(start) = block_start {
+            blocks.push((start, pos));
+        }
+        blocks.retain(|(start, end)| start < end);
+        blocks
+    }
+}
+
+impl FromStr for Cigar {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "*" {
+            return Ok(Self::default());
+        }
+        let mut ops = Vec::new();
+        let mut len: Option<u32> = None;
+        for c in s.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                let value = len
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or("CIGAR operation length is too large")?;
+                len = Some(value);
+            } else {
+                let op = CigarOp::try_from(c)?;
+                ops.push((len.take().ok_or("CIGAR operation is missing a length")?, op));
+            }
+        }
+        if len.is_some() || ops.is_empty() {
+            return Err("CIGAR must end with an operation");
+        }
+        Ok(Self(ops))
+    }
+}
+
+impl Display for Cigar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "*");
+        }
+        for (len, op) in &self.0 {
+            write!(f, "{len}{op}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let cigar: Cigar = "3S10M2I5=1X".parse().unwrap();
+        assert_eq!(
+            cigar.ops(),
+            &[
+                (3, CigarOp::SoftClip),
+                (10, CigarOp::Match),
+                (2, CigarOp::Insertion),
+                (5, CigarOp::SequenceMatch),
+                (1, CigarOp::SequenceMismatch),
+            ]
+        );
+        assert_eq!(cigar.reference_len(), 16);
+        assert_eq!(cigar.to_string(), "3S10M2I5=1X");
+    }
+
+    #[test]
+    fn test_parse_unavailable() {
+        let cigar: Cigar = "*".parse().unwrap();
+        assert!(cigar.is_empty());
+        assert_eq!(cigar.reference_len(), 0);
+        assert!(cigar.blocks().is_empty());
+        assert_eq!(cigar.to_string(), "*");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("10".parse::<Cigar>().is_err());
+        assert!("M".parse::<Cigar>().is_err());
+        assert!("10Q".parse::<Cigar>().is_err());
+        assert!("".parse::<Cigar>().is_err());
+        assert!("99999999999M".parse::<Cigar>().is_err());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let cigar: Cigar = "10M2I10M".parse().unwrap();
+        assert_eq!(cigar.blocks(), vec![(0, 20)]);
+
+        let cigar: Cigar = "2H10M5D10M50N4M3S".parse().unwrap();
+        assert_eq!(cigar.blocks(), vec![(0, 10), (15, 25), (75, 79)]);
+
+        let cigar: Cigar = "5M0N5M".parse().unwrap();
+        assert_eq!(cigar.blocks(), vec![(0, 5), (5, 10)]);
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How readers handle malformed lines.
+///
+/// With every policy the errors of the underlying reader (e.g. I/O
+/// errors) end the stream.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ErrorPolicy {
+    /// Malformed lines are returned as errors carrying their line number
+    #[default]
+    Fail,
+
+    /// Malformed lines are skipped and counted
+    Skip,
+
+    /// Unrecognized strands are read as [`Strand::Unknown`](crate::Strand::Unknown)
+    /// and inverted coordinates are swapped.
+    /// Lines that cannot be repaired are skipped and counted.
+    Repair,
+}
@@ -1,10 +1,18 @@
+mod aggregate;
+mod cigar;
+mod error_policy;
 mod frame;
+mod pair_method;
 mod query;
 mod query_method;
 mod score;
 mod strand;
 mod strand_method;
+pub use aggregate::{AggregateValue, Aggregator};
+pub use cigar::{Cigar, CigarOp};
+pub use error_policy::ErrorPolicy;
 pub use frame::Frame;
+pub use pair_method::PairMethod;
 pub use query::Query;
 pub use query_method::QueryMethod;
 pub use score::Score;
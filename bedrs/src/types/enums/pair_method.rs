@@ -0,0 +1,33 @@
+/// The rule deciding whether a paired record (see [`BedPe`](crate::BedPe))
+/// is reported, based on which of its two anchors overlap.
+///
+/// Mirrors the `-type` option of `bedtools pairtobed` and
+/// `bedtools pairtopair`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PairMethod {
+    /// Report the pair if at least one of its anchors overlaps
+    #[default]
+    Either,
+    /// Report the pair only if both of its anchors overlap
+    Both,
+    /// Report the pair only if neither of its anchors overlaps
+    Neither,
+    /// Report the pair unless both of its anchors overlap
+    NotBoth,
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(PairMethod::default(), PairMethod::Either);
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", PairMethod::Both), "Both");
+        assert_eq!(format!("{:?}", PairMethod::NotBoth), "NotBoth");
+    }
+}
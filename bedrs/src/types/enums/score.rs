@@ -36,7 +36,7 @@ where
 {
     struct OptionFloatVisitor;
 
-    impl<'de> de::Visitor<'de> for OptionFloatVisitor {
+    impl de::Visitor<'_> for OptionFloatVisitor {
         type Value = Option<f64>;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -63,6 +63,7 @@ where
 }
 
 #[cfg(feature = "serde")]
+#[allow(clippy::ref_option)]
 // Custom serializer to convert Option<f64> to string, writing None as '.'
 fn serialize_option_float<S>(option: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -122,6 +123,11 @@ impl From<Option<f64>> for Score {
         Score(s)
     }
 }
+impl From<Score> for Option<f64> {
+    fn from(s: Score) -> Self {
+        s.0
+    }
+}
 
 #[cfg(test)]
 mod testing {
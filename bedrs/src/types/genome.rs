@@ -0,0 +1,369 @@
+use crate::{
+    io::LineReader,
+    traits::{
+        fields::parse_value, ChromBounds, GenomeError, IntervalBounds, ParseError, ValueBounds,
+    },
+    Bed3, Coordinates, IntervalContainer,
+};
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fmt::Display,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+/// A set of chromosome sizes describing a genome assembly.
+///
+/// Chromosomes keep the order in which they were provided, which is used
+/// as the canonical chromosome order of the genome (e.g. `chr1`, `chr2`,
+/// ..., `chr10` rather than the lexicographic `chr1`, `chr10`, `chr2`).
+///
+/// A genome can be loaded from a two-column sizes file (`.genome` /
+/// `.chrom.sizes`) or from a FASTA index (`.fai`), of which only the first
+/// two columns are used.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{Bed3, Genome, IntervalContainer};
+///
+/// let text = "chr1\t1000\nchr2\t500\n";
+/// let genome: Genome<String, u32> = Genome::from_reader(text.as_bytes()).unwrap();
+/// assert_eq!(genome.len(), 2);
+/// assert_eq!(genome.chrom_size(&"chr2".to_string()), Some(500));
+///
+/// let set = IntervalContainer::from_unsorted(vec![
+///     Bed3::new("chr1".to_string(), 10, 20),
+///     Bed3::new("chr2".to_string(), 450, 550),
+/// ]);
+/// assert!(genome.validate(&set).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Genome<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    chroms: Vec<(C, T)>,
+    index: BTreeMap<C, usize>,
+}
+
+impl<C, T> Genome<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Creates an empty genome
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            chroms: Vec::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of chromosomes in the genome
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.chroms.len()
+    }
+
+    /// Returns true if the genome holds no chromosomes
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.chroms.is_empty()
+    }
+
+    /// Returns true if the chromosome is present in the genome
+    pub fn contains(&self, chr: &C) -> bool {
+        self.index.contains_key(chr)
+    }
+
+    /// Returns the size of a chromosome.
+    ///
+    /// This can be used as the `max_bound` of [`Coordinates::extend_right`].
+    pub fn chrom_size(&self, chr: &C) -> Option<T> {
+        self.index.get(chr).map(|idx| self.chroms[*idx].1)
+    }
+
+    /// Returns the position of a chromosome in the canonical order
+    pub fn rank(&self, chr: &C) -> Option<usize> {
+        self.index.get(chr).copied()
+    }
+
+    /// Compares two chromosomes by their canonical order.
+    ///
+    /// Chromosomes missing from the genome are ordered after all known
+    /// chromosomes and then by their natural order.
+    pub fn cmp_chroms(&self, a: &C, b: &C) -> Ordering {
+        match (self.rank(a), self.rank(b)) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.cmp(b),
+        }
+    }
+
+    /// Returns the full-length interval of a chromosome
+    pub fn bounds(&self, chr: &C) -> Option<Bed3<C, T>> {
+        self.chrom_size(chr)
+            .map(|size| Bed3::new(chr.clone(), T::zero(), size))
+    }
+
+    /// Iterates over the chromosomes in canonical order
+    pub fn chroms(&self) -> impl Iterator<Item = &C> {
+        self.chroms.iter().map(|(chr, _)| chr)
+    }
+
+    /// Iterates over the `(chromosome, size)` pairs in canonical order
+    pub fn iter(&self) -> impl Iterator<Item = (&C, T)> {
+        self.chroms.iter().map(|(chr, size)| (chr, *size))
+    }
+
+    /// Returns the full-length intervals of all chromosomes in canonical
+    /// order
+    #[must_use]
+    pub fn intervals(&self) -> Vec<Bed3<C, T>> {
+        self.chroms
+            .iter()
+            .map(|(chr, size)| Bed3::new(chr.clone(), T::zero(), *size))
+            .collect()
+    }
+}
+
+/// Chromosomes and positions are written into the messages of the
+/// [`GenomeError`] raised by these methods.
+impl<C, T> Genome<C, T>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+{
+    /// Creates a genome from an iterator of `(chromosome, size)` pairs.
+    ///
+    /// The order of the iterator defines the canonical chromosome order.
+    pub fn from_sizes<It>(sizes: It) -> Result<Self, GenomeError>
+    where
+        It: IntoIterator<Item = (C, T)>,
+    {
+        let mut genome = Self::new();
+        for (chr, size) in sizes {
+            genome.insert(chr, size)?;
+        }
+        Ok(genome)
+    }
+
+    /// Appends a chromosome to the end of the genome.
+    ///
+    /// Returns an error if the chromosome is already present.
+    pub fn insert(&mut self, chr: C, size: T) -> Result<(), GenomeError> {
+        if self.index.contains_key(&chr) {
+            return Err(GenomeError::DuplicateChrom {
+                chr: chr.to_string(),
+            });
+        }
+        self.index.insert(chr.clone(), self.chroms.len());
+        self.chroms.push((chr, size));
+        Ok(())
+    }
+
+    /// Checks that an interval lies on a known chromosome and does not
+    /// extend past its end.
+    pub fn validate_interval<I>(&self, iv: &I) -> Result<(), GenomeError>
+    where
+        I: Coordinates<C, T>,
+    {
+        let Some(size) = self.chrom_size(iv.chr()) else {
+            return Err(GenomeError::UnknownChrom {
+                chr: iv.chr().to_string(),
+            });
+        };
+        if iv.end() > size {
+            return Err(GenomeError::OutOfBounds {
+                chr: iv.chr().to_string(),
+                end: iv.end().to_string(),
+                size: size.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that all intervals of a container lie within the genome.
+    ///
+    /// Returns the error of the first invalid interval.
+    pub fn validate<I>(&self, set: &IntervalContainer<I, C, T>) -> Result<(), GenomeError>
+    where
+        I: IntervalBounds<C, T>,
+    {
+        set.records()
+            .iter()
+            .try_for_each(|iv| self.validate_interval(iv))
+    }
+
+    /// Returns the indices of all intervals of a container that are not
+    /// valid within the genome along with the reason.
+    pub fn invalid_records<I>(&self, set: &IntervalContainer<I, C, T>) -> Vec<(usize, GenomeError)>
+    where
+        I: IntervalBounds<C, T>,
+    {
+        set.records()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, iv)| self.validate_interval(iv).err().map(|e| (idx, e)))
+            .collect()
+    }
+}
+
+impl<C, T> Genome<C, T>
+where
+    C: ChromBounds + Display + FromStr,
+    T: ValueBounds + Display + FromStr,
+{
+    /// Reads a genome from a sizes file or a FASTA index.
+    ///
+    /// The first two whitespace-delimited columns of each line are used as
+    /// the chromosome name and size. Empty lines and `#` comments are
+    /// skipped.
+    /// Parse errors carry the line number they occurred on.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, GenomeError> {
+        Self::from_lines(LineReader::new(reader))
+    }
+
+    /// Reads a genome from a sizes file or a FASTA index on disk
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, GenomeError> {
+        let file = File::open(&path).map_err(ParseError::from)?;
+        Self::from_lines(LineReader::new(BufReader::new(file)).with_path(path.as_ref().into()))
+    }
+
+    fn from_lines<R: BufRead>(mut lines: LineReader<R>) -> Result<Self, GenomeError> {
+        let mut genome = Self::new();
+        while lines.next_line(|line| line.trim().is_empty() || line.starts_with('#'))? {
+            let mut fields = lines.line().split_whitespace();
+            let chr = fields.next().unwrap_or_default();
+            let (chr, size) = fields
+                .next()
+                .ok_or(ParseError::MissingField { field: "size" })
+                .and_then(|size| Ok((parse_value(chr, "chr")?, parse_value(size, "size")?)))
+                .map_err(|e| lines.locate(e))?;
+            genome.insert(chr, size)?;
+        }
+        Ok(genome)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Strand, StrandedBed3};
+
+    const SIZES: &str = "chr1\t1000\nchr2\t500\n\nchr10\t200\n";
+    const FAI: &str = "chr1\t1000\t6\t60\t61\nchrM\t16569\t1030\t60\t61\n";
+
+    #[test]
+    fn genome_from_sizes_file() {
+        let genome: Genome<String, u32> = Genome::from_reader(SIZES.as_bytes()).unwrap();
+        assert_eq!(genome.len(), 3);
+        assert_eq!(genome.chrom_size(&"chr1".to_string()), Some(1000));
+        assert_eq!(genome.chrom_size(&"chr10".to_string()), Some(200));
+        assert_eq!(genome.chrom_size(&"chr3".to_string()), None);
+        let chroms: Vec<_> = genome.chroms().cloned().collect();
+        assert_eq!(chroms, vec!["chr1", "chr2", "chr10"]);
+    }
+
+    #[test]
+    fn genome_from_fai() {
+        let genome: Genome<String, u64> = Genome::from_reader(FAI.as_bytes()).unwrap();
+        assert_eq!(genome.len(), 2);
+        assert_eq!(genome.chrom_size(&"chrM".to_string()), Some(16569));
+    }
+
+    #[test]
+    fn genome_from_malformed() {
+        let text = "chr1\n";
+        match Genome::<String, u32>::from_reader(text.as_bytes()) {
+            Err(GenomeError::Parse(err)) => {
+                assert_eq!(err.line(), Some(1));
+                assert!(matches!(
+                    err.inner(),
+                    ParseError::MissingField { field: "size" }
+                ));
+            }
+            _ => panic!("Expected missing field"),
+        }
+        let text = "# sizes\nchr1\t10\n\nchr2\tabc\n";
+        let err = Genome::<String, u32>::from_reader(text.as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4: Unable to parse field size from: abc"
+        );
+        let text = "chr1\t10\nchr1\t20\n";
+        match Genome::<String, u32>::from_reader(text.as_bytes()) {
+            Err(GenomeError::DuplicateChrom { chr }) => assert_eq!(chr, "chr1"),
+            _ => panic!("Expected duplicate chromosome"),
+        }
+    }
+
+    #[test]
+    fn genome_canonical_order() {
+        let genome: Genome<String, u32> = Genome::from_reader(SIZES.as_bytes()).unwrap();
+        let chr2 = "chr2".to_string();
+        let chr10 = "chr10".to_string();
+        let chr_x = "chrX".to_string();
+        assert_eq!(genome.rank(&chr10), Some(2));
+        assert_eq!(genome.cmp_chroms(&chr2, &chr10), Ordering::Less);
+        assert_eq!(genome.cmp_chroms(&chr_x, &chr10), Ordering::Greater);
+        let mut chroms = vec![chr_x.clone(), chr10.clone(), chr2.clone()];
+        chroms.sort_by(|a, b| genome.cmp_chroms(a, b));
+        assert_eq!(chroms, vec![chr2, chr10, chr_x]);
+    }
+
+    #[test]
+    fn genome_bounds() {
+        let genome = Genome::from_sizes(vec![(1, 100), (2, 50)]).unwrap();
+        let bounds = genome.bounds(&2).unwrap();
+        assert!(bounds.eq(&Bed3::new(2, 0, 50)));
+        assert!(genome.bounds(&3).is_none());
+        let intervals = genome.intervals();
+        assert_eq!(intervals.len(), 2);
+        assert!(intervals[0].eq(&Bed3::new(1, 0, 100)));
+    }
+
+    #[test]
+    fn genome_extend_right() {
+        let genome = Genome::from_sizes(vec![(1, 100)]).unwrap();
+        let mut iv = Bed3::new(1, 80, 90);
+        iv.extend_right(&20, genome.chrom_size(iv.chr()));
+        assert_eq!(iv.end(), 100);
+    }
+
+    #[test]
+    fn genome_validate() {
+        let genome = Genome::from_sizes(vec![(1, 100), (2, 50)]).unwrap();
+        let set = IntervalContainer::from_unsorted(vec![
+            StrandedBed3::new(1, 10, 20, Strand::Forward),
+            StrandedBed3::new(2, 40, 50, Strand::Reverse),
+        ]);
+        assert!(genome.validate(&set).is_ok());
+
+        let set = IntervalContainer::from_unsorted(vec![
+            Bed3::new(1, 10, 20),
+            Bed3::new(2, 40, 60),
+            Bed3::new(3, 0, 10),
+        ]);
+        match genome.validate(&set) {
+            Err(GenomeError::OutOfBounds { chr, end, size }) => {
+                assert_eq!(chr, "2");
+                assert_eq!(end, "60");
+                assert_eq!(size, "50");
+            }
+            _ => panic!("Expected out of bounds"),
+        }
+        let invalid = genome.invalid_records(&set);
+        assert_eq!(invalid.len(), 2);
+        assert_eq!(invalid[0].0, 1);
+        assert_eq!(invalid[1].0, 2);
+        assert!(matches!(invalid[1].1, GenomeError::UnknownChrom { .. }));
+    }
+}
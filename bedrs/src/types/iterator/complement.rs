@@ -1,5 +1,8 @@
-use crate::traits::{ChromBounds, IntervalBounds, ValueBounds};
-use std::{fmt::Debug, marker::PhantomData};
+use crate::{
+    traits::{ChromBounds, IntervalBounds, ValueBounds},
+    Genome,
+};
+use std::{fmt::Debug, iter::Peekable, marker::PhantomData};
 
 /// An iterator over the complement of a set of interval records.
 ///
@@ -72,12 +75,100 @@ where
     }
 }
 
+/// An iterator over the complement of a set of interval records with
+/// respect to a [`Genome`].
+///
+/// Unlike [`ComplementIter`] this includes the regions before the first
+/// and after the last interval of each chromosome as well as the full
+/// length of chromosomes without any intervals.
+///
+/// The input is expected to be sorted.
+/// Complements are reported in sorted order and overlapping input
+/// intervals are handled as if they were merged.
+/// Intervals on chromosomes missing from the genome are ignored and
+/// intervals are clipped to the chromosome size.
+pub struct GenomeComplementIter<It, I, C, T>
+where
+    It: Iterator<Item = I>,
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    iter: Peekable<It>,
+    chroms: Vec<(C, T)>,
+    chrom_idx: usize,
+    position: T,
+}
+impl<It, I, C, T> GenomeComplementIter<It, I, C, T>
+where
+    It: Iterator<Item = I>,
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    pub fn new(iter: It, genome: &Genome<C, T>) -> Self {
+        let mut chroms: Vec<(C, T)> = genome
+            .iter()
+            .map(|(chr, size)| (chr.clone(), size))
+            .collect();
+        chroms.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            iter: iter.peekable(),
+            chroms,
+            chrom_idx: 0,
+            position: T::zero(),
+        }
+    }
+    fn build(chr: &C, start: T, end: T) -> I {
+        let mut iv = I::empty();
+        iv.update_all(chr, &start, &end);
+        iv
+    }
+}
+impl<It, I, C, T> Iterator for GenomeComplementIter<It, I, C, T>
+where
+    It: Iterator<Item = I>,
+    I: IntervalBounds<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    type Item = I;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (chr, size) = self.chroms.get(self.chrom_idx)?;
+
+            // skip intervals on chromosomes missing from the genome
+            while self.iter.peek().is_some_and(|iv| iv.chr() < chr) {
+                self.iter.next();
+            }
+
+            if let Some(iv) = self.iter.next_if(|iv| iv.chr() == chr) {
+                let start = self.position;
+                let end = iv.start().min(*size);
+                if iv.end() > self.position {
+                    self.position = iv.end();
+                }
+                if start < end {
+                    return Some(Self::build(chr, start, end));
+                }
+            } else {
+                let start = self.position;
+                self.chrom_idx += 1;
+                self.position = T::zero();
+                if start < *size {
+                    return Some(Self::build(chr, start, *size));
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod testing {
-    use super::ComplementIter;
+    use super::{ComplementIter, GenomeComplementIter};
     use crate::{
         traits::{ChromBounds, IntervalBounds, ValueBounds},
-        BaseInterval, Bed3,
+        BaseInterval, Bed3, Genome,
     };
 
     fn validate_records<I, C, T>(obs: &[I], exp: &[I])
@@ -156,4 +247,71 @@ mod testing {
         let complements: Vec<_> = comp_iter.collect();
         validate_records(&complements, &expected);
     }
+
+    #[test]
+    /// |1|   x-----y    i---j   |2|        |3| k---l
+    /// ===============================================
+    /// |1| 0-x     y----i   j-E |2| 0----E |3| 0-k
+    fn genome_complement_a() {
+        let genome = Genome::from_sizes(vec![(1, 100), (2, 50), (3, 30)]).unwrap();
+        let intervals = vec![
+            Bed3::new(1, 10, 20),
+            Bed3::new(1, 30, 40),
+            Bed3::new(3, 10, 30),
+        ];
+        let expected = vec![
+            Bed3::new(1, 0, 10),
+            Bed3::new(1, 20, 30),
+            Bed3::new(1, 40, 100),
+            Bed3::new(2, 0, 50),
+            Bed3::new(3, 0, 10),
+        ];
+        let comp_iter = GenomeComplementIter::new(intervals.into_iter(), &genome);
+        let complements: Vec<_> = comp_iter.collect();
+        validate_records(&complements, &expected);
+    }
+
+    #[test]
+    /// |1| x-------y
+    ///        i-------j  k--l
+    /// ===========================
+    /// |1|            j--k  l-E
+    fn genome_complement_overlapping() {
+        let genome = Genome::from_sizes(vec![(1, 100)]).unwrap();
+        let intervals = vec![
+            Bed3::new(1, 0, 20),
+            Bed3::new(1, 10, 30),
+            Bed3::new(1, 15, 25),
+            Bed3::new(1, 40, 50),
+        ];
+        let expected = vec![Bed3::new(1, 30, 40), Bed3::new(1, 50, 100)];
+        let comp_iter = GenomeComplementIter::new(intervals.into_iter(), &genome);
+        let complements: Vec<_> = comp_iter.collect();
+        validate_records(&complements, &expected);
+    }
+
+    #[test]
+    fn genome_complement_empty_input() {
+        let genome = Genome::from_sizes(vec![(2, 50), (1, 100)]).unwrap();
+        let intervals: Vec<Bed3<i32, i32>> = vec![];
+        let expected = vec![Bed3::new(1, 0, 100), Bed3::new(2, 0, 50)];
+        let comp_iter = GenomeComplementIter::new(intervals.into_iter(), &genome);
+        let complements: Vec<_> = comp_iter.collect();
+        validate_records(&complements, &expected);
+    }
+
+    #[test]
+    fn genome_complement_unknown_and_clipped() {
+        let genome = Genome::from_sizes(vec![(2, 50)]).unwrap();
+        let intervals = vec![
+            Bed3::new(1, 10, 20),
+            Bed3::new(2, 10, 20),
+            Bed3::new(2, 40, 60),
+            Bed3::new(3, 10, 20),
+        ];
+        let expected = vec![Bed3::new(2, 0, 10), Bed3::new(2, 20, 40)];
+        let comp_iter = GenomeComplementIter::new(intervals.into_iter(), &genome);
+        let complements: Vec<_> = comp_iter.collect();
+        validate_records(&complements, &expected);
+    }
 }
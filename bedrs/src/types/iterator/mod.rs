@@ -5,10 +5,12 @@ mod intersect;
 mod interval;
 mod merge;
 mod subtract;
+mod union_bedgraph;
 pub use cluster::ClusterIter;
-pub use complement::ComplementIter;
+pub use complement::{ComplementIter, GenomeComplementIter};
 pub use find::{FindIter, FindIterEnumerate, FindIterOwned};
 pub use intersect::IntersectIter;
 pub use interval::{IntervalIterOwned, IntervalIterRef};
 pub use merge::MergeIter;
 pub use subtract::{SubtractFromIter, SubtractIter};
+pub use union_bedgraph::UnionBedGraphIter;
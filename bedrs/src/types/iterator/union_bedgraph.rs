@@ -0,0 +1,245 @@
+use crate::{
+    traits::{ChromBounds, ValueBounds},
+    BedGraph, Coordinates, UnionBedGraph,
+};
+use std::iter::Peekable;
+
+/// An iterator over the union of multiple `BedGraph` tracks
+///
+/// This iterator takes an iterator of [`BedGraph`] records for each track
+/// and aligns them onto their shared breakpoints, yielding a
+/// [`UnionBedGraph`] row with the score of every track for each interval
+/// covered by at least one track (as with `bedtools unionbedg`).
+/// Tracks without a record over an interval are given the fill value.
+///
+/// Records are consumed as the iterator advances so tracks can be
+/// streamed without being held in memory.
+/// The records of each track must be sorted by chromosome and start
+/// position and must not overlap each other.
+///
+/// Undefined behavior if the records are not sorted.
+///
+/// # Example
+///
+/// ```
+/// use bedrs::{BedGraph, Coordinates, UnionBedGraphIter};
+///
+/// let a = vec![BedGraph::new(1, 10, 30, 1.0), BedGraph::new(1, 30, 40, 2.0)];
+/// let b = vec![BedGraph::new(1, 20, 50, 5.0)];
+///
+/// let iter = UnionBedGraphIter::new(vec![a.into_iter(), b.into_iter()], 0.0);
+/// let rows: Vec<_> = iter
+///     .map(|row| (row.start(), row.end(), row.scores().clone()))
+///     .collect();
+/// assert_eq!(
+///     rows,
+///     vec![
+///         (10, 20, vec![1.0, 0.0]),
+///         (20, 30, vec![1.0, 5.0]),
+///         (30, 40, vec![2.0, 5.0]),
+///         (40, 50, vec![0.0, 5.0]),
+///     ]
+/// );
+/// ```
+pub struct UnionBedGraphIter<It, C, T>
+where
+    It: Iterator<Item = BedGraph<C, T>>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    tracks: Vec<Peekable<It>>,
+    fill: f64,
+    chr: Option<C>,
+    pos: T,
+}
+
+impl<It, C, T> UnionBedGraphIter<It, C, T>
+where
+    It: Iterator<Item = BedGraph<C, T>>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    pub fn new(tracks: Vec<It>, fill: f64) -> Self {
+        Self {
+            tracks: tracks.into_iter().map(Iterator::peekable).collect(),
+            fill,
+            chr: None,
+            pos: T::zero(),
+        }
+    }
+
+    /// Moves to the lowest chromosome among the next records of the
+    /// tracks, starting at its first record.
+    ///
+    /// Returns `false` once all tracks are exhausted.
+    #[allow(clippy::unnecessary_map_or)]
+    fn next_chrom(&mut self) -> bool {
+        let mut chr: Option<&C> = None;
+        for track in &mut self.tracks {
+            if let Some(head) = track.peek() {
+                if chr.map_or(true, |chr| head.chr() < chr) {
+                    chr = Some(head.chr());
+                }
+            }
+        }
+        let Some(chr) = chr.cloned() else {
+            return false;
+        };
+        self.pos = self
+            .tracks
+            .iter_mut()
+            .filter_map(|track| track.peek())
+            .filter(|head| head.chr() == &chr)
+            .map(Coordinates::start)
+            .min()
+            .unwrap_or_else(T::zero);
+        self.chr = Some(chr);
+        true
+    }
+}
+
+impl<It, C, T> Iterator for UnionBedGraphIter<It, C, T>
+where
+    It: Iterator<Item = BedGraph<C, T>>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    type Item = UnionBedGraph<C, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(chr) = self.chr.clone() else {
+                if self.next_chrom() {
+                    continue;
+                }
+                return None;
+            };
+
+            // Find the next breakpoint and the scores of the tracks up to it
+            let mut end = None;
+            let mut covered = false;
+            let mut scores = Vec::with_capacity(self.tracks.len());
+            for track in &mut self.tracks {
+                let head = track.peek().filter(|head| head.chr() == &chr);
+                let score = match head {
+                    Some(head) if head.start() > self.pos => {
+                        end = Some(end.map_or(head.start(), |end: T| end.min(head.start())));
+                        self.fill
+                    }
+                    Some(head) => {
+                        end = Some(end.map_or(head.end(), |end: T| end.min(head.end())));
+                        covered = true;
+                        head.score()
+                    }
+                    None => self.fill,
+                };
+                scores.push(score);
+            }
+
+            // No track has records left on the chromosome
+            let Some(end) = end else {
+                self.chr = None;
+                continue;
+            };
+
+            let start = self.pos;
+            self.pos = end;
+            for track in &mut self.tracks {
+                while track
+                    .peek()
+                    .is_some_and(|head| head.chr() == &chr && head.end() <= end)
+                {
+                    track.next();
+                }
+            }
+            if covered && start < end {
+                return Some(UnionBedGraph::new(chr, start, end, scores));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+
+    type Row = (i32, u32, u32, Vec<f64>);
+
+    fn union(tracks: Vec<Vec<BedGraph<i32, u32>>>, fill: f64) -> Vec<Row> {
+        let tracks = tracks.into_iter().map(Vec::into_iter).collect();
+        UnionBedGraphIter::new(tracks, fill)
+            .map(|row| (*row.chr(), row.start(), row.end(), row.scores().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn union_disjoint_tracks() {
+        let rows = union(
+            vec![
+                vec![BedGraph::new(1, 0, 10, 1.0)],
+                vec![BedGraph::new(1, 20, 30, 2.0)],
+            ],
+            -1.0,
+        );
+        assert_eq!(
+            rows,
+            vec![(1, 0, 10, vec![1.0, -1.0]), (1, 20, 30, vec![-1.0, 2.0])]
+        );
+    }
+
+    #[test]
+    fn union_shared_breakpoints() {
+        let rows = union(
+            vec![
+                vec![BedGraph::new(1, 0, 100, 1.0)],
+                vec![BedGraph::new(1, 10, 20, 2.0), BedGraph::new(1, 20, 30, 3.0)],
+                vec![
+                    BedGraph::new(1, 15, 25, 4.0),
+                    BedGraph::new(1, 90, 110, 5.0),
+                ],
+            ],
+            0.0,
+        );
+        assert_eq!(
+            rows,
+            vec![
+                (1, 0, 10, vec![1.0, 0.0, 0.0]),
+                (1, 10, 15, vec![1.0, 2.0, 0.0]),
+                (1, 15, 20, vec![1.0, 2.0, 4.0]),
+                (1, 20, 25, vec![1.0, 3.0, 4.0]),
+                (1, 25, 30, vec![1.0, 3.0, 0.0]),
+                (1, 30, 90, vec![1.0, 0.0, 0.0]),
+                (1, 90, 100, vec![1.0, 0.0, 5.0]),
+                (1, 100, 110, vec![0.0, 0.0, 5.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_chromosomes() {
+        let rows = union(
+            vec![
+                vec![BedGraph::new(1, 0, 10, 1.0), BedGraph::new(3, 5, 10, 3.0)],
+                vec![BedGraph::new(2, 0, 10, 2.0), BedGraph::new(3, 0, 10, 4.0)],
+            ],
+            0.0,
+        );
+        assert_eq!(
+            rows,
+            vec![
+                (1, 0, 10, vec![1.0, 0.0]),
+                (2, 0, 10, vec![0.0, 2.0]),
+                (3, 0, 5, vec![0.0, 4.0]),
+                (3, 5, 10, vec![3.0, 4.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_empty_tracks() {
+        assert!(union(vec![], 0.0).is_empty());
+        assert!(union(vec![vec![], vec![]], 0.0).is_empty());
+        let rows = union(vec![vec![], vec![BedGraph::new(1, 0, 10, 1.0)]], 0.0);
+        assert_eq!(rows, vec![(1, 0, 10, vec![0.0, 1.0])]);
+    }
+}
@@ -1,13 +1,23 @@
+pub mod chrom_dict;
 pub mod container;
 pub mod enums;
+pub mod genome;
 pub mod iterator;
+pub mod one_based;
 pub mod record;
-pub use container::IntervalContainer;
-pub use enums::{Frame, Query, QueryMethod, Score, Strand, StrandMethod};
+pub use chrom_dict::ChromDict;
+pub use container::{BedPeContainer, Coverage, IntervalContainer};
+pub use enums::{
+    AggregateValue, Aggregator, Cigar, CigarOp, ErrorPolicy, Frame, PairMethod, Query, QueryMethod,
+    Score, Strand, StrandMethod,
+};
+pub use genome::Genome;
 pub use iterator::{
     ClusterIter, FindIter, FindIterEnumerate, FindIterOwned, IntersectIter, IntervalIterOwned,
-    IntervalIterRef, MergeIter, SubtractFromIter, SubtractIter,
+    IntervalIterRef, MergeIter, SubtractFromIter, SubtractIter, UnionBedGraphIter,
 };
+pub use one_based::OneBased;
 pub use record::{
-    BaseInterval, Bed12, Bed3, Bed4, Bed6, BedGraph, Gtf, MetaInterval, StrandedBed3,
+    Attributes, BaseInterval, Bed12, Bed3, Bed4, Bed6, BedGraph, BedPe, BroadPeak, Gff3, Gtf,
+    MetaInterval, MultiSegment, NarrowPeak, Psl, Sam, StrandedBed3, UnionBedGraph,
 };
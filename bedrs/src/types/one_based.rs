@@ -0,0 +1,175 @@
+use crate::traits::{ChromBounds, Coordinates, ValueBounds};
+use num_traits::One;
+use std::marker::PhantomData;
+
+/// An interval whose coordinates are 1-based and closed.
+///
+/// Every [`Coordinates`] implementation in this library is 0-based and
+/// half-open, which is what all overlap, distance, and set operations
+/// assume.
+/// Coordinates in the 1-based closed convention (as found in GTF, GFF3,
+/// VCF, SAM, or region strings) must be wrapped in a `OneBased` so that
+/// they cannot be passed to those operations by mistake: `OneBased` does
+/// not implement [`Coordinates`] and has to be converted explicitly with
+/// [`OneBased::into_half_open`].
+///
+/// The readers of this library already perform this conversion, so
+/// `OneBased` is only needed when building records from 1-based values
+/// by hand or when reporting 1-based positions.
+///
+/// # Examples
+///
+/// ```
+/// use bedrs::{Bed3, Coordinates, OneBased, Overlap};
+///
+/// // A GTF style interval covering bases 11 through 20
+/// let gtf = OneBased::new(Bed3::new(1, 11, 20)).unwrap();
+/// assert_eq!(gtf.len(), 10);
+///
+/// // Converted to the half-open convention it covers [10, 20)
+/// let a = gtf.into_half_open();
+/// assert_eq!(a.start(), 10);
+/// assert_eq!(a.end(), 20);
+///
+/// let b = Bed3::new(1, 0, 11);
+/// assert!(a.overlaps(&b));
+///
+/// // And back again
+/// let b = OneBased::from_half_open(b);
+/// assert_eq!(b.start(), 1);
+/// assert_eq!(b.end(), 11);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OneBased<I, C, T>
+where
+    I: Coordinates<C, T>,
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    iv: I,
+    phantom_c: PhantomData<C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<I, C, T> OneBased<I, C, T>
+where
+    I: Coordinates<C, T>,
+    C: ChromBounds,
+    T: ValueBounds + One,
+{
+    /// Wraps an interval whose `start` and `end` hold 1-based closed
+    /// coordinates.
+    ///
+    /// Returns `None` if the start is zero or if the end lies more than
+    /// one position before the start.
+    /// An end one position before the start describes an empty interval.
+    pub fn new(iv: I) -> Option<Self> {
+        if iv.start() < T::one() || iv.end() + T::one() < iv.start() {
+            return None;
+        }
+        Some(Self {
+            iv,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+        })
+    }
+
+    /// Converts a 0-based half-open interval to the 1-based closed
+    /// convention
+    pub fn from_half_open(mut iv: I) -> Self {
+        iv.update_start(&(iv.start() + T::one()));
+        Self {
+            iv,
+            phantom_c: PhantomData,
+            phantom_t: PhantomData,
+        }
+    }
+
+    /// Converts the interval to the 0-based half-open convention used by
+    /// every [`Coordinates`] operation
+    pub fn into_half_open(mut self) -> I {
+        self.iv.update_start(&(self.iv.start() - T::one()));
+        self.iv
+    }
+
+    /// Returns the chromosome of the interval
+    pub fn chr(&self) -> &C {
+        self.iv.chr()
+    }
+
+    /// Returns the 1-based position of the first base of the interval
+    pub fn start(&self) -> T {
+        self.iv.start()
+    }
+
+    /// Returns the 1-based position of the last base of the interval
+    pub fn end(&self) -> T {
+        self.iv.end()
+    }
+
+    /// Returns the number of bases covered by the interval
+    pub fn len(&self) -> T {
+        self.iv.end() + T::one() - self.iv.start()
+    }
+
+    /// Returns true if the interval covers no bases
+    pub fn is_empty(&self) -> bool {
+        self.len() == T::zero()
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{Bed3, Gtf, Score, Strand};
+
+    #[test]
+    fn one_based_new() {
+        let iv = OneBased::new(Bed3::new(1, 1, 1)).unwrap();
+        assert_eq!(iv.len(), 1);
+        assert!(!iv.is_empty());
+        let iv = OneBased::new(Bed3::new(1, 5, 4)).unwrap();
+        assert!(iv.is_empty());
+        assert!(OneBased::new(Bed3::new(1, 0, 10)).is_none());
+        assert!(OneBased::new(Bed3::new(1, 5, 3)).is_none());
+    }
+
+    #[test]
+    fn one_based_round_trip() {
+        let a = Bed3::new(1, 10, 20);
+        let b = OneBased::from_half_open(a);
+        assert_eq!(*b.chr(), 1);
+        assert_eq!(b.start(), 11);
+        assert_eq!(b.end(), 20);
+        assert_eq!(b.len(), a.len());
+        let c = b.into_half_open();
+        assert!(c.eq(&a));
+    }
+
+    #[test]
+    fn one_based_empty_round_trip() {
+        let a = Bed3::new(1, 10_u32, 10);
+        let b = OneBased::from_half_open(a);
+        assert!(b.is_empty());
+        assert!(b.into_half_open().eq(&a));
+    }
+
+    #[test]
+    fn one_based_gtf() {
+        let record = Gtf::new(
+            "chr1",
+            "src",
+            "exon",
+            11_869_u64,
+            14_409,
+            Score(None),
+            Strand::Forward,
+            0.into(),
+            "",
+        );
+        let record = OneBased::new(record).unwrap().into_half_open();
+        assert_eq!(record.start(), 11_868);
+        assert_eq!(record.end(), 14_409);
+        assert_eq!(record.feature(), &"exon");
+    }
+}
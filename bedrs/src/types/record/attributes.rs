@@ -0,0 +1,323 @@
+use crate::traits::ParseError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An ordered collection of key-value attributes from an annotation record.
+///
+/// Keys may be repeated (e.g. the GTF `tag` key) and the order of the
+/// attributes in the source line is preserved.
+///
+/// # Usage
+///
+/// ```
+/// use bedrs::Attributes;
+///
+/// let attr = Attributes::parse_gtf(
+///     "gene_id \"ENSG01\"; transcript_id \"ENST01\"; tag \"basic\"; tag \"CCDS\"; level 2;",
+/// ).unwrap();
+/// assert_eq!(attr.get("gene_id"), Some("ENSG01"));
+/// assert_eq!(attr.get("level"), Some("2"));
+/// assert_eq!(attr.get_all("tag").collect::<Vec<_>>(), vec!["basic", "CCDS"]);
+/// assert_eq!(attr.get("gene_name"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Attributes {
+    pairs: Vec<(String, String)>,
+}
+
+impl Attributes {
+    #[must_use]
+    pub fn new(pairs: Vec<(String, String)>) -> Self {
+        Self { pairs }
+    }
+
+    /// Parses the ninth column of a GTF (GFF2) line.
+    ///
+    /// Attributes are `;`-separated `key value` pairs where the value may
+    /// be enclosed in double quotes.
+    /// Semicolons within quoted values are preserved.
+    pub fn parse_gtf(text: &str) -> Result<Self, ParseError> {
+        let mut pairs = Vec::new();
+        for entry in split_unquoted(text, ';') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (key, value) =
+                entry
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| ParseError::InvalidField {
+                        field: "attributes",
+                        value: entry.to_string(),
+                    })?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            pairs.push((key.to_string(), value.to_string()));
+        }
+        Ok(Self { pairs })
+    }
+
+    /// Parses the ninth column of a GFF3 line.
+    ///
+    /// Attributes are `;`-separated `key=value` pairs where a value may hold
+    /// multiple comma-separated entries (e.g. `Parent=tx1,tx2`).
+    /// Each entry is stored as its own pair and percent-encoded characters
+    /// are decoded.
+    ///
+    /// ```
+    /// use bedrs::Attributes;
+    ///
+    /// let attr = Attributes::parse_gff3("ID=exon1;Parent=tx1,tx2;Note=a%3Bb").unwrap();
+    /// assert_eq!(attr.get("ID"), Some("exon1"));
+    /// assert_eq!(attr.get_all("Parent").collect::<Vec<_>>(), vec!["tx1", "tx2"]);
+    /// assert_eq!(attr.get("Note"), Some("a;b"));
+    /// ```
+    pub fn parse_gff3(text: &str) -> Result<Self, ParseError> {
+        let text = text.trim();
+        let mut pairs = Vec::new();
+        if text == "." {
+            return Ok(Self { pairs });
+        }
+        for entry in text.split(';') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (key, values) = entry
+                .split_once('=')
+                .ok_or_else(|| ParseError::InvalidField {
+                    field: "attributes",
+                    value: entry.to_string(),
+                })?;
+            let key = percent_decode(key)?;
+            for value in values.split(',') {
+                pairs.push((key.clone(), percent_decode(value)?));
+            }
+        }
+        Ok(Self { pairs })
+    }
+
+    /// Writes the attributes in the GFF3 convention.
+    ///
+    /// Values sharing a key are joined by commas under the first occurrence
+    /// of the key and reserved characters are percent-encoded.
+    /// An empty collection is written as `.`.
+    pub fn write_gff3<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        if self.is_empty() {
+            return wtr.write_char('.');
+        }
+        let mut keys: Vec<&str> = Vec::new();
+        for (key, _) in &self.pairs {
+            if !keys.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+        for (idx, key) in keys.iter().enumerate() {
+            if idx > 0 {
+                wtr.write_char(';')?;
+            }
+            percent_encode(wtr, key)?;
+            wtr.write_char('=')?;
+            for (jdx, value) in self.get_all(key).enumerate() {
+                if jdx > 0 {
+                    wtr.write_char(',')?;
+                }
+                percent_encode(wtr, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the first value associated with a key
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns all values associated with a key in their original order
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.pairs
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns true if the key is present at least once
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| k == key)
+    }
+
+    /// Iterates over all key-value pairs in their original order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+/// Decodes `%XX` escape sequences
+fn percent_decode(text: &str) -> Result<String, ParseError> {
+    if !text.contains('%') {
+        return Ok(text.to_string());
+    }
+    let invalid = || ParseError::InvalidField {
+        field: "attributes",
+        value: text.to_string(),
+    };
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' {
+            let hex = text.get(idx + 1..idx + 3).ok_or_else(invalid)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            idx += 3;
+        } else {
+            decoded.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| invalid())
+}
+
+/// Writes text escaping the characters reserved by GFF3
+fn percent_encode<W: fmt::Write>(wtr: &mut W, text: &str) -> fmt::Result {
+    for c in text.chars() {
+        match c {
+            ';' | '=' | '&' | ',' | '%' | '\t' | '\n' | '\r' => write!(wtr, "%{:02X}", c as u32)?,
+            c if c.is_control() => write!(wtr, "%{:02X}", c as u32)?,
+            c => wtr.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+/// Splits text on a delimiter that is not enclosed in double quotes
+fn split_unquoted(text: &str, delim: char) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    let mut last = 0;
+    let mut bounds = Vec::new();
+    for (idx, c) in text.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == delim && !in_quotes {
+            bounds.push((last, idx));
+            last = idx + c.len_utf8();
+        }
+    }
+    bounds.push((last, text.len()));
+    bounds.into_iter().map(move |(a, b)| &text[a..b])
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn parse_gtf_quoted() {
+        let attr = Attributes::parse_gtf("gene_id \"g1\"; transcript_id \"t1\";").unwrap();
+        assert_eq!(attr.len(), 2);
+        assert_eq!(attr.get("gene_id"), Some("g1"));
+        assert_eq!(attr.get("transcript_id"), Some("t1"));
+    }
+
+    #[test]
+    fn parse_gtf_unquoted_and_spacing() {
+        let attr = Attributes::parse_gtf("exon_number 3 ;  gene_name \"ABC\"").unwrap();
+        assert_eq!(attr.get("exon_number"), Some("3"));
+        assert_eq!(attr.get("gene_name"), Some("ABC"));
+    }
+
+    #[test]
+    fn parse_gtf_multi_valued() {
+        let attr = Attributes::parse_gtf("tag \"basic\"; tag \"CCDS\"; tag \"MANE\";").unwrap();
+        assert_eq!(attr.get("tag"), Some("basic"));
+        assert_eq!(
+            attr.get_all("tag").collect::<Vec<_>>(),
+            vec!["basic", "CCDS", "MANE"]
+        );
+    }
+
+    #[test]
+    fn parse_gtf_quoted_delimiter() {
+        let attr = Attributes::parse_gtf("note \"a; b\"; gene_id \"g1\";").unwrap();
+        assert_eq!(attr.get("note"), Some("a; b"));
+        assert_eq!(attr.get("gene_id"), Some("g1"));
+    }
+
+    #[test]
+    fn parse_gtf_empty() {
+        let attr = Attributes::parse_gtf("").unwrap();
+        assert!(attr.is_empty());
+        assert!(!attr.contains_key("gene_id"));
+    }
+
+    #[test]
+    fn parse_gtf_missing_value() {
+        assert!(Attributes::parse_gtf("gene_id;").is_err());
+    }
+
+    #[test]
+    fn parse_gff3_multi_parent() {
+        let attr = Attributes::parse_gff3("ID=e1;Parent=t1,t2;Name=exon").unwrap();
+        assert_eq!(attr.len(), 4);
+        assert_eq!(attr.get("ID"), Some("e1"));
+        assert_eq!(attr.get_all("Parent").collect::<Vec<_>>(), vec!["t1", "t2"]);
+    }
+
+    #[test]
+    fn parse_gff3_escaped() {
+        let attr = Attributes::parse_gff3("Note=50%25 of a%2Cb%3Dc;Alias=x%20y").unwrap();
+        assert_eq!(attr.get("Note"), Some("50% of a,b=c"));
+        assert_eq!(attr.get("Alias"), Some("x y"));
+    }
+
+    #[test]
+    fn parse_gff3_empty() {
+        assert!(Attributes::parse_gff3(".").unwrap().is_empty());
+        assert!(Attributes::parse_gff3("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_gff3_invalid() {
+        assert!(Attributes::parse_gff3("ID").is_err());
+        assert!(Attributes::parse_gff3("ID=a%2").is_err());
+        assert!(Attributes::parse_gff3("ID=a%zz").is_err());
+    }
+
+    #[test]
+    fn write_gff3_round_trip() {
+        let text = "ID=e1;Parent=t1,t2;Note=a%3Bb%2Cc";
+        let attr = Attributes::parse_gff3(text).unwrap();
+        let mut observed = String::new();
+        attr.write_gff3(&mut observed).unwrap();
+        assert_eq!(observed, text);
+        let mut observed = String::new();
+        Attributes::default().write_gff3(&mut observed).unwrap();
+        assert_eq!(observed, ".");
+    }
+
+    #[test]
+    fn iterate_in_order() {
+        let attr = Attributes::parse_gtf("b \"2\"; a \"1\";").unwrap();
+        let pairs: Vec<_> = attr.iter().collect();
+        assert_eq!(pairs, vec![("b", "2"), ("a", "1")]);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
+    traits::{
+        fields::{parse_field, parse_list, write_list},
+        ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
     types::Score,
     Bed3, Bed4, Bed6, BedGraph, Coordinates, Strand,
 };
@@ -8,6 +11,10 @@ use derive_new::new;
 use getset::{CopyGetters, Getters, Setters};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// A representation of a Bed12 interval.
 ///
@@ -138,11 +145,126 @@ where
     }
 }
 
+impl<C, T, N, Ts, Te, R> FromFields<C, T> for Bed12<C, T, N, Ts, Te, R, Vec<T>, Vec<T>>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+    Ts: ValueBounds + FromStr,
+    Te: ValueBounds + FromStr,
+    R: MetaBounds + FromStr,
+{
+    const STRAND_FIELD: Option<usize> = Some(5);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            chr,
+            parse_field(fields, 1, "start")?,
+            parse_field(fields, 2, "end")?,
+            parse_field(fields, 3, "name")?,
+            parse_field(fields, 4, "score")?,
+            parse_field(fields, 5, "strand")?,
+            parse_field(fields, 6, "thick_start")?,
+            parse_field(fields, 7, "thick_end")?,
+            parse_field(fields, 8, "item_rgb")?,
+            parse_field(fields, 9, "block_count")?,
+            parse_list(fields, 10, "block_sizes")?,
+            parse_list(fields, 11, "block_starts")?,
+        ))
+    }
+}
+
+impl<C, T, N, Ts, Te, R> ToFields<C, T> for Bed12<C, T, N, Ts, Te, R, Vec<T>, Vec<T>>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+    Ts: ValueBounds + Display,
+    Te: ValueBounds + Display,
+    R: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.start,
+            self.end,
+            self.name,
+            self.score,
+            self.strand,
+            self.thick_start,
+            self.thick_end,
+            self.item_rgb,
+            self.block_count
+        )?;
+        write_list(wtr, &self.block_sizes)?;
+        write_list(wtr, &self.block_starts)
+    }
+}
+
+impl<C, T, N, Ts, Te, R> Display for Bed12<C, T, N, Ts, Te, R, Vec<T>, Vec<T>>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+    Ts: ValueBounds + Display,
+    Te: ValueBounds + Display,
+    R: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod testing {
     use super::*;
 
+    #[test]
+    fn test_display() {
+        let a = Bed12::new(
+            "chr1",
+            10,
+            100,
+            "tx",
+            Score(None),
+            Strand::Reverse,
+            20,
+            90,
+            "255,0,0",
+            2,
+            vec![30, 40],
+            vec![0, 50],
+        );
+        assert_eq!(
+            format!("{a}"),
+            "chr1\t10\t100\ttx\t.\t-\t20\t90\t255,0,0\t2\t30,40,\t0,50,"
+        );
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = [
+            "chr1", "10", "100", "tx", "0", "+", "20", "90", "255,0,0", "2", "30,40,", "0,50,",
+        ];
+        let a: Bed12<String, u32, String, u32, u32, String, Vec<u32>, Vec<u32>> =
+            Bed12::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.chr(), "chr1");
+        assert_eq!(a.start(), 10);
+        assert_eq!(a.end(), 100);
+        assert_eq!(a.name(), "tx");
+        assert_eq!(a.score(), 0.into());
+        assert_eq!(a.strand().unwrap(), Strand::Forward);
+        assert_eq!(a.thick_start(), 20);
+        assert_eq!(a.thick_end(), 90);
+        assert_eq!(a.item_rgb(), "255,0,0");
+        assert_eq!(a.block_count(), 2);
+        assert_eq!(a.block_sizes(), &vec![30, 40]);
+        assert_eq!(a.block_starts(), &vec![0, 50]);
+    }
+
     #[test]
     fn test_init() {
         let a: Bed12<String, usize, usize, usize, usize, usize, usize, usize> = Bed12::empty();
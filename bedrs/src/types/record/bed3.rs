@@ -1,12 +1,19 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
+    traits::{
+        fields::parse_field, region::parse_region, ChromBounds, FromFields, MetaBounds, ParseError,
+        ToFields, ValueBounds,
+    },
     types::Score,
     Bed12, Bed4, Bed6, BedGraph, Coordinates, Strand,
 };
 use bedrs_derive::Coordinates;
-use num_traits::zero;
+use num_traits::{zero, One};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// A representation of a Bed3 Interval.
 ///
@@ -116,10 +123,103 @@ where
     }
 }
 
+impl<C, T> FromFields<C, T> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+{
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let start = parse_field(fields, 1, "start")?;
+        let end = parse_field(fields, 2, "end")?;
+        Ok(Self::new(chr, start, end))
+    }
+}
+
+impl<C, T> ToFields<C, T> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(wtr, "\t{}\t{}", self.start, self.end)
+    }
+}
+
+impl<C, T> Display for Bed3<C, T>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+impl<C, T> FromStr for Bed3<C, T>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    type Err = ParseError;
+
+    /// Parses a samtools / UCSC style region string (`chr1:1,001-2,000`).
+    ///
+    /// Positions are 1-based and inclusive and are converted to the
+    /// half-open convention.
+    /// A bare chromosome name (`chr1`) spans from zero to the maximum
+    /// value of `T`, and any strand suffix (`(+)`) is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bedrs::{Bed3, Coordinates};
+    ///
+    /// let iv: Bed3<String, u32> = "chr1:1,001-2,000".parse().unwrap();
+    /// assert_eq!(iv.chr(), "chr1");
+    /// assert_eq!(iv.start(), 1000);
+    /// assert_eq!(iv.end(), 2000);
+    ///
+    /// assert!("chr1:0-10".parse::<Bed3<String, u32>>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chr, start, end, _) = parse_region(s)?;
+        Ok(Self::new(chr, start, end))
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use super::*;
 
+    #[test]
+    fn test_display() {
+        let a = Bed3::new("chr1", 20, 30);
+        assert_eq!(format!("{a}"), "chr1\t20\t30");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let a: Bed3<String, u32> = "chr1:21-30".parse().unwrap();
+        assert!(a.eq(&Bed3::new("chr1".to_string(), 20, 30)));
+        let a: Bed3<u32, u64> = "2:1,001-2,000(-)".parse().unwrap();
+        assert!(a.eq(&Bed3::new(2, 1000, 2000)));
+        let a: Bed3<String, u32> = "chrM".parse().unwrap();
+        assert_eq!(a.end(), u32::MAX);
+        assert!("chr1:30-21".parse::<Bed3<String, u32>>().is_err());
+        assert!("chr1:1-10".parse::<Bed3<u32, u32>>().is_err());
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = ["chr1", "20", "30", "ignored"];
+        let a: Bed3<String, u32> = Bed3::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.chr(), "chr1");
+        assert_eq!(a.start(), 20);
+        assert_eq!(a.end(), 30);
+        assert!(Bed3::<String, u32>::from_fields("chr1".to_string(), &fields[..2]).is_err());
+    }
+
     #[test]
     fn test_init_numeric() {
         let a = Bed3::new(1, 20, 30);
@@ -1,5 +1,7 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
+    traits::{
+        fields::parse_field, ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
     types::Score,
     Bed12, Bed3, Bed6, BedGraph, Coordinates, Strand,
 };
@@ -9,6 +11,10 @@ use getset::{Getters, Setters};
 use num_traits::zero;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// A representation of a Bed4 Interval.
 ///
@@ -115,10 +121,66 @@ where
     }
 }
 
+impl<C, T, N> FromFields<C, T> for Bed4<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let start = parse_field(fields, 1, "start")?;
+        let end = parse_field(fields, 2, "end")?;
+        let name = parse_field(fields, 3, "name")?;
+        Ok(Self::new(chr, start, end, name))
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for Bed4<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(wtr, "\t{}\t{}\t{}", self.start, self.end, self.name)
+    }
+}
+
+impl<C, T, N> Display for Bed4<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use super::*;
 
+    #[test]
+    fn test_display() {
+        let a = Bed4::new("chr1", 20, 30, "gene");
+        assert_eq!(format!("{a}"), "chr1\t20\t30\tgene");
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = ["chr1", "10", "20", "gene"];
+        let b: Bed4<String, u32, String> = Bed4::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(b.chr(), "chr1");
+        assert_eq!(b.start(), 10);
+        assert_eq!(b.end(), 20);
+        assert_eq!(b.name(), "gene");
+        assert!(
+            Bed4::<String, u32, String>::from_fields("chr1".to_string(), &fields[..3]).is_err()
+        );
+    }
+
     #[test]
     fn test_init_chrom_numeric() {
         let b = Bed4::new(1, 10, 20, "test".to_string());
@@ -1,7 +1,9 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
+    traits::{
+        fields::parse_field, ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
     types::Score,
-    Bed12, Bed3, Bed4, BedGraph, Coordinates, Strand,
+    Bed12, Bed3, Bed4, BedGraph, BroadPeak, Coordinates, NarrowPeak, Strand,
 };
 use bedrs_derive::Coordinates;
 use derive_new::new;
@@ -9,6 +11,10 @@ use getset::{CopyGetters, Getters, Setters};
 use num_traits::zero;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// A representation of a Bed4 Interval.
 ///
@@ -113,6 +119,77 @@ where
     }
 }
 
+impl<C, T, N> From<Bed6<C, T, N>> for NarrowPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(bed: Bed6<C, T, N>) -> Self {
+        Self::new(
+            bed.chr, bed.start, bed.end, bed.name, bed.score, bed.strand, -1.0, -1.0, -1.0, None,
+        )
+    }
+}
+
+impl<C, T, N> From<Bed6<C, T, N>> for BroadPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(bed: Bed6<C, T, N>) -> Self {
+        Self::new(
+            bed.chr, bed.start, bed.end, bed.name, bed.score, bed.strand, -1.0, -1.0, -1.0,
+        )
+    }
+}
+
+impl<C, T, N> FromFields<C, T> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const STRAND_FIELD: Option<usize> = Some(5);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let start = parse_field(fields, 1, "start")?;
+        let end = parse_field(fields, 2, "end")?;
+        let name = parse_field(fields, 3, "name")?;
+        let score = parse_field(fields, 4, "score")?;
+        let strand = parse_field(fields, 5, "strand")?;
+        Ok(Self::new(chr, start, end, name, score, strand))
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}",
+            self.start, self.end, self.name, self.score, self.strand
+        )
+    }
+}
+
+impl<C, T, N> Display for Bed6<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod testing {
@@ -120,6 +197,34 @@ mod testing {
 
     use super::*;
 
+    #[test]
+    fn test_display() {
+        let a = Bed6::new("chr1", 20, 30, "gene", 11.1.into(), Strand::Forward);
+        assert_eq!(format!("{a}"), "chr1\t20\t30\tgene\t11.1\t+");
+        let a = Bed6::new("chr1", 20, 30, "gene", Score(None), Strand::Unknown);
+        assert_eq!(format!("{a}"), "chr1\t20\t30\tgene\t.\t.");
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = ["chr1", "10", "20", "gene", "11.1", "-"];
+        let a: Bed6<String, u32, String> = Bed6::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.chr(), "chr1");
+        assert_eq!(a.start(), 10);
+        assert_eq!(a.end(), 20);
+        assert_eq!(a.name(), "gene");
+        assert_eq!(a.score(), 11.1.into());
+        assert_eq!(a.strand().unwrap(), Strand::Reverse);
+
+        let fields = ["chr1", "10", "20", "gene", ".", "."];
+        let a: Bed6<String, u32, String> = Bed6::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.score(), Score(None));
+        assert_eq!(a.strand().unwrap(), Strand::Unknown);
+
+        let fields = ["chr1", "10", "20", "gene", ".", "x"];
+        assert!(Bed6::<String, u32, String>::from_fields("chr1".to_string(), &fields).is_err());
+    }
+
     #[test]
     fn test_init_chrom_numeric() {
         let a = Bed6::new(1, 10, 20, 0, Score(None), Strand::Unknown);
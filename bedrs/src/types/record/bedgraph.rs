@@ -1,5 +1,7 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
+    traits::{
+        fields::parse_field, ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
     types::Score,
     Bed12, Bed3, Bed6, Coordinates, Strand,
 };
@@ -9,6 +11,10 @@ use getset::{CopyGetters, Getters, Setters};
 use num_traits::zero;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// A representation of a `BedGraph` Interval.
 ///
@@ -101,6 +107,40 @@ where
     }
 }
 
+impl<C, T> FromFields<C, T> for BedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+{
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let start = parse_field(fields, 1, "start")?;
+        let end = parse_field(fields, 2, "end")?;
+        let score = parse_field(fields, 3, "score")?;
+        Ok(Self::new(chr, start, end, score))
+    }
+}
+
+impl<C, T> ToFields<C, T> for BedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(wtr, "\t{}\t{}\t{}", self.start, self.end, self.score)
+    }
+}
+
+impl<C, T> Display for BedGraph<C, T>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use super::*;
@@ -110,6 +150,22 @@ mod testing {
         (a - b).abs() < f64::EPSILON
     }
 
+    #[test]
+    fn test_display() {
+        let b = BedGraph::new("chr1", 10, 20, 66.6);
+        assert_eq!(format!("{b}"), "chr1\t10\t20\t66.6");
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = ["chr1", "10", "20", "66.6"];
+        let b: BedGraph<String, u32> = BedGraph::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(b.chr(), "chr1");
+        assert_eq!(b.start(), 10);
+        assert_eq!(b.end(), 20);
+        assert!(float_eq(b.score(), 66.6));
+    }
+
     #[test]
     fn test_init_chrom_numeric() {
         let b = BedGraph::new(1, 10, 20, 66.6);
@@ -0,0 +1,381 @@
+use crate::{
+    traits::{
+        fields::{parse_field, parse_optional_field, parse_value},
+        ChromBounds, FromFields, IntervalFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
+    types::{Query, Score},
+    Coordinates, Strand, StrandedBed3,
+};
+use derive_new::new;
+use getset::{CopyGetters, Getters, Setters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A representation of a [BEDPE](https://bedtools.readthedocs.io/en/latest/content/general-usage.html#bedpe-format)
+/// paired interval record.
+///
+/// Holds two anchors (e.g. the two ends of a Hi-C contact, a structural
+/// variant breakpoint pair, or a paired-end fragment) along with a
+/// `name` and `score`.
+/// Each anchor is a [`StrandedBed3`] and can be used anywhere a
+/// [`Coordinates`] is expected.
+///
+/// Has ten values
+///     1. `chr1`
+///     2. `start1`
+///     3. `end1`
+///     4. `chr2`
+///     5. `start2`
+///     6. `end2`
+///     7. `name`
+///     8. `score`
+///     9. `strand1`
+///     10. `strand2`
+///
+/// The first six columns are required when parsing, the remaining ones
+/// fall back to their defaults when absent.
+/// An empty name is written as `.`, which is read back as the default name.
+///
+/// As a [`Coordinates`] the pair is placed at its first anchor, so that
+/// containers of pairs sort by `chr1` and `start1` as with `bedtools` and
+/// can be written with a [`BedWriter`](crate::io::BedWriter).
+///
+/// ```
+/// use bedrs::{Bed3, BedPe, Coordinates, Overlap, Score, Strand, StrandedBed3};
+///
+/// let pair = BedPe::new(
+///     StrandedBed3::new(1, 100, 200, Strand::Forward),
+///     StrandedBed3::new(1, 5000, 5100, Strand::Reverse),
+///     "loop_1",
+///     Score(None),
+/// );
+/// assert_eq!(pair.first().start(), 100);
+/// assert_eq!(pair.second().strand(), Some(Strand::Reverse));
+/// assert!(pair.second().overlaps(&Bed3::new(1, 5050, 6000)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, new, Getters, Setters, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BedPe<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    #[getset(get = "pub", set = "pub")]
+    first: StrandedBed3<C, T>,
+    #[getset(get = "pub", set = "pub")]
+    second: StrandedBed3<C, T>,
+    #[getset(get = "pub", set = "pub")]
+    name: N,
+    #[getset(get_copy = "pub", set = "pub")]
+    score: Score,
+}
+
+impl<C, T, N> BedPe<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Returns true if both anchors lie on the same chromosome
+    pub fn is_intrachromosomal(&self) -> bool {
+        self.first.chr() == self.second.chr()
+    }
+
+    /// Returns whether each anchor of this pair overlaps an anchor of the
+    /// other pair in the same orientation (`first`/`first` and
+    /// `second`/`second`) or in the swapped orientation.
+    pub(crate) fn pair_overlaps<M>(&self, other: &BedPe<C, T, M>, query: Query<T>) -> (bool, bool)
+    where
+        M: MetaBounds,
+    {
+        let ff = query.predicate(&other.first, &self.first);
+        let ss = query.predicate(&other.second, &self.second);
+        let fs = query.predicate(&other.second, &self.first);
+        let sf = query.predicate(&other.first, &self.second);
+        (ff || ss || fs || sf, (ff && ss) || (fs && sf))
+    }
+}
+
+impl<C, T, N> FromFields<C, T> for BedPe<C, T, N>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const INTERVAL_FIELDS: &'static [IntervalFields] =
+        &[((1, "start1"), (2, "end1")), ((4, "start2"), (5, "end2"))];
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let first = StrandedBed3::new(
+            chr,
+            parse_field(fields, 1, "start1")?,
+            parse_field(fields, 2, "end1")?,
+            parse_optional_field(fields, 8, "strand1")?,
+        );
+        let second = StrandedBed3::new(
+            parse_field(fields, 3, "chr2")?,
+            parse_field(fields, 4, "start2")?,
+            parse_field(fields, 5, "end2")?,
+            parse_optional_field(fields, 9, "strand2")?,
+        );
+        Ok(Self::new(
+            first,
+            second,
+            match fields.get(6) {
+                Some(&".") | None => N::default(),
+                Some(name) => parse_value(name, "name")?,
+            },
+            parse_optional_field(fields, 7, "score")?,
+        ))
+    }
+}
+
+impl<C, T, N> Coordinates<C, T> for BedPe<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn empty() -> Self {
+        Self::default()
+    }
+    fn start(&self) -> T {
+        self.first.start()
+    }
+    fn end(&self) -> T {
+        self.first.end()
+    }
+    fn chr(&self) -> &C {
+        self.first.chr()
+    }
+    fn strand(&self) -> Option<Strand> {
+        self.first.strand()
+    }
+    fn update_start(&mut self, val: &T) {
+        self.first.update_start(val);
+    }
+    fn update_end(&mut self, val: &T) {
+        self.first.update_end(val);
+    }
+    fn update_chr(&mut self, val: &C) {
+        self.first.update_chr(val);
+    }
+    fn update_strand(&mut self, strand: Option<Strand>) {
+        self.first.update_strand(strand);
+    }
+    fn from<Iv: Coordinates<C, T>>(other: &Iv) -> Self {
+        Self {
+            first: Coordinates::from(other),
+            ..Self::default()
+        }
+    }
+}
+
+impl<C, T, N> Coordinates<C, T> for &BedPe<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn empty() -> Self {
+        unimplemented!("Cannot create an empty interval as an immutable reference")
+    }
+    fn start(&self) -> T {
+        self.first.start()
+    }
+    fn end(&self) -> T {
+        self.first.end()
+    }
+    fn chr(&self) -> &C {
+        self.first.chr()
+    }
+    fn strand(&self) -> Option<Strand> {
+        self.first.strand()
+    }
+    fn update_start(&mut self, _val: &T) {
+        unimplemented!("Cannot update an immutable reference")
+    }
+    fn update_end(&mut self, _val: &T) {
+        unimplemented!("Cannot update an immutable reference")
+    }
+    fn update_chr(&mut self, _val: &C) {
+        unimplemented!("Cannot update an immutable reference")
+    }
+    fn update_strand(&mut self, _strand: Option<Strand>) {
+        unimplemented!("Cannot update an immutable reference")
+    }
+    fn from<Iv: Coordinates<C, T>>(_other: &Iv) -> Self {
+        unimplemented!("Cannot create a new reference from a reference")
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for BedPe<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        let name = self.name.to_string();
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.first.start(),
+            self.first.end(),
+            self.second.chr(),
+            self.second.start(),
+            self.second.end(),
+            if name.is_empty() { "." } else { &name },
+            self.score,
+            self.first.strand().unwrap_or_default(),
+            self.second.strand().unwrap_or_default(),
+        )
+    }
+}
+
+impl<C, T, N> Display for BedPe<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.first.chr())?;
+        self.write_fields(f)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::{
+        io::{BedReader, BedWriter},
+        types::StrandMethod,
+        IntervalContainer,
+    };
+
+    fn build_pair(s1: u32, e1: u32, s2: u32, e2: u32) -> BedPe<u32, u32, u32> {
+        BedPe::new(
+            StrandedBed3::new(1, s1, e1, Strand::Forward),
+            StrandedBed3::new(1, s2, e2, Strand::Reverse),
+            0,
+            Score(None),
+        )
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = [
+            "chr1", "100", "200", "chr5", "5000", "5100", "bedpe_1", "30", "+", "-",
+        ];
+        let pair: BedPe<String, u32, String> =
+            BedPe::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(pair.first().chr(), "chr1");
+        assert_eq!(pair.first().start(), 100);
+        assert_eq!(pair.first().end(), 200);
+        assert_eq!(pair.first().strand(), Some(Strand::Forward));
+        assert_eq!(pair.second().chr(), "chr5");
+        assert_eq!(pair.second().start(), 5000);
+        assert_eq!(pair.second().end(), 5100);
+        assert_eq!(pair.second().strand(), Some(Strand::Reverse));
+        assert_eq!(pair.name(), "bedpe_1");
+        assert_eq!(pair.score(), Score::new(30.0));
+        assert!(!pair.is_intrachromosomal());
+    }
+
+    #[test]
+    fn test_from_fields_minimal() {
+        let fields = ["chr1", "100", "200", "chr1", "5000", "5100"];
+        let pair: BedPe<String, u32, String> =
+            BedPe::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(pair.name(), "");
+        assert_eq!(pair.score(), Score(None));
+        assert_eq!(pair.first().strand(), Some(Strand::Unknown));
+        assert!(pair.is_intrachromosomal());
+
+        let fields = ["chr1", "100", "200"];
+        assert!(BedPe::<String, u32, String>::from_fields("chr1".to_string(), &fields).is_err());
+        let fields = ["chr1", "100", "200", "chr1", "5000"];
+        assert!(BedPe::<String, u32, String>::from_fields("chr1".to_string(), &fields).is_err());
+    }
+
+    #[test]
+    fn test_read_and_display() {
+        let text = "chr1\t100\t200\tchr5\t5000\t5100\tp1\t.\t+\t-\n";
+        let reader: BedReader<_, BedPe<String, u32, String>, _, _> =
+            BedReader::new(text.as_bytes());
+        let pairs = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(format!("{}\n", pairs[0]), text);
+    }
+
+    #[test]
+    fn test_write_and_read_back() {
+        let pairs = IntervalContainer::from_unsorted(vec![
+            BedPe::new(
+                StrandedBed3::new("chr2".to_string(), 10, 20, Strand::Forward),
+                StrandedBed3::new("chr1".to_string(), 50, 60, Strand::Unknown),
+                String::new(),
+                Score::new(1.5),
+            ),
+            BedPe::new(
+                StrandedBed3::new("chr1".to_string(), 100, 200, Strand::Reverse),
+                StrandedBed3::new("chr5".to_string(), 5000, 5100, Strand::Forward),
+                "p1".to_string(),
+                Score(None),
+            ),
+        ]);
+        // Pairs are sorted by their first anchor
+        assert_eq!(pairs.records()[0].name(), "p1");
+
+        let mut writer = BedWriter::new(Vec::new());
+        writer.write_records(pairs.records()).unwrap();
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(
+            text,
+            "chr1\t100\t200\tchr5\t5000\t5100\tp1\t.\t-\t+\n\
+             chr2\t10\t20\tchr1\t50\t60\t.\t1.5\t+\t.\n"
+        );
+
+        let reader: BedReader<_, BedPe<String, u32, String>, _, _> =
+            BedReader::new(text.as_bytes());
+        let read = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        for (a, b) in read.iter().zip(pairs.records()) {
+            assert_eq!(a.to_string(), b.to_string());
+            assert_eq!(a.second().strand(), b.second().strand());
+            assert_eq!(a.name(), b.name());
+        }
+    }
+
+    #[test]
+    fn test_pair_overlaps() {
+        let query = Query::default();
+        let a = build_pair(10, 20, 100, 200);
+
+        let b = build_pair(15, 25, 150, 250);
+        assert_eq!(a.pair_overlaps(&b, query), (true, true));
+
+        let b = build_pair(150, 250, 15, 25);
+        assert_eq!(a.pair_overlaps(&b, query), (true, true));
+
+        let b = build_pair(15, 25, 300, 400);
+        assert_eq!(a.pair_overlaps(&b, query), (true, false));
+
+        let b = build_pair(300, 400, 500, 600);
+        assert_eq!(a.pair_overlaps(&b, query), (false, false));
+    }
+
+    #[test]
+    fn test_pair_overlaps_stranded() {
+        let a = build_pair(10, 20, 100, 200);
+        let b = build_pair(150, 250, 15, 25);
+        let query = Query::new_strandedness(StrandMethod::MatchStrand);
+        assert_eq!(a.pair_overlaps(&b, query), (false, false));
+        let query = Query::new_strandedness(StrandMethod::OppositeStrand);
+        assert_eq!(a.pair_overlaps(&b, query), (true, true));
+    }
+}
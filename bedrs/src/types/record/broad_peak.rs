@@ -0,0 +1,218 @@
+use crate::{
+    traits::{
+        fields::parse_field, ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
+    types::Score,
+    Bed3, Bed6, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{CopyGetters, Getters, Setters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A representation of an [ENCODE broadPeak](https://genome.ucsc.edu/FAQ/FAQformat.html#format13)
+/// record.
+///
+/// Has nine values
+///     1. `chr`
+///     2. `start`
+///     3. `end`
+///     4. `name`
+///     5. `score`
+///     6. `strand`
+///     7. `signal_value` - overall enrichment of the region
+///     8. `p_value` - `-log10` p-value (`-1` if not assigned)
+///     9. `q_value` - `-log10` q-value (`-1` if not assigned)
+///
+/// ```
+/// use bedrs::{BroadPeak, Coordinates, Score, Strand};
+///
+/// let a = BroadPeak::new("chr1", 100, 3000, "domain_1", Score(None), Strand::Unknown, 2.1, 8.3, -1.0);
+/// assert_eq!(a.end(), 3000);
+/// assert_eq!(a.p_value(), 8.3);
+/// ```
+#[allow(clippy::too_many_arguments)]
+#[derive(Debug, Default, Clone, Copy, Coordinates, new, Getters, Setters, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BroadPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    chr: C,
+    start: T,
+    end: T,
+    #[getset(get = "pub", set = "pub")]
+    name: N,
+    #[getset(get_copy = "pub", set = "pub")]
+    score: Score,
+    strand: Strand,
+    #[getset(get_copy = "pub", set = "pub")]
+    signal_value: f64,
+    #[getset(get_copy = "pub", set = "pub")]
+    p_value: f64,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_value: f64,
+}
+
+impl<C, T, N> From<BroadPeak<C, T, N>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(peak: BroadPeak<C, T, N>) -> Self {
+        Self::new(peak.chr, peak.start, peak.end)
+    }
+}
+
+impl<C, T, N> From<BroadPeak<C, T, N>> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(peak: BroadPeak<C, T, N>) -> Self {
+        Self::new(
+            peak.chr,
+            peak.start,
+            peak.end,
+            peak.name,
+            peak.score,
+            peak.strand,
+        )
+    }
+}
+
+impl<C, T, N> FromFields<C, T> for BroadPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const STRAND_FIELD: Option<usize> = Some(5);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        Ok(Self::new(
+            chr,
+            parse_field(fields, 1, "start")?,
+            parse_field(fields, 2, "end")?,
+            parse_field(fields, 3, "name")?,
+            parse_field(fields, 4, "score")?,
+            parse_field(fields, 5, "strand")?,
+            parse_field(fields, 6, "signal_value")?,
+            parse_field(fields, 7, "p_value")?,
+            parse_field(fields, 8, "q_value")?,
+        ))
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for BroadPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.start,
+            self.end,
+            self.name,
+            self.score,
+            self.strand,
+            self.signal_value,
+            self.p_value,
+            self.q_value,
+        )
+    }
+}
+
+impl<C, T, N> Display for BroadPeak<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_from_fields() {
+        let fields = ["chr1", "10", "2000", "d", "500", "-", "3.5", "10.2", "8.1"];
+        let a: BroadPeak<String, u32, String> =
+            BroadPeak::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.end(), 2000);
+        assert_eq!(a.name(), "d");
+        assert_eq!(a.score(), 500.into());
+        assert_eq!(a.strand(), Some(Strand::Reverse));
+        assert_eq!(a.signal_value(), 3.5);
+        assert_eq!(a.p_value(), 10.2);
+        assert_eq!(a.q_value(), 8.1);
+
+        let fields = ["chr1", "10", "2000", "d", "500", "-", "3.5", "x", "8.1"];
+        assert!(
+            BroadPeak::<String, u32, String>::from_fields("chr1".to_string(), &fields).is_err()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let a = BroadPeak::new(
+            "chr1",
+            10,
+            20,
+            "d",
+            Score(None),
+            Strand::Unknown,
+            1.5,
+            -1.0,
+            -1.0,
+        );
+        assert_eq!(format!("{a}"), "chr1\t10\t20\td\t.\t.\t1.5\t-1\t-1");
+    }
+
+    #[test]
+    fn convert_to_bed6() {
+        let a = BroadPeak::new(
+            1,
+            10,
+            20,
+            "d".to_string(),
+            5.into(),
+            Strand::Forward,
+            1.0,
+            2.0,
+            3.0,
+        );
+        let b: Bed6<i32, i32, String> = a.into();
+        assert_eq!(b.start(), 10);
+        assert_eq!(b.name(), "d");
+        assert_eq!(b.strand(), Some(Strand::Forward));
+    }
+
+    #[test]
+    fn from_bed6() {
+        let a = Bed6::new(1, 10, 20, "d".to_string(), 5.into(), Strand::Forward);
+        let b: BroadPeak<i32, i32, String> = a.into();
+        assert_eq!(b.score(), 5.into());
+        assert_eq!(b.signal_value(), -1.0);
+        assert_eq!(b.p_value(), -1.0);
+        assert_eq!(b.q_value(), -1.0);
+    }
+}
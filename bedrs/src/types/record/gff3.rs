@@ -0,0 +1,264 @@
+use crate::{
+    traits::{
+        fields::{get_field, parse_field, parse_value},
+        ChromBounds, FromFields, IntervalFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
+    types::{enums::Frame, Attributes, Score},
+    Bed3, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{CopyGetters, Getters, Setters};
+use num_traits::One;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// This is a representation of the [GFF3 file format](https://github.com/The-Sequence-Ontology/Specifications/blob/master/gff3.md).
+///
+/// The GFF3 format is a tab-delimited file that must have the following columns:
+/// 1. seqid - The name of the sequence. Must be a chromosome or scaffold.
+/// 2. source - The program that generated this feature.
+/// 3. type - The type of this feature (e.g. "gene", "mRNA", "exon").
+/// 4. start - The starting position of the feature in the sequence. The first base is numbered 1.
+/// 5. end - The ending position of the feature (inclusive).
+/// 6. score - The score of the feature. If there is no score, use ".".
+/// 7. strand - The strand on which the feature is located. Valid values include '+', '-', or '.'.
+/// 8. phase - The phase of a CDS feature. It must be a number between 0-2 or ".".
+/// 9. attributes - A semicolon-separated list of `key=value` pairs.
+///
+/// The coordinates are stored 0-based and half-open and are converted
+/// from (and back to) the 1-based inclusive GFF3 convention.
+///
+/// Attributes are stored parsed and percent-decoded so that the `ID` and
+/// `Parent` hierarchy can be resolved.
+///
+/// # Usage
+///
+/// ```
+/// use bedrs::{Gff3, Coordinates, Strand, Score, Frame, Attributes};
+///
+/// let record = Gff3::new(
+///     "chr1",
+///     "RefSeq",
+///     "exon",
+///     1000,
+///     4000,
+///     Score(None),
+///     Strand::Forward,
+///     Frame::None,
+///     Attributes::parse_gff3("ID=exon-1;Parent=rna-1,rna-2").unwrap(),
+/// );
+/// assert_eq!(record.chr(), &"chr1");
+/// assert_eq!(record.start(), 1000);
+/// assert_eq!(record.id(), Some("exon-1"));
+/// assert_eq!(record.parents().collect::<Vec<_>>(), vec!["rna-1", "rna-2"]);
+/// ```
+#[allow(clippy::too_many_arguments)]
+#[derive(Debug, Clone, Default, Coordinates, Getters, Setters, CopyGetters, new)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Gff3<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    chr: C,
+    #[getset(get = "pub", set = "pub")]
+    source: N,
+    #[getset(get = "pub", set = "pub")]
+    feature: N,
+    start: T,
+    end: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    score: Score,
+    strand: Strand,
+    #[getset(get_copy = "pub", set = "pub")]
+    phase: Frame,
+    #[getset(get = "pub", set = "pub")]
+    attributes: Attributes,
+}
+
+impl<C, T, N> Gff3<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Returns the `ID` attribute of the feature
+    pub fn id(&self) -> Option<&str> {
+        self.attributes.get("ID")
+    }
+
+    /// Returns the `Name` attribute of the feature
+    pub fn name(&self) -> Option<&str> {
+        self.attributes.get("Name")
+    }
+
+    /// Returns all `Parent` identifiers of the feature
+    pub fn parents(&self) -> impl Iterator<Item = &str> {
+        self.attributes.get_all("Parent")
+    }
+}
+
+impl<C, T, N> From<Gff3<C, T, N>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(record: Gff3<C, T, N>) -> Self {
+        Self::new(record.chr, record.start, record.end)
+    }
+}
+
+impl<C, T, N> FromFields<C, T> for Gff3<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const INTERVAL_FIELDS: &'static [IntervalFields] = &[((3, "start"), (4, "end"))];
+    const STRAND_FIELD: Option<usize> = Some(6);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let raw_start = get_field(fields, 3, "start")?;
+        let start: T = parse_value(raw_start, "start")?;
+        if start < T::one() {
+            return Err(ParseError::InvalidField {
+                field: "start",
+                value: raw_start.to_string(),
+            });
+        }
+        Ok(Self::new(
+            chr,
+            parse_field(fields, 1, "source")?,
+            parse_field(fields, 2, "feature")?,
+            start - T::one(),
+            parse_field(fields, 4, "end")?,
+            parse_field(fields, 5, "score")?,
+            parse_field(fields, 6, "strand")?,
+            parse_field(fields, 7, "phase")?,
+            Attributes::parse_gff3(get_field(fields, 8, "attributes")?)?,
+        ))
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for Gff3<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + One + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t",
+            self.source,
+            self.feature,
+            self.start + T::one(),
+            self.end,
+            self.score,
+            self.strand,
+            self.phase,
+        )?;
+        self.attributes.write_gff3(wtr)
+    }
+}
+
+impl<C, T, N> Display for Gff3<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + One + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::IntervalContainer;
+
+    fn build_record() -> Gff3<String, u64, String> {
+        let fields = [
+            "NC_000001.11",
+            "BestRefSeq",
+            "exon",
+            "11874",
+            "12227",
+            ".",
+            "+",
+            ".",
+            "ID=exon-1;Parent=rna-1,rna-2;gene=DDX11L1",
+        ];
+        Gff3::from_fields("NC_000001.11".to_string(), &fields).unwrap()
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let record = build_record();
+        assert_eq!(record.chr(), "NC_000001.11");
+        assert_eq!(record.source(), "BestRefSeq");
+        assert_eq!(record.feature(), "exon");
+        assert_eq!(record.start(), 11873);
+        assert_eq!(record.end(), 12227);
+        assert_eq!(record.score(), Score(None));
+        assert_eq!(record.strand(), Some(Strand::Forward));
+        assert_eq!(record.phase(), Frame::None);
+        assert_eq!(record.id(), Some("exon-1"));
+        assert_eq!(record.name(), None);
+        assert_eq!(record.parents().collect::<Vec<_>>(), vec!["rna-1", "rna-2"]);
+        assert_eq!(record.attributes().get("gene"), Some("DDX11L1"));
+    }
+
+    #[test]
+    fn test_from_fields_invalid() {
+        let fields = ["chr1", "src", "gene", "0", "10", ".", "+", ".", "ID=a"];
+        assert!(Gff3::<String, u64, String>::from_fields("chr1".to_string(), &fields).is_err());
+        let fields = ["chr1", "src", "gene", "1", "10", ".", "+", ".", "ID"];
+        assert!(Gff3::<String, u64, String>::from_fields("chr1".to_string(), &fields).is_err());
+        let fields = ["chr1", "src", "gene", "1", "10", ".", "+", "."];
+        assert!(Gff3::<String, u64, String>::from_fields("chr1".to_string(), &fields).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let record = build_record();
+        assert_eq!(
+            format!("{record}"),
+            "NC_000001.11\tBestRefSeq\texon\t11874\t12227\t.\t+\t.\tID=exon-1;Parent=rna-1,rna-2;gene=DDX11L1"
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut record = build_record();
+        record.set_feature("CDS".to_string());
+        record.set_phase(Frame::Two);
+        record.set_score(Score::new(1.0));
+        assert_eq!(record.feature(), "CDS");
+        assert_eq!(record.phase(), Frame::Two);
+        assert_eq!(record.score(), Score::new(1.0));
+    }
+
+    #[test]
+    fn test_into_bed3() {
+        let bed: Bed3<String, u64> = build_record().into();
+        assert_eq!(bed.start(), 11873);
+        assert_eq!(bed.end(), 12227);
+    }
+
+    #[test]
+    fn test_merge() {
+        let set = IntervalContainer::from_unsorted(vec![build_record(), build_record()]);
+        let merged = set.merge().unwrap();
+        assert_eq!(merged.len(), 1);
+    }
+}
@@ -1,13 +1,21 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
-    types::{enums::Frame, Score},
+    traits::{
+        fields::{get_field, parse_field, parse_value},
+        ChromBounds, FromFields, IntervalFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
+    types::{enums::Frame, Attributes, Score},
     Bed3, Coordinates, Strand,
 };
 use bedrs_derive::Coordinates;
 use derive_new::new;
 use getset::{CopyGetters, Getters, Setters};
+use num_traits::One;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// This is a representation of the [GTF file format](https://www.ensembl.org/info/website/upload/gff.html)
 /// which is identical to the GFF version 2 format.
@@ -23,6 +31,13 @@ use serde::{Deserialize, Serialize};
 /// 8. frame - The frame of the feature. It must be a number between 0-2 or "." if the feature is not a coding exon.
 /// 9. attributes - A semicolon-separated list of tag-value pairs, providing additional information about each feature.
 ///
+/// Like every other record in this library, the coordinates of a [`Gtf`] are stored
+/// 0-based and half-open.
+/// They are converted from the 1-based inclusive GTF convention when parsed
+/// and written back in that convention when formatted.
+/// Records built by hand from 1-based values can be converted with
+/// [`OneBased`](crate::OneBased).
+///
 /// # Usage
 ///
 /// ```
@@ -80,6 +95,103 @@ where
     }
 }
 
+impl<C, T, N> Gtf<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds + AsRef<str>,
+{
+    /// Parses the attributes column into an [`Attributes`] map.
+    ///
+    /// ```
+    /// use bedrs::{Gtf, Score, Strand, Frame};
+    ///
+    /// let record = Gtf::new(
+    ///     "chr1",
+    ///     "HAVANA",
+    ///     "exon",
+    ///     10,
+    ///     20,
+    ///     Score(None),
+    ///     Strand::Forward,
+    ///     Frame::None,
+    ///     "gene_id \"ENSG01\"; tag \"basic\"; tag \"CCDS\";",
+    /// );
+    /// let attr = record.parse_attributes().unwrap();
+    /// assert_eq!(attr.get("gene_id"), Some("ENSG01"));
+    /// assert_eq!(attr.get_all("tag").count(), 2);
+    /// ```
+    pub fn parse_attributes(&self) -> Result<Attributes, ParseError> {
+        Attributes::parse_gtf(self.attributes.as_ref())
+    }
+}
+
+impl<C, T, N> FromFields<C, T> for Gtf<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const INTERVAL_FIELDS: &'static [IntervalFields] = &[((3, "start"), (4, "end"))];
+    const STRAND_FIELD: Option<usize> = Some(6);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let raw_start = get_field(fields, 3, "start")?;
+        let start: T = parse_value(raw_start, "start")?;
+        if start < T::one() {
+            return Err(ParseError::InvalidField {
+                field: "start",
+                value: raw_start.to_string(),
+            });
+        }
+        Ok(Self::new(
+            chr,
+            parse_field(fields, 1, "source")?,
+            parse_field(fields, 2, "feature")?,
+            start - T::one(),
+            parse_field(fields, 4, "end")?,
+            parse_field(fields, 5, "score")?,
+            parse_field(fields, 6, "strand")?,
+            parse_field(fields, 7, "frame")?,
+            parse_field(fields, 8, "attributes")?,
+        ))
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for Gtf<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + One + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.source,
+            self.feature,
+            self.start + T::one(),
+            self.end,
+            self.score,
+            self.strand,
+            self.frame,
+            self.attributes
+        )
+    }
+}
+
+impl<C, T, N> Display for Gtf<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + One + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use crate::IntervalContainer;
@@ -146,6 +258,62 @@ mod testing {
         assert_eq!(record.attributes(), &"");
     }
 
+    #[test]
+    fn test_display() {
+        let record = Gtf::new(
+            "chr1",
+            "Ensembl",
+            "exon",
+            9,
+            30,
+            Score(None),
+            Strand::Reverse,
+            Frame::None,
+            "gene_id \"a\";",
+        );
+        assert_eq!(
+            format!("{record}"),
+            "chr1\tEnsembl\texon\t10\t30\t.\t-\t.\tgene_id \"a\";"
+        );
+    }
+
+    #[test]
+    fn test_from_fields() {
+        let fields = [
+            "chr1",
+            "HAVANA",
+            "exon",
+            "11",
+            "30",
+            ".",
+            "-",
+            "2",
+            "gene_id \"g1\"; transcript_id \"t1\";",
+        ];
+        let record: Gtf<String, u64, String> =
+            Gtf::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(record.chr(), "chr1");
+        assert_eq!(record.source(), "HAVANA");
+        assert_eq!(record.feature(), "exon");
+        assert_eq!(record.start(), 10);
+        assert_eq!(record.end(), 30);
+        assert_eq!(record.len(), 20);
+        assert_eq!(record.score(), Score(None));
+        assert_eq!(record.strand(), Some(Strand::Reverse));
+        assert_eq!(record.frame(), Frame::Two);
+        let attr = record.parse_attributes().unwrap();
+        assert_eq!(attr.get("transcript_id"), Some("t1"));
+
+        // round trip back to the 1-based convention
+        assert_eq!(format!("{record}"), fields.join("\t"));
+    }
+
+    #[test]
+    fn test_from_fields_zero_start() {
+        let fields = ["chr1", "src", "exon", "0", "30", ".", "-", ".", ""];
+        assert!(Gtf::<String, u64, String>::from_fields("chr1".to_string(), &fields).is_err());
+    }
+
     #[test]
     fn test_collect() {
         let set: IntervalContainer<Gtf<usize, usize, usize>, _, _> =
@@ -1,5 +1,5 @@
 use crate::{
-    traits::{ChromBounds, MetaBounds, ValueBounds},
+    traits::{ChromBounds, MetaBounds, ToFields, ValueBounds},
     Bed3, Coordinates, Strand,
 };
 use bedrs_derive::Coordinates;
@@ -7,6 +7,7 @@ use derive_new::new;
 use getset::{Getters, Setters};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
 
 /// A representation of a Meta Interval.
 ///
@@ -54,10 +55,39 @@ where
     }
 }
 
+impl<C, T, M> ToFields<C, T> for MetaInterval<C, T, M>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    M: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(wtr, "\t{}\t{}\t{}", self.start, self.end, self.meta)
+    }
+}
+
+impl<C, T, M> Display for MetaInterval<C, T, M>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    M: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use super::*;
 
+    #[test]
+    fn test_display() {
+        let a = MetaInterval::new("chr1", 20, 30, "meta");
+        assert_eq!(format!("{a}"), "chr1\t20\t30\tmeta");
+    }
+
     #[test]
     fn test_init_numeric() {
         let a = MetaInterval::new(1, 20, 30, 100);
@@ -1,19 +1,38 @@
+mod attributes;
 mod base_interval;
 mod bed12;
 mod bed3;
 mod bed4;
 mod bed6;
 mod bedgraph;
+mod bedpe;
+mod broad_peak;
+mod gff3;
 mod gtf;
 mod meta_interval;
+mod multi_segment;
+mod narrow_peak;
+mod psl;
+mod sam;
 mod stranded_genomic_interval;
+mod union_bedgraph;
 
+pub use attributes::Attributes;
 pub use base_interval::BaseInterval;
 pub use bed12::Bed12;
 pub use bed3::Bed3;
 pub use bed4::Bed4;
 pub use bed6::Bed6;
 pub use bedgraph::BedGraph;
+pub use bedpe::BedPe;
+pub use broad_peak::BroadPeak;
+pub use gff3::Gff3;
 pub use gtf::Gtf;
 pub use meta_interval::MetaInterval;
+pub use multi_segment::MultiSegment;
+pub use narrow_peak::NarrowPeak;
+pub use psl::Psl;
+pub(crate) use sam::is_unmapped;
+pub use sam::Sam;
 pub use stranded_genomic_interval::StrandedBed3;
+pub use union_bedgraph::UnionBedGraph;
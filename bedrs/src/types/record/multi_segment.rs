@@ -0,0 +1,158 @@
+use crate::{
+    traits::{ChromBounds, ToFields, ValueBounds},
+    Bed3, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{CopyGetters, Getters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// An elementary segment of a multi-way intersection along with the
+/// input sets covering it.
+///
+/// Has five values
+///     1. `chr`
+///     2. `start`
+///     3. `end`
+///     4. `members` - the sorted indices of the sets covering the segment
+///     5. `n_sets` - the number of sets in the intersection
+///
+/// Segments are written as with `bedtools multiinter`: the number of
+/// covering sets, their comma separated 1-based indices, and a `0`/`1`
+/// column per set.
+///
+/// ```
+/// use bedrs::{Coordinates, MultiSegment};
+///
+/// let seg = MultiSegment::new(1, 20, 30, vec![0, 2], 3);
+/// assert_eq!(seg.count(), 2);
+/// assert!(seg.is_member(2));
+/// assert!(!seg.is_member(1));
+/// assert_eq!(seg.mask(), vec![true, false, true]);
+/// assert!(!seg.is_shared());
+/// assert_eq!(format!("{seg}"), "1\t20\t30\t2\t1,3\t1\t0\t1");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Coordinates, Getters, CopyGetters, new)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiSegment<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    chr: C,
+    start: T,
+    end: T,
+    #[getset(get = "pub")]
+    members: Vec<usize>,
+    #[getset(get_copy = "pub")]
+    n_sets: usize,
+}
+
+impl<C, T> MultiSegment<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Returns the number of sets covering the segment
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the set at the index covers the segment
+    pub fn is_member(&self, idx: usize) -> bool {
+        self.members.binary_search(&idx).is_ok()
+    }
+
+    /// Whether every set covers the segment
+    pub fn is_shared(&self) -> bool {
+        self.count() == self.n_sets
+    }
+
+    /// Whether the segment is covered by the set at the index alone
+    pub fn is_unique_to(&self, idx: usize) -> bool {
+        self.members == [idx]
+    }
+
+    /// Returns the membership of each set as a mask
+    pub fn mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; self.n_sets];
+        for &idx in &self.members {
+            mask[idx] = true;
+        }
+        mask
+    }
+}
+
+impl<C, T> From<MultiSegment<C, T>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    fn from(seg: MultiSegment<C, T>) -> Self {
+        Self::new(seg.chr, seg.start, seg.end)
+    }
+}
+
+impl<C, T> ToFields<C, T> for MultiSegment<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(wtr, "\t{}\t{}\t{}\t", self.start, self.end, self.count())?;
+        for (i, idx) in self.members.iter().enumerate() {
+            if i > 0 {
+                write!(wtr, ",")?;
+            }
+            write!(wtr, "{}", idx + 1)?;
+        }
+        for member in self.mask() {
+            write!(wtr, "\t{}", u8::from(member))?;
+        }
+        Ok(())
+    }
+}
+
+impl<C, T> Display for MultiSegment<C, T>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_membership() {
+        let seg = MultiSegment::new("chr1", 10, 20, vec![0, 1, 2], 3);
+        assert!(seg.is_shared());
+        assert!(!seg.is_unique_to(0));
+        assert_eq!(seg.mask(), vec![true; 3]);
+
+        let seg = MultiSegment::new("chr1", 10, 20, vec![1], 3);
+        assert!(seg.is_unique_to(1));
+        assert!(!seg.is_unique_to(0));
+        assert_eq!(seg.count(), 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let seg = MultiSegment::new("chr1", 10, 20, vec![1, 3], 4);
+        assert_eq!(format!("{seg}"), "chr1\t10\t20\t2\t2,4\t0\t1\t0\t1");
+    }
+
+    #[test]
+    fn test_bed3_conversion() {
+        let seg = MultiSegment::new("chr1", 10, 20, vec![0], 1);
+        let bed: Bed3<_, _> = seg.into();
+        assert_eq!((bed.start(), bed.end()), (10, 20));
+    }
+}
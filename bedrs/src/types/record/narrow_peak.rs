@@ -0,0 +1,343 @@
+use crate::{
+    traits::{
+        fields::{get_field, parse_field},
+        ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
+    types::Score,
+    Bed3, Bed6, BroadPeak, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{CopyGetters, Getters, Setters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A representation of an [ENCODE narrowPeak](https://genome.ucsc.edu/FAQ/FAQformat.html#format12)
+/// record.
+///
+/// Has ten values
+///     1. `chr`
+///     2. `start`
+///     3. `end`
+///     4. `name`
+///     5. `score`
+///     6. `strand`
+///     7. `signal_value` - overall enrichment of the region
+///     8. `p_value` - `-log10` p-value (`-1` if not assigned)
+///     9. `q_value` - `-log10` q-value (`-1` if not assigned)
+///     10. `peak` - summit offset from `start` (`-1` if not called)
+///
+/// The summit offset is stored as an `Option` so that it can share the
+/// coordinate type, with `None` read from and written as `-1`.
+///
+/// ```
+/// use bedrs::{Coordinates, NarrowPeak, Score, Strand};
+///
+/// let a = NarrowPeak::new("chr1", 100, 300, "peak_1", Score(None), Strand::Unknown, 5.2, 12.1, 9.8, Some(120));
+/// assert_eq!(a.start(), 100);
+/// assert_eq!(a.signal_value(), 5.2);
+/// assert_eq!(a.summit(), Some(220));
+/// ```
+#[allow(clippy::too_many_arguments)]
+#[derive(Debug, Default, Clone, Copy, Coordinates, new, Getters, Setters, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NarrowPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    chr: C,
+    start: T,
+    end: T,
+    #[getset(get = "pub", set = "pub")]
+    name: N,
+    #[getset(get_copy = "pub", set = "pub")]
+    score: Score,
+    strand: Strand,
+    #[getset(get_copy = "pub", set = "pub")]
+    signal_value: f64,
+    #[getset(get_copy = "pub", set = "pub")]
+    p_value: f64,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_value: f64,
+    #[getset(get_copy = "pub", set = "pub")]
+    peak: Option<T>,
+}
+
+impl<C, T, N> NarrowPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Returns the absolute position of the peak summit if it was called
+    pub fn summit(&self) -> Option<T> {
+        self.peak.map(|offset| self.start + offset)
+    }
+}
+
+impl<C, T, N> From<NarrowPeak<C, T, N>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(peak: NarrowPeak<C, T, N>) -> Self {
+        Self::new(peak.chr, peak.start, peak.end)
+    }
+}
+
+impl<C, T, N> From<NarrowPeak<C, T, N>> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(peak: NarrowPeak<C, T, N>) -> Self {
+        Self::new(
+            peak.chr,
+            peak.start,
+            peak.end,
+            peak.name,
+            peak.score,
+            peak.strand,
+        )
+    }
+}
+
+impl<C, T, N> From<NarrowPeak<C, T, N>> for BroadPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(peak: NarrowPeak<C, T, N>) -> Self {
+        Self::new(
+            peak.chr,
+            peak.start,
+            peak.end,
+            peak.name,
+            peak.score,
+            peak.strand,
+            peak.signal_value,
+            peak.p_value,
+            peak.q_value,
+        )
+    }
+}
+
+impl<C, T, N> FromFields<C, T> for NarrowPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const STRAND_FIELD: Option<usize> = Some(5);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let peak = match get_field(fields, 9, "peak")? {
+            "-1" => None,
+            _ => Some(parse_field(fields, 9, "peak")?),
+        };
+        Ok(Self::new(
+            chr,
+            parse_field(fields, 1, "start")?,
+            parse_field(fields, 2, "end")?,
+            parse_field(fields, 3, "name")?,
+            parse_field(fields, 4, "score")?,
+            parse_field(fields, 5, "strand")?,
+            parse_field(fields, 6, "signal_value")?,
+            parse_field(fields, 7, "p_value")?,
+            parse_field(fields, 8, "q_value")?,
+            peak,
+        ))
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for NarrowPeak<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.start,
+            self.end,
+            self.name,
+            self.score,
+            self.strand,
+            self.signal_value,
+            self.p_value,
+            self.q_value,
+        )?;
+        match self.peak {
+            Some(peak) => write!(wtr, "\t{peak}"),
+            None => write!(wtr, "\t-1"),
+        }
+    }
+}
+
+impl<C, T, N> Display for NarrowPeak<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod testing {
+    use super::*;
+    use crate::IntervalContainer;
+
+    #[test]
+    fn test_from_fields() {
+        let fields = [
+            "chr1", "9356548", "9356648", ".", "0", ".", "182", "5.0945", "-1", "50",
+        ];
+        let a: NarrowPeak<String, u32, String> =
+            NarrowPeak::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.start(), 9_356_548);
+        assert_eq!(a.end(), 9_356_648);
+        assert_eq!(a.name(), ".");
+        assert_eq!(a.score(), Score::new(0.0));
+        assert_eq!(a.strand(), Some(Strand::Unknown));
+        assert_eq!(a.signal_value(), 182.0);
+        assert_eq!(a.p_value(), 5.0945);
+        assert_eq!(a.q_value(), -1.0);
+        assert_eq!(a.peak(), Some(50));
+        assert_eq!(a.summit(), Some(9_356_598));
+    }
+
+    #[test]
+    fn test_from_fields_no_summit() {
+        let fields = ["chr1", "10", "20", "p", "0", "+", "1", "2", "3", "-1"];
+        let a: NarrowPeak<String, u32, String> =
+            NarrowPeak::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!(a.peak(), None);
+        assert_eq!(a.summit(), None);
+
+        let fields = ["chr1", "10", "20", "p", "0", "+", "1", "2", "3"];
+        assert!(
+            NarrowPeak::<String, u32, String>::from_fields("chr1".to_string(), &fields).is_err()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let a = NarrowPeak::new(
+            "chr1",
+            10,
+            20,
+            "p",
+            Score(None),
+            Strand::Forward,
+            1.5,
+            2.0,
+            -1.0,
+            Some(5),
+        );
+        assert_eq!(format!("{a}"), "chr1\t10\t20\tp\t.\t+\t1.5\t2\t-1\t5");
+        let mut a = a;
+        a.set_peak(None);
+        assert_eq!(format!("{a}"), "chr1\t10\t20\tp\t.\t+\t1.5\t2\t-1\t-1");
+    }
+
+    #[test]
+    fn convert_to_bed6() {
+        let a = NarrowPeak::new(
+            1,
+            10,
+            20,
+            "p".to_string(),
+            5.into(),
+            Strand::Reverse,
+            1.0,
+            2.0,
+            3.0,
+            Some(5),
+        );
+        let b: Bed6<i32, i32, String> = a.clone().into();
+        assert!(b.eq(&a));
+        assert_eq!(b.name(), "p");
+        assert_eq!(b.score(), 5.into());
+        assert_eq!(b.strand(), Some(Strand::Reverse));
+    }
+
+    #[test]
+    fn convert_to_broad_peak() {
+        let a = NarrowPeak::new(
+            1,
+            10,
+            20,
+            "p".to_string(),
+            5.into(),
+            Strand::Reverse,
+            1.0,
+            2.0,
+            3.0,
+            Some(5),
+        );
+        let b: BroadPeak<i32, i32, String> = a.into();
+        assert_eq!(b.signal_value(), 1.0);
+        assert_eq!(b.p_value(), 2.0);
+        assert_eq!(b.q_value(), 3.0);
+    }
+
+    #[test]
+    fn from_bed6() {
+        let a = Bed6::new(1, 10, 20, "p".to_string(), 5.into(), Strand::Forward);
+        let b: NarrowPeak<i32, i32, String> = a.into();
+        assert_eq!(b.name(), "p");
+        assert_eq!(b.signal_value(), -1.0);
+        assert_eq!(b.p_value(), -1.0);
+        assert_eq!(b.q_value(), -1.0);
+        assert_eq!(b.peak(), None);
+    }
+
+    #[test]
+    fn merge_narrow_peak() {
+        let set = IntervalContainer::from_unsorted(vec![
+            NarrowPeak::new(
+                1,
+                10,
+                20,
+                0,
+                Score(None),
+                Strand::Unknown,
+                1.0,
+                1.0,
+                1.0,
+                Some(5),
+            ),
+            NarrowPeak::new(
+                1,
+                15,
+                25,
+                0,
+                Score(None),
+                Strand::Unknown,
+                1.0,
+                1.0,
+                1.0,
+                None,
+            ),
+        ]);
+        let merged = set.merge().unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged.records()[0].end(), 25);
+    }
+}
@@ -0,0 +1,515 @@
+use crate::{
+    traits::{
+        fields::{get_field, parse_field, parse_list, parse_value, write_list},
+        ChromBounds, FromFields, IntervalFields, MetaBounds, ParseError, ValueBounds,
+    },
+    types::Score,
+    Bed12, Bed3, Bed6, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{CopyGetters, Getters, Setters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A representation of a [PSL](https://genome.ucsc.edu/FAQ/FAQformat.html#format2)
+/// alignment as written by BLAT and similar aligners.
+///
+/// Has twenty-one values
+///     1. `matches` - matching bases that are not repeats
+///     2. `mismatches` - mismatching bases
+///     3. `rep_matches` - matching bases that are part of repeats
+///     4. `n_count` - `N` bases
+///     5. `q_num_insert` - number of inserts in the query
+///     6. `q_base_insert` - number of bases inserted in the query
+///     7. `t_num_insert` - number of inserts in the target
+///     8. `t_base_insert` - number of bases inserted in the target
+///     9. `strand` - strand of the query, followed by the strand of the
+///        target for translated alignments
+///     10. `q_name`
+///     11. `q_size`
+///     12. `q_start`
+///     13. `q_end`
+///     14. `chr` - the target name
+///     15. `t_size`
+///     16. `start` - the target start
+///     17. `end` - the target end
+///     18. `block_count`
+///     19. `block_sizes`
+///     20. `q_starts`
+///     21. `t_starts`
+///
+/// The interval of the record is the aligned span on the target, so that
+/// alignments can be used anywhere other intervals are.
+/// Its gapped structure is available as [`Bed6`] blocks through
+/// [`Psl::blocks`] or as a whole through the conversion into a [`Bed12`].
+/// Parsing rejects records whose block lists disagree with `block_count`
+/// or whose blocks fall outside of the target span.
+///
+/// ```
+/// use bedrs::{Coordinates, Psl, Strand};
+///
+/// let line = "100\t0\t0\t0\t0\t0\t1\t50\t-\tread1\t100\t0\t100\tchr1\t1000\t200\t350\t2\t60,40,\t0,60,\t200,310,";
+/// let psl: Psl<String, u32, String> = line.parse().unwrap();
+/// assert_eq!(psl.chr(), "chr1");
+/// assert_eq!((psl.start(), psl.end()), (200, 350));
+/// assert_eq!(psl.strand(), Some(Strand::Reverse));
+///
+/// let blocks: Vec<_> = psl.blocks().map(|b| (b.start(), b.end())).collect();
+/// assert_eq!(blocks, vec![(200, 260), (310, 350)]);
+/// ```
+#[allow(clippy::too_many_arguments)]
+#[derive(Debug, Default, Clone, Coordinates, new, Getters, Setters, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Psl<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    #[getset(get_copy = "pub", set = "pub")]
+    matches: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    mismatches: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    rep_matches: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    n_count: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_num_insert: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_base_insert: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    t_num_insert: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    t_base_insert: T,
+    strand: Strand,
+    #[getset(get_copy = "pub", set = "pub")]
+    target_strand: Option<Strand>,
+    #[getset(get = "pub", set = "pub")]
+    q_name: N,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_size: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_start: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    q_end: T,
+    chr: C,
+    #[getset(get_copy = "pub", set = "pub")]
+    t_size: T,
+    start: T,
+    end: T,
+    #[getset(get_copy = "pub", set = "pub")]
+    block_count: T,
+    #[getset(get = "pub", set = "pub")]
+    block_sizes: Vec<T>,
+    #[getset(get = "pub", set = "pub")]
+    q_starts: Vec<T>,
+    #[getset(get = "pub", set = "pub")]
+    t_starts: Vec<T>,
+}
+
+impl<C, T, N> Psl<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Returns the start and end of every aligned block on the forward
+    /// strand of the target in ascending order.
+    ///
+    /// Block starts of translated alignments against the reverse strand
+    /// of the target are converted to forward strand positions.
+    pub fn target_blocks(&self) -> Vec<(T, T)> {
+        let reverse = self.target_strand == Some(Strand::Reverse);
+        let mut blocks: Vec<(T, T)> = self
+            .t_starts
+            .iter()
+            .zip(&self.block_sizes)
+            .map(|(&start, &size)| {
+                if reverse {
+                    let end = self.t_size - start;
+                    (end - size, end)
+                } else {
+                    (start, start + size)
+                }
+            })
+            .collect();
+        if reverse {
+            blocks.reverse();
+        }
+        blocks
+    }
+
+    /// Returns every aligned block as a [`Bed6`] named after the query
+    pub fn blocks(&self) -> impl Iterator<Item = Bed6<C, T, N>> + '_ {
+        let score = self.score();
+        self.target_blocks().into_iter().map(move |(start, end)| {
+            Bed6::new(
+                self.chr.clone(),
+                start,
+                end,
+                self.q_name.clone(),
+                score,
+                self.strand,
+            )
+        })
+    }
+
+    /// Returns the alignment score used by the UCSC tools:
+    /// `matches + rep_matches / 2 - mismatches - q_num_insert - t_num_insert`
+    pub fn score(&self) -> Score {
+        let value = |v: T| v.to_f64().unwrap_or_default();
+        let score = value(self.matches) + (value(self.rep_matches) / 2.0).floor()
+            - value(self.mismatches)
+            - value(self.q_num_insert)
+            - value(self.t_num_insert);
+        Score(Some(score))
+    }
+}
+
+impl<C, T, N> From<Psl<C, T, N>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(psl: Psl<C, T, N>) -> Self {
+        Bed3::new(psl.chr, psl.start, psl.end)
+    }
+}
+
+impl<C, T, N> From<Psl<C, T, N>> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(psl: Psl<C, T, N>) -> Self {
+        let score = psl.score();
+        Bed6::new(psl.chr, psl.start, psl.end, psl.q_name, score, psl.strand)
+    }
+}
+
+#[allow(clippy::mismatching_type_param_order)]
+impl<C, T, N, R> From<Psl<C, T, N>> for Bed12<C, T, N, T, T, R, Vec<T>, Vec<T>>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+    R: MetaBounds,
+{
+    fn from(psl: Psl<C, T, N>) -> Self {
+        let score = psl.score();
+        let (block_sizes, block_starts) = psl
+            .target_blocks()
+            .into_iter()
+            .map(|(start, end)| (end - start, start - psl.start))
+            .unzip();
+        Bed12::new(
+            psl.chr,
+            psl.start,
+            psl.end,
+            psl.q_name,
+            score,
+            psl.strand,
+            psl.start,
+            psl.end,
+            R::default(),
+            psl.block_count,
+            block_sizes,
+            block_starts,
+        )
+    }
+}
+
+/// Parses the query and optional target strands of a PSL strand field
+fn parse_strands(value: &str) -> Result<(Strand, Option<Strand>), ParseError> {
+    let invalid = || ParseError::InvalidField {
+        field: "strand",
+        value: value.to_string(),
+    };
+    let mut chars = value.char_indices();
+    let query = match chars.next() {
+        Some((_, '+')) => Strand::Forward,
+        Some((_, '-')) => Strand::Reverse,
+        _ => return Err(invalid()),
+    };
+    let target = match chars.next() {
+        None => None,
+        Some((idx, _)) => Some(parse_value(&value[idx..], "strand").map_err(|_| invalid())?),
+    };
+    Ok((query, target))
+}
+
+/// The chromosome is the target name (column 14) and is parsed by the
+/// caller, as done by the [`PslReader`](crate::io::PslReader).
+impl<C, T, N> FromFields<C, T> for Psl<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const INTERVAL_FIELDS: &'static [IntervalFields] = &[((15, "t_start"), (16, "t_end"))];
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let (strand, target_strand) = parse_strands(get_field(fields, 8, "strand")?)?;
+        let psl = Self::new(
+            parse_field(fields, 0, "matches")?,
+            parse_field(fields, 1, "mismatches")?,
+            parse_field(fields, 2, "rep_matches")?,
+            parse_field(fields, 3, "n_count")?,
+            parse_field(fields, 4, "q_num_insert")?,
+            parse_field(fields, 5, "q_base_insert")?,
+            parse_field(fields, 6, "t_num_insert")?,
+            parse_field(fields, 7, "t_base_insert")?,
+            strand,
+            target_strand,
+            parse_field(fields, 9, "q_name")?,
+            parse_field(fields, 10, "q_size")?,
+            parse_field(fields, 11, "q_start")?,
+            parse_field(fields, 12, "q_end")?,
+            chr,
+            parse_field(fields, 14, "t_size")?,
+            parse_field(fields, 15, "t_start")?,
+            parse_field(fields, 16, "t_end")?,
+            parse_field(fields, 17, "block_count")?,
+            parse_list(fields, 18, "block_sizes")?,
+            parse_list(fields, 19, "q_starts")?,
+            parse_list(fields, 20, "t_starts")?,
+        );
+        psl.validate_blocks(fields)?;
+        Ok(psl)
+    }
+}
+
+impl<C, T, N> Psl<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Checks that every block list holds `block_count` entries and that
+    /// every block lies within the target span and size, so that the
+    /// blocks can be converted to forward strand positions.
+    fn validate_blocks(&self, fields: &[&str]) -> Result<(), ParseError> {
+        let invalid = |idx: usize, field: &'static str| ParseError::InvalidField {
+            field,
+            value: fields[idx].to_string(),
+        };
+        let count = self.block_count.to_usize();
+        for (idx, field, len) in [
+            (18, "block_sizes", self.block_sizes.len()),
+            (19, "q_starts", self.q_starts.len()),
+            (20, "t_starts", self.t_starts.len()),
+        ] {
+            if count != Some(len) {
+                return Err(invalid(idx, field));
+            }
+        }
+        let reverse = self.target_strand == Some(Strand::Reverse);
+        for (&start, &size) in self.t_starts.iter().zip(&self.block_sizes) {
+            if size > self.t_size || start > self.t_size - size {
+                return Err(invalid(20, "t_starts"));
+            }
+            let (start, end) = if reverse {
+                (self.t_size - start - size, self.t_size - start)
+            } else {
+                (start, start + size)
+            };
+            if start < self.start || end > self.end {
+                return Err(invalid(20, "t_starts"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C, T, N> FromStr for Psl<C, T, N>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    type Err = ParseError;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let chr = parse_field(&fields, 13, "t_name")?;
+        Self::from_fields(chr, &fields)
+    }
+}
+
+impl<C, T, N> Display for Psl<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.matches,
+            self.mismatches,
+            self.rep_matches,
+            self.n_count,
+            self.q_num_insert,
+            self.q_base_insert,
+            self.t_num_insert,
+            self.t_base_insert,
+            self.strand,
+        )?;
+        if let Some(target_strand) = self.target_strand {
+            write!(f, "{target_strand}")?;
+        }
+        write!(
+            f,
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.q_name,
+            self.q_size,
+            self.q_start,
+            self.q_end,
+            self.chr,
+            self.t_size,
+            self.start,
+            self.end,
+            self.block_count,
+        )?;
+        write_list(f, &self.block_sizes)?;
+        write_list(f, &self.q_starts)?;
+        write_list(f, &self.t_starts)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    const ALIGNMENT: &str = "100\t0\t0\t0\t0\t0\t1\t50\t-\tread1\t100\t0\t100\tchr1\t1000\t200\t350\t2\t60,40,\t0,60,\t200,310,";
+    const TRANSLATED: &str = "59\t9\t0\t0\t1\t823\t1\t96\t+-\tFS_CONTIG_48080_1\t1955\t171\t1062\tchr22\t47748585\t13073589\t13073753\t2\t48,20,\t171,1042,\t34674832,34674976,";
+
+    type Alignment = Psl<String, u32, String>;
+
+    #[test]
+    fn test_from_str() {
+        let psl: Alignment = ALIGNMENT.parse().unwrap();
+        assert_eq!(psl.matches(), 100);
+        assert_eq!(psl.t_num_insert(), 1);
+        assert_eq!(psl.t_base_insert(), 50);
+        assert_eq!(psl.strand(), Some(Strand::Reverse));
+        assert_eq!(psl.target_strand(), None);
+        assert_eq!(psl.q_name(), "read1");
+        assert_eq!(psl.q_size(), 100);
+        assert_eq!(psl.chr(), "chr1");
+        assert_eq!(psl.t_size(), 1000);
+        assert_eq!(psl.start(), 200);
+        assert_eq!(psl.end(), 350);
+        assert_eq!(psl.block_count(), 2);
+        assert_eq!(psl.block_sizes(), &vec![60, 40]);
+        assert_eq!(psl.q_starts(), &vec![0, 60]);
+        assert_eq!(psl.t_starts(), &vec![200, 310]);
+    }
+
+    #[test]
+    fn test_display() {
+        let psl: Alignment = ALIGNMENT.parse().unwrap();
+        assert_eq!(format!("{psl}"), ALIGNMENT);
+        let psl: Alignment = TRANSLATED.parse().unwrap();
+        assert_eq!(format!("{psl}"), TRANSLATED);
+    }
+
+    #[test]
+    fn test_translated_blocks() {
+        let psl: Alignment = TRANSLATED.parse().unwrap();
+        assert_eq!(psl.strand(), Some(Strand::Forward));
+        assert_eq!(psl.target_strand(), Some(Strand::Reverse));
+        assert_eq!(
+            psl.target_blocks(),
+            vec![(13_073_589, 13_073_609), (13_073_705, 13_073_753)]
+        );
+        let first = psl.blocks().next().unwrap();
+        assert_eq!(first.start(), psl.start());
+        assert_eq!(first.name(), "FS_CONTIG_48080_1");
+    }
+
+    #[test]
+    fn test_score() {
+        let psl: Alignment = TRANSLATED.parse().unwrap();
+        assert_eq!(psl.score(), Score(Some(48.0)));
+    }
+
+    #[test]
+    fn test_into_bed12() {
+        let psl: Alignment = ALIGNMENT.parse().unwrap();
+        let bed: Bed12<String, u32, String, u32, u32, String, Vec<u32>, Vec<u32>> = psl.into();
+        assert_eq!(
+            format!("{bed}"),
+            "chr1\t200\t350\tread1\t99\t-\t200\t350\t\t2\t60,40,\t0,110,"
+        );
+    }
+
+    #[test]
+    fn test_into_bed6() {
+        let psl: Alignment = ALIGNMENT.parse().unwrap();
+        let bed: Bed6<String, u32, String> = psl.clone().into();
+        assert_eq!(bed.start(), 200);
+        assert_eq!(bed.end(), 350);
+        assert_eq!(bed.strand(), Some(Strand::Reverse));
+        let bed: Bed3<String, u32> = psl.into();
+        assert_eq!(bed.end(), 350);
+    }
+
+    #[test]
+    fn test_invalid_strand() {
+        let line = ALIGNMENT.replace("\t-\t", "\t?\t");
+        let result: Result<Alignment, _> = line.parse();
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidField {
+                field: "strand",
+                ..
+            })
+        ));
+        let result: Result<Alignment, _> = "100\t0".parse();
+        assert!(matches!(
+            result,
+            Err(ParseError::MissingField { field: "t_name" })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_blocks() {
+        let invalid = |line: &str| match line.parse::<Alignment>() {
+            Err(ParseError::InvalidField { field, .. }) => field,
+            other => panic!("expected an invalid field, got {other:?}"),
+        };
+        // Block counts disagreeing with the lists
+        assert_eq!(
+            invalid(&ALIGNMENT.replace("\t2\t60,40,", "\t3\t60,40,")),
+            "block_sizes"
+        );
+        assert_eq!(invalid(&ALIGNMENT.replace("\t0,60,", "\t0,")), "q_starts");
+        assert_eq!(
+            invalid(&ALIGNMENT.replace("\t200,310,", "\t200,")),
+            "t_starts"
+        );
+        // Blocks outside of the target span
+        assert_eq!(
+            invalid(&ALIGNMENT.replace("\t200,310,", "\t100,310,")),
+            "t_starts"
+        );
+        assert_eq!(
+            invalid(&ALIGNMENT.replace("\t200,310,", "\t200,320,")),
+            "t_starts"
+        );
+        // Reverse strand blocks beyond the target size
+        assert_eq!(
+            invalid(&TRANSLATED.replace("34674832,34674976,", "34674832,47748600,")),
+            "t_starts"
+        );
+    }
+}
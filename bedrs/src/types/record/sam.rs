@@ -0,0 +1,344 @@
+use crate::{
+    traits::{
+        fields::{get_field, parse_field, parse_value},
+        ChromBounds, FromFields, MetaBounds, ParseError, ToFields, ValueBounds,
+    },
+    types::{Cigar, Score},
+    Bed3, Bed6, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{CopyGetters, Getters, Setters};
+use num_traits::One;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Flag bit of a segment with multiple segments in sequencing
+const FLAG_PAIRED: u16 = 0x1;
+/// Flag bit of an unmapped segment
+const FLAG_UNMAPPED: u16 = 0x4;
+/// Flag bit of a reverse complemented segment
+const FLAG_REVERSE: u16 = 0x10;
+/// Flag bit of a secondary alignment
+const FLAG_SECONDARY: u16 = 0x100;
+/// Flag bit of a PCR or optical duplicate
+const FLAG_DUPLICATE: u16 = 0x400;
+/// Flag bit of a supplementary alignment
+const FLAG_SUPPLEMENTARY: u16 = 0x800;
+
+/// The reference footprint of a [SAM](https://samtools.github.io/hts-specs/SAMv1.pdf)
+/// alignment.
+///
+/// Has seven values
+///     1. `chr` - the reference name (`RNAME`)
+///     2. `start` - the 0-based alignment start (`POS - 1`)
+///     3. `end` - the end of the reference span of the `CIGAR`
+///     4. `name` - the read name (`QNAME`)
+///     5. `flag` - the bitwise `FLAG`
+///     6. `mapq` - the mapping quality (`MAPQ`)
+///     7. `strand` - [`Strand::Reverse`] if the reverse flag is set,
+///        otherwise [`Strand::Forward`]
+///
+/// Records are parsed from the columns of a SAM line, either with
+/// [`FromStr`] or the [`SamReader`](crate::io::SamReader), and written as
+/// BED6 with the mapping quality as the score and the flag as a seventh
+/// column.
+/// [`FromFields`] reads back this written layout rather than SAM columns.
+///
+/// ```
+/// use bedrs::{Coordinates, Sam, Strand};
+///
+/// let line = "read1\t16\tchr1\t101\t60\t10M50N10M\t*\t0\t0\t*\t*";
+/// let sam: Sam<String, u32, String> = line.parse().unwrap();
+/// assert_eq!((sam.start(), sam.end()), (100, 170));
+/// assert_eq!(sam.strand(), Some(Strand::Reverse));
+/// assert_eq!(sam.mapq(), 60);
+/// assert_eq!(format!("{sam}"), "chr1\t100\t170\tread1\t60\t-\t16");
+/// ```
+#[derive(Debug, Default, Clone, Coordinates, new, Getters, Setters, CopyGetters)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sam<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    chr: C,
+    start: T,
+    end: T,
+    #[getset(get = "pub", set = "pub")]
+    name: N,
+    #[getset(get_copy = "pub", set = "pub")]
+    flag: u16,
+    #[getset(get_copy = "pub", set = "pub")]
+    mapq: u8,
+    strand: Strand,
+}
+
+impl<C, T, N> Sam<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    /// Whether the read has multiple segments in sequencing
+    pub fn is_paired(&self) -> bool {
+        self.flag & FLAG_PAIRED != 0
+    }
+
+    /// Whether the alignment is secondary
+    pub fn is_secondary(&self) -> bool {
+        self.flag & FLAG_SECONDARY != 0
+    }
+
+    /// Whether the alignment is supplementary
+    pub fn is_supplementary(&self) -> bool {
+        self.flag & FLAG_SUPPLEMENTARY != 0
+    }
+
+    /// Whether the read is a PCR or optical duplicate
+    pub fn is_duplicate(&self) -> bool {
+        self.flag & FLAG_DUPLICATE != 0
+    }
+}
+
+impl<C, T, N> Sam<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    /// Parses the footprint of an alignment along with its CIGAR
+    pub(crate) fn parse_alignment(chr: C, fields: &[&str]) -> Result<(Self, Cigar), ParseError> {
+        let flag: u16 = parse_field(fields, 1, "flag")?;
+        let raw_pos = get_field(fields, 3, "pos")?;
+        let pos: T = parse_value(raw_pos, "pos")?;
+        if pos < T::one() {
+            return Err(ParseError::InvalidField {
+                field: "pos",
+                value: raw_pos.to_string(),
+            });
+        }
+        let raw_cigar = get_field(fields, 5, "cigar")?;
+        let cigar: Cigar = parse_value(raw_cigar, "cigar")?;
+        let start = pos - T::one();
+        let end = T::from_u64(cigar.reference_len())
+            .map(|len| start + len)
+            .ok_or_else(|| ParseError::InvalidField {
+                field: "cigar",
+                value: raw_cigar.to_string(),
+            })?;
+        let strand = if flag & FLAG_REVERSE == 0 {
+            Strand::Forward
+        } else {
+            Strand::Reverse
+        };
+        let record = Self::new(
+            chr,
+            start,
+            end,
+            parse_field(fields, 0, "qname")?,
+            flag,
+            parse_field(fields, 4, "mapq")?,
+            strand,
+        );
+        Ok((record, cigar))
+    }
+}
+
+/// Whether the columns of a SAM line describe an unmapped read, which has
+/// no reference footprint
+pub(crate) fn is_unmapped(fields: &[&str]) -> bool {
+    let flag = fields.get(1).and_then(|x| x.parse::<u16>().ok());
+    flag.is_some_and(|flag| flag & FLAG_UNMAPPED != 0)
+        || fields.get(2) == Some(&"*")
+        || fields.get(5) == Some(&"*")
+}
+
+/// Reads the BED6 layout written by [`ToFields`], with the flag as a
+/// seventh column.
+impl<C, T, N> FromFields<C, T> for Sam<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + FromStr,
+    N: MetaBounds + FromStr,
+{
+    const STRAND_FIELD: Option<usize> = Some(5);
+
+    fn from_fields(chr: C, fields: &[&str]) -> Result<Self, ParseError> {
+        let start = parse_field(fields, 1, "start")?;
+        let end = parse_field(fields, 2, "end")?;
+        let name = parse_field(fields, 3, "name")?;
+        let mapq = parse_field(fields, 4, "mapq")?;
+        let strand = parse_field(fields, 5, "strand")?;
+        let flag = parse_field(fields, 6, "flag")?;
+        Ok(Self::new(chr, start, end, name, flag, mapq, strand))
+    }
+}
+
+/// Parses the columns of a SAM line, with the chromosome taken from the
+/// reference name (column 3)
+impl<C, T, N> FromStr for Sam<C, T, N>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+    N: MetaBounds + FromStr,
+{
+    type Err = ParseError;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let chr = parse_field(&fields, 2, "rname")?;
+        Self::parse_alignment(chr, &fields).map(|(record, _)| record)
+    }
+}
+
+impl<C, T, N> ToFields<C, T> for Sam<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(
+            wtr,
+            "\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.start, self.end, self.name, self.mapq, self.strand, self.flag
+        )
+    }
+}
+
+impl<C, T, N> Display for Sam<C, T, N>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+    N: MetaBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+impl<C, T, N> From<Sam<C, T, N>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(sam: Sam<C, T, N>) -> Self {
+        Bed3::new(sam.chr, sam.start, sam.end)
+    }
+}
+
+impl<C, T, N> From<Sam<C, T, N>> for Bed6<C, T, N>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+    N: MetaBounds,
+{
+    fn from(sam: Sam<C, T, N>) -> Self {
+        let score = Score(Some(f64::from(sam.mapq)));
+        Bed6::new(sam.chr, sam.start, sam.end, sam.name, score, sam.strand)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    type Alignment = Sam<String, u32, String>;
+
+    #[test]
+    fn test_from_str() {
+        let sam: Alignment = "read1\t0\tchr1\t1\t30\t5S20M\t*\t0\t0\tACGT\t*"
+            .parse()
+            .unwrap();
+        assert_eq!(sam.chr(), "chr1");
+        assert_eq!((sam.start(), sam.end()), (0, 20));
+        assert_eq!(sam.name(), "read1");
+        assert_eq!(sam.flag(), 0);
+        assert_eq!(sam.mapq(), 30);
+        assert_eq!(sam.strand(), Some(Strand::Forward));
+    }
+
+    #[test]
+    fn test_parse_alignment() {
+        let fields = ["read1", "16", "chr1", "11", "60", "10M5D10M"];
+        let (sam, cigar) = Alignment::parse_alignment("chr1".to_string(), &fields).unwrap();
+        assert_eq!((sam.start(), sam.end()), (10, 35));
+        assert_eq!(sam.strand(), Some(Strand::Reverse));
+        assert_eq!(cigar.blocks(), vec![(0, 10), (15, 25)]);
+    }
+
+    #[test]
+    fn test_fields_round_trip() {
+        let sam: Alignment = "read1\t1040\tchr1\t11\t60\t10M5D10M".parse().unwrap();
+        let line = format!("{sam}");
+        assert_eq!(line, "chr1\t10\t35\tread1\t60\t-\t1040");
+        let fields: Vec<&str> = line.split('\t').collect();
+        let parsed = Alignment::from_fields("chr1".to_string(), &fields).unwrap();
+        assert_eq!((parsed.start(), parsed.end()), (10, 35));
+        assert_eq!(parsed.name(), "read1");
+        assert_eq!(parsed.flag(), 1040);
+        assert_eq!(parsed.mapq(), 60);
+        assert_eq!(parsed.strand(), Some(Strand::Reverse));
+        assert!(parsed.is_duplicate());
+        assert!(Alignment::from_fields("chr1".to_string(), &fields[..6]).is_err());
+    }
+
+    #[test]
+    fn test_flags() {
+        let sam: Alignment = "read1\t3361\tchr1\t1\t0\t5M".parse().unwrap();
+        assert!(sam.is_paired());
+        assert!(sam.is_secondary());
+        assert!(sam.is_supplementary());
+        assert!(sam.is_duplicate());
+        assert_eq!(sam.strand(), Some(Strand::Forward));
+
+        let sam: Alignment = "read1\t0\tchr1\t1\t0\t5M".parse().unwrap();
+        assert!(!sam.is_paired());
+        assert!(!sam.is_secondary());
+        assert!(!sam.is_supplementary());
+        assert!(!sam.is_duplicate());
+    }
+
+    #[test]
+    fn test_is_unmapped() {
+        assert!(is_unmapped(&["r", "4", "chr1", "1", "0", "5M"]));
+        assert!(is_unmapped(&["r", "0", "*", "0", "0", "*"]));
+        assert!(is_unmapped(&["r", "0", "chr1", "1", "0", "*"]));
+        assert!(!is_unmapped(&["r", "16", "chr1", "1", "0", "5M"]));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let result: Result<Alignment, _> = "read1\t0\tchr1\t0\t30\t5M".parse();
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidField { field: "pos", .. })
+        ));
+        let result: Result<Alignment, _> = "read1\t0\tchr1\t1\t30\t5Q".parse();
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidField { field: "cigar", .. })
+        ));
+        let result: Result<Alignment, _> = "read1\t0\tchr1\t1\t300\t5M".parse();
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidField { field: "mapq", .. })
+        ));
+    }
+
+    #[test]
+    fn test_into_bed() {
+        let sam: Alignment = "read1\t16\tchr1\t1\t30\t5M".parse().unwrap();
+        let bed: Bed6<String, u32, String> = sam.clone().into();
+        assert_eq!(format!("{bed}\t16"), format!("{sam}"));
+        let bed: Bed3<String, u32> = sam.into();
+        assert_eq!(bed.end(), 5);
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
-    traits::{ChromBounds, Coordinates, ValueBounds},
+    traits::{region::parse_region, ChromBounds, Coordinates, ParseError, ValueBounds},
     Strand,
 };
 use bedrs_derive::Coordinates;
 use derive_new::new;
+use num_traits::One;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// A representation of a Genomic Interval.
 ///
@@ -39,6 +41,38 @@ where
     strand: Strand,
 }
 
+impl<C, T> FromStr for StrandedBed3<C, T>
+where
+    C: ChromBounds + FromStr,
+    T: ValueBounds + One + FromStr,
+{
+    type Err = ParseError;
+
+    /// Parses a samtools / UCSC style region string with an optional
+    /// strand suffix (`chr1:1,001-2,000(+)`).
+    ///
+    /// Positions are 1-based and inclusive and are converted to the
+    /// half-open convention.
+    /// A bare chromosome name (`chr1`) spans from zero to the maximum
+    /// value of `T`, and a missing strand suffix is parsed as
+    /// [`Strand::Unknown`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bedrs::{Coordinates, Strand, StrandedBed3};
+    ///
+    /// let iv: StrandedBed3<String, u32> = "chr1:1,001-2,000(-)".parse().unwrap();
+    /// assert_eq!(iv.start(), 1000);
+    /// assert_eq!(iv.end(), 2000);
+    /// assert_eq!(iv.strand(), Some(Strand::Reverse));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chr, start, end, strand) = parse_region(s)?;
+        Ok(Self::new(chr, start, end, strand))
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use crate::{
@@ -50,6 +84,20 @@ mod testing {
     use bincode::{deserialize, serialize};
     use std::cmp::Ordering;
 
+    #[test]
+    fn test_from_str() {
+        let interval: StrandedBed3<String, u32> = "chr1:11-100(+)".parse().unwrap();
+        assert_eq!(interval.chr(), "chr1");
+        assert_eq!(interval.start(), 10);
+        assert_eq!(interval.end(), 100);
+        assert_eq!(interval.strand(), Some(Strand::Forward));
+        let interval: StrandedBed3<String, u32> = "chr1:11-100".parse().unwrap();
+        assert_eq!(interval.strand(), Some(Strand::Unknown));
+        assert!("chr1:11-100(*)"
+            .parse::<StrandedBed3<String, u32>>()
+            .is_err());
+    }
+
     #[test]
     fn test_interval_init() {
         let interval = StrandedBed3::new(1, 10, 100, Strand::Forward);
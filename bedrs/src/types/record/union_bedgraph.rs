@@ -0,0 +1,114 @@
+use crate::{
+    traits::{ChromBounds, ToFields, ValueBounds},
+    Bed3, Coordinates, Strand,
+};
+use bedrs_derive::Coordinates;
+use derive_new::new;
+use getset::{Getters, Setters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// A row of the union of multiple `BedGraph` tracks.
+///
+/// Has four values
+///     1. `chr`
+///     2. `start`
+///     3. `end`
+///     4. `scores` - the score of each track over the interval
+///
+/// Rows are written as with `bedtools unionbedg`, with one score column
+/// per track.
+///
+/// ```
+/// use bedrs::{Coordinates, UnionBedGraph};
+///
+/// let row = UnionBedGraph::new("chr1", 20, 30, vec![1.5, 0.0, 2.0]);
+/// assert_eq!(row.start(), 20);
+/// assert_eq!(row.score(2), Some(2.0));
+/// assert_eq!(format!("{row}"), "chr1\t20\t30\t1.5\t0\t2");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Coordinates, Getters, Setters, new)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnionBedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    chr: C,
+    start: T,
+    end: T,
+    #[getset(get = "pub", set = "pub")]
+    scores: Vec<f64>,
+}
+
+impl<C, T> UnionBedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    /// Returns the score of the track at the index
+    pub fn score(&self, idx: usize) -> Option<f64> {
+        self.scores.get(idx).copied()
+    }
+}
+
+impl<C, T> From<UnionBedGraph<C, T>> for Bed3<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds,
+{
+    fn from(row: UnionBedGraph<C, T>) -> Self {
+        Self::new(row.chr, row.start, row.end)
+    }
+}
+
+impl<C, T> ToFields<C, T> for UnionBedGraph<C, T>
+where
+    C: ChromBounds,
+    T: ValueBounds + Display,
+{
+    fn write_fields<W: fmt::Write>(&self, wtr: &mut W) -> fmt::Result {
+        write!(wtr, "\t{}\t{}", self.start, self.end)?;
+        for score in &self.scores {
+            write!(wtr, "\t{score}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<C, T> Display for UnionBedGraph<C, T>
+where
+    C: ChromBounds + Display,
+    T: ValueBounds + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)?;
+        self.write_fields(f)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let row = UnionBedGraph::new(1, 20, 30, vec![0.5, 3.0]);
+        assert_eq!(format!("{row}"), "1\t20\t30\t0.5\t3");
+    }
+
+    #[test]
+    fn test_score() {
+        let row = UnionBedGraph::new(1, 20, 30, vec![0.5, 3.0]);
+        assert_eq!(row.score(0), Some(0.5));
+        assert_eq!(row.score(2), None);
+    }
+
+    #[test]
+    fn test_bed3_conversion() {
+        let row = UnionBedGraph::new("chr1", 20, 30, vec![1.0]);
+        let bed: Bed3<_, _> = row.into();
+        assert_eq!((bed.start(), bed.end()), (20, 30));
+    }
+}